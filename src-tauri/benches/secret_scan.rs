@@ -0,0 +1,176 @@
+// 密钥扫描基准：cargo bench --bench secret_scan [-- <MB>...]
+//
+// 生成几 MB 的合成输入（源码、日志、压缩后的单行 JS），分别测量：
+//   - scan_with 的端到端吞吐（多线程，按片段并行，包括编码片段的解码和重新扫描）
+//   - 单线程下每个 16 KB 片段的规则匹配：逐条规则执行（关键字区分大小写的 contains 判断）
+//     与 Aho-Corasick / RegexSet 预筛选后只执行候选规则的对比
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use ctxrun_lib::gitleaks::{self, RuleSet};
+
+const FRAGMENT_SIZE: usize = 16 * 1024;
+const DEFAULT_SIZES_MB: &[usize] = &[4, 16];
+/// 每项至少运行这么久，取平均
+const MIN_BENCH_TIME: Duration = Duration::from_secs(2);
+
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    fn below(&mut self, n: usize) -> usize {
+        (self.next() % n as u64) as usize
+    }
+
+    fn token(&mut self, charset: &[u8], len: usize) -> String {
+        (0..len).map(|_| charset[self.below(charset.len())] as char).collect()
+    }
+}
+
+const ALNUM: &[u8] = b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789";
+const UPPER_DIGITS: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+const IDENTS: &[&str] = &[
+    "user", "config", "request", "response", "buffer", "index", "value", "result", "handler", "options",
+    "client", "session", "payload", "context", "items", "count", "filter", "render", "update", "token_count",
+];
+
+fn secret(rng: &mut Rng) -> String {
+    match rng.below(4) {
+        0 => format!("ghp_{}", rng.token(ALNUM, 36)),
+        1 => format!("AKIA{}", rng.token(UPPER_DIGITS, 16)),
+        2 => format!("sk_live_{}", rng.token(ALNUM, 24)),
+        _ => format!("xoxb-{}-{}-{}", rng.token(b"0123456789", 12), rng.token(b"0123456789", 12), rng.token(ALNUM, 24)),
+    }
+}
+
+/// 普通源码，约每 2000 行一个明文密钥、一个 base64 编码的密钥
+fn source_code(rng: &mut Rng, size: usize) -> String {
+    let mut out = String::with_capacity(size + 256);
+    while out.len() < size {
+        let a = IDENTS[rng.below(IDENTS.len())];
+        let b = IDENTS[rng.below(IDENTS.len())];
+        match rng.below(2000) {
+            0 => out.push_str(&format!("const apiKey = \"{}\";\n", secret(rng))),
+            1 => out.push_str(&format!("  token: {}\n", STANDARD.encode(format!("API_TOKEN={}", secret(rng))))),
+            n if n % 7 == 0 => out.push_str(&format!("    // TODO: refactor {} before merging {}\n", a, b)),
+            n if n % 5 == 0 => out.push_str(&format!("export function {}_{}({}) {{\n", a, b, a)),
+            n if n % 3 == 0 => out.push_str(&format!("    if ({}.length > {}) {{ return {}; }}\n", a, n, b)),
+            n => out.push_str(&format!("    let {} = {}.get(\"{}_{}\") ?? {};\n", a, b, a, n, n * 31)),
+        }
+    }
+    out
+}
+
+/// 大写关键字的日志，关键字判断区分大小写时会漏掉这些密钥
+fn logs(rng: &mut Rng, size: usize) -> String {
+    let mut out = String::with_capacity(size + 256);
+    let mut ts = 1_700_000_000u64;
+    while out.len() < size {
+        ts += rng.below(5) as u64;
+        let a = IDENTS[rng.below(IDENTS.len())];
+        match rng.below(1500) {
+            0 => out.push_str(&format!("{} WARN  env GITHUB_TOKEN={} loaded\n", ts, secret(rng))),
+            n => out.push_str(&format!("{} INFO  [{}] handled request id={} in {}ms\n", ts, a, rng.token(ALNUM, 12), n % 400)),
+        }
+    }
+    out
+}
+
+/// 压缩后的单行 JS，大量无关的高熵字符串
+fn minified(rng: &mut Rng, size: usize) -> String {
+    let mut out = String::with_capacity(size + 256);
+    while out.len() < size {
+        let a = IDENTS[rng.below(IDENTS.len())];
+        out.push_str(&format!("var {}=\"{}\";function {}(e){{return e.{}||\"{}\"}};", a, rng.token(ALNUM, 20), a, a, rng.token(ALNUM, 8)));
+    }
+    out
+}
+
+fn fragments(text: &str) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut start = 0;
+    while start < text.len() {
+        let mut end = (start + FRAGMENT_SIZE).min(text.len());
+        while !text.is_char_boundary(end) {
+            end -= 1;
+        }
+        parts.push(&text[start..end]);
+        start = end;
+    }
+    parts
+}
+
+/// 原来的做法：每条规则先做区分大小写的关键字判断，再单独执行正则
+fn naive_matches(rules: &RuleSet, fragment: &str) -> usize {
+    rules.rules.iter()
+        .filter(|r| r.keywords.is_empty() || r.keywords.iter().any(|k| fragment.contains(k.as_str())))
+        .map(|r| r.regex.find_iter(fragment).count())
+        .sum()
+}
+
+fn prefiltered_matches(rules: &RuleSet, fragment: &str) -> usize {
+    rules.prefilter().candidates(fragment).into_iter()
+        .map(|i| rules.rules[i].regex.find_iter(fragment).count())
+        .sum()
+}
+
+/// 返回每次运行的平均耗时和最后一次的结果
+fn measure<T>(mut f: impl FnMut() -> T) -> (Duration, T) {
+    let mut runs = 0u32;
+    let started = Instant::now();
+    loop {
+        let result = black_box(f());
+        runs += 1;
+        if started.elapsed() >= MIN_BENCH_TIME {
+            return (started.elapsed() / runs, result);
+        }
+    }
+}
+
+fn report(name: &str, bytes: usize, elapsed: Duration, detail: String) {
+    let mb_per_sec = bytes as f64 / (1024.0 * 1024.0) / elapsed.as_secs_f64();
+    println!("  {:<28} {:>10.2?} {:>9.1} MB/s  {}", name, elapsed, mb_per_sec, detail);
+}
+
+fn main() {
+    let sizes: Vec<usize> = std::env::args().skip(1)
+        .filter_map(|a| a.parse().ok())
+        .collect();
+    let sizes = if sizes.is_empty() { DEFAULT_SIZES_MB.to_vec() } else { sizes };
+
+    let rules = RuleSet::builtin();
+    let mut rng = Rng(0x9e37_79b9_7f4a_7c15);
+    // 构建预筛选器，不计入耗时
+    rules.prefilter();
+
+    type Generator = fn(&mut Rng, usize) -> String;
+    let inputs: &[(&str, Generator)] = &[("source", source_code), ("logs", logs), ("minified", minified)];
+
+    for &mb in &sizes {
+        for (name, generate) in inputs {
+            let text = generate(&mut rng, mb * 1024 * 1024);
+            let parts = fragments(&text);
+            println!("{} ({} MB, {} fragments, {} rules)", name, mb, parts.len(), rules.rules.len());
+
+            let (elapsed, found) = measure(|| gitleaks::scan_with(&rules, &text, Some("bench.js")).len());
+            report("scan_with", text.len(), elapsed, format!("{} secrets", found));
+
+            let (elapsed, found) = measure(|| parts.iter().map(|p| naive_matches(&rules, p)).sum::<usize>());
+            report("per-rule (1 thread)", text.len(), elapsed, format!("{} raw matches", found));
+
+            let (elapsed, found) = measure(|| parts.iter().map(|p| prefiltered_matches(&rules, p)).sum::<usize>());
+            report("prefiltered (1 thread)", text.len(), elapsed, format!("{} raw matches", found));
+
+            let (elapsed, candidates) = measure(|| parts.iter().map(|p| rules.prefilter().candidates(p).len()).sum::<usize>());
+            report("prefilter only (1 thread)", text.len(), elapsed, format!("{:.1} candidate rules / fragment", candidates as f64 / parts.len() as f64));
+        }
+    }
+}
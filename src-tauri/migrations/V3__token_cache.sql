@@ -0,0 +1,17 @@
+-- src-tauri/migrations/V3__token_cache.sql
+
+CREATE TABLE IF NOT EXISTS token_cache (
+    path TEXT NOT NULL,
+    tokenizer TEXT NOT NULL,
+    remove_comments INTEGER NOT NULL,
+    keep_doc_comments INTEGER NOT NULL,
+    mtime INTEGER NOT NULL,
+    size INTEGER NOT NULL,
+    hash TEXT NOT NULL,
+    tokens INTEGER NOT NULL,
+    bytes INTEGER NOT NULL,
+    last_used INTEGER NOT NULL,
+    PRIMARY KEY (path, tokenizer, remove_comments, keep_doc_comments)
+);
+
+CREATE INDEX IF NOT EXISTS idx_token_cache_last_used ON token_cache(last_used);
@@ -0,0 +1,21 @@
+-- src-tauri/migrations/V4__token_cache_format.sql
+-- 输出格式不同，文件块的 token 数也不同，需要加入缓存键。缓存可以随时重建，直接重建表
+
+DROP TABLE IF EXISTS token_cache;
+
+CREATE TABLE token_cache (
+    path TEXT NOT NULL,
+    tokenizer TEXT NOT NULL,
+    format TEXT NOT NULL,
+    remove_comments INTEGER NOT NULL,
+    keep_doc_comments INTEGER NOT NULL,
+    mtime INTEGER NOT NULL,
+    size INTEGER NOT NULL,
+    hash TEXT NOT NULL,
+    tokens INTEGER NOT NULL,
+    bytes INTEGER NOT NULL,
+    last_used INTEGER NOT NULL,
+    PRIMARY KEY (path, tokenizer, format, remove_comments, keep_doc_comments)
+);
+
+CREATE INDEX IF NOT EXISTS idx_token_cache_last_used ON token_cache(last_used);
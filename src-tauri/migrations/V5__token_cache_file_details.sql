@@ -0,0 +1,23 @@
+-- src-tauri/migrations/V5__token_cache_file_details.sql
+-- 统计面板需要每个文件的行数和状态（正常、二进制、过大、出错），一并缓存。缓存可以随时重建，直接重建表
+
+DROP TABLE IF EXISTS token_cache;
+
+CREATE TABLE token_cache (
+    path TEXT NOT NULL,
+    tokenizer TEXT NOT NULL,
+    format TEXT NOT NULL,
+    remove_comments INTEGER NOT NULL,
+    keep_doc_comments INTEGER NOT NULL,
+    mtime INTEGER NOT NULL,
+    size INTEGER NOT NULL,
+    hash TEXT NOT NULL,
+    tokens INTEGER NOT NULL,
+    bytes INTEGER NOT NULL,
+    lines INTEGER NOT NULL,
+    status TEXT NOT NULL,
+    last_used INTEGER NOT NULL,
+    PRIMARY KEY (path, tokenizer, format, remove_comments, keep_doc_comments)
+);
+
+CREATE INDEX IF NOT EXISTS idx_token_cache_last_used ON token_cache(last_used);
@@ -0,0 +1,24 @@
+-- src-tauri/migrations/V6__token_cache_encoding.sql
+-- 缓存每个文件检测到的编码。旧缓存是按 UTF-8 有损解码统计的，非 UTF-8 文件的 token 数不准，直接重建表
+
+DROP TABLE IF EXISTS token_cache;
+
+CREATE TABLE token_cache (
+    path TEXT NOT NULL,
+    tokenizer TEXT NOT NULL,
+    format TEXT NOT NULL,
+    remove_comments INTEGER NOT NULL,
+    keep_doc_comments INTEGER NOT NULL,
+    mtime INTEGER NOT NULL,
+    size INTEGER NOT NULL,
+    hash TEXT NOT NULL,
+    tokens INTEGER NOT NULL,
+    bytes INTEGER NOT NULL,
+    lines INTEGER NOT NULL,
+    status TEXT NOT NULL,
+    encoding TEXT,
+    last_used INTEGER NOT NULL,
+    PRIMARY KEY (path, tokenizer, format, remove_comments, keep_doc_comments)
+);
+
+CREATE INDEX IF NOT EXISTS idx_token_cache_last_used ON token_cache(last_used);
@@ -0,0 +1,36 @@
+-- src-tauri/migrations/V7__secret_allowlist_scopes.sql
+-- 白名单条目支持按规则、路径 glob、项目、指纹（file:rule:line）限定范围，可以用正则匹配密钥值，可以设置过期时间。
+-- value 不再必填（只按指纹或路径放行的条目没有值），需要重建表去掉 NOT NULL UNIQUE 约束。
+-- 一条条目中所有非空的条件都满足时才放行。
+
+CREATE TABLE ignored_secrets_new (
+    id TEXT PRIMARY KEY,
+    value TEXT,
+    value_regex TEXT,
+    rule_id TEXT,
+    path_glob TEXT,
+    project_root TEXT,
+    fingerprint TEXT,
+    expires_at INTEGER,
+    -- 加入白名单时报告该密钥的规则，只用于显示，不限定范围
+    detected_by TEXT,
+    created_at INTEGER
+);
+
+-- 旧表的 rule_id 只是记录发现时的规则，并不限定范围：保存到 detected_by，迁移后旧条目仍对所有规则生效
+INSERT INTO ignored_secrets_new (id, value, detected_by, created_at)
+SELECT id, value, rule_id, created_at FROM ignored_secrets;
+
+DROP TABLE ignored_secrets;
+ALTER TABLE ignored_secrets_new RENAME TO ignored_secrets;
+
+-- 相同范围的条目只保留一条（add_ignored_secrets 以该索引为冲突目标，重复添加时只更新过期时间）
+CREATE UNIQUE INDEX IF NOT EXISTS idx_ignored_scope ON ignored_secrets (
+    COALESCE(value, ''),
+    COALESCE(value_regex, ''),
+    COALESCE(rule_id, ''),
+    COALESCE(path_glob, ''),
+    COALESCE(project_root, ''),
+    COALESCE(fingerprint, '')
+);
+CREATE INDEX IF NOT EXISTS idx_ignored_expires ON ignored_secrets (expires_at);
@@ -0,0 +1,746 @@
+//! 无窗口的命令行入口，与桌面应用共用 context / gitleaks / git / export / db 模块，
+//! 方便在终端和 CI 中执行同样的操作。
+//!
+//! 桌面应用的二进制已经叫 `ctxrun`，所以这里命名为 `ctxrun-cli`。
+
+use std::collections::HashSet;
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::sync::atomic::AtomicBool;
+
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use git2::Repository;
+use rusqlite::{Connection, OpenFlags};
+use serde::Serialize;
+
+use ctxrun_lib::context::budget::{self, BudgetOptions, OverflowStrategy};
+use ctxrun_lib::context::core::{ProcessOptions, SkeletonOptions};
+use ctxrun_lib::context::format::{ContextHeader, OutputFormat};
+use ctxrun_lib::context::redact::Redactor;
+use ctxrun_lib::context::repo_map::{self, RepoMapOptions};
+use ctxrun_lib::context::stream;
+use ctxrun_lib::context::tokenizer::TokenizerKind;
+use ctxrun_lib::context::walker::{self, NodeKind, WalkNode};
+use ctxrun_lib::db::{self, ProjectConfig};
+use ctxrun_lib::notebook::NotebookOptions;
+use ctxrun_lib::gitleaks::directory::{self, DirectoryScanOptions, FileSecretMatch};
+use ctxrun_lib::gitleaks::report::{self, ReportFinding, ReportFormat};
+use ctxrun_lib::gitleaks::{confidence, history, hook, Encoding, IgnoreSet, RuleError, RuleSet, Severity};
+use ctxrun_lib::{charset, export, git, gitleaks};
+
+/// 与 tauri.conf.json 中的 identifier 一致，应用数据目录为 `<local data>/<identifier>`
+const APP_IDENTIFIER: &str = "com.ctxrun";
+
+#[derive(Parser)]
+#[command(name = "ctxrun-cli", version, about = "CtxRun command line tools")]
+struct Cli {
+    /// App database path (defaults to the desktop app database)
+    #[arg(long, global = true, value_name = "PATH")]
+    db: Option<PathBuf>,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Assemble files and directories into an LLM context document
+    Context(ContextArgs),
+    /// Scan a file or directory for secrets; exits with 1 when any are found
+    Scan(ScanArgs),
+    /// Export the diff between two commits
+    Diff(DiffArgs),
+    /// Print a map of the top-level symbols in a project, most referenced first
+    RepoMap(RepoMapArgs),
+    /// Saved prompts
+    Prompt {
+        #[command(subcommand)]
+        command: PromptCommand,
+    },
+}
+
+#[derive(Args)]
+struct ContextArgs {
+    /// Files or directories (directories respect .gitignore)
+    #[arg(required = true)]
+    paths: Vec<PathBuf>,
+    #[arg(long, value_enum, default_value_t = FormatArg::Xml)]
+    format: FormatArg,
+    /// Fit the context into this many tokens
+    #[arg(long, value_name = "TOKENS")]
+    budget: Option<usize>,
+    /// What to do with files that do not fit the budget
+    #[arg(long, value_enum, default_value_t = OverflowArg::Truncate, requires = "budget")]
+    overflow: OverflowArg,
+    /// Paths or directory prefixes to include first when using --budget
+    #[arg(long, requires = "budget")]
+    priority: Vec<String>,
+    #[arg(long, value_enum, default_value_t = TokenizerArg::Cl100k)]
+    tokenizer: TokenizerArg,
+    #[arg(long)]
+    remove_comments: bool,
+    /// Keep doc comments when removing comments
+    #[arg(long, requires = "remove_comments")]
+    keep_doc_comments: bool,
+    /// Reduce source files larger than this to signatures only
+    #[arg(long, value_name = "BYTES")]
+    skeleton_threshold: Option<u64>,
+    /// Include text outputs of Jupyter notebook cells, truncated to this many characters each
+    #[arg(long, value_name = "CHARS")]
+    notebook_outputs: Option<usize>,
+    /// Prepend a repository symbol map of at most this many tokens
+    #[arg(long, value_name = "TOKENS")]
+    repo_map: Option<usize>,
+    /// Replace detected secrets with [REDACTED:rule#n] placeholders
+    #[arg(long)]
+    redact: bool,
+    /// Write to a file instead of stdout
+    #[arg(short, long, value_name = "FILE")]
+    output: Option<PathBuf>,
+}
+
+#[derive(Args)]
+struct ScanArgs {
+    path: PathBuf,
+    /// Print findings as JSON
+    #[arg(long, conflicts_with = "report")]
+    json: bool,
+    /// Write a SARIF, gitleaks-compatible JSON or CSV report instead of the finding list
+    #[arg(long, value_enum, value_name = "FORMAT")]
+    report: Option<ReportFormatArg>,
+    /// Write the report to FILE instead of stdout
+    #[arg(short, long, value_name = "FILE", requires = "report")]
+    output: Option<PathBuf>,
+    /// Ignore the allowlist stored in the app database (.gitleaksignore is still honored)
+    #[arg(long)]
+    no_allowlist: bool,
+    /// Additional gitleaks-compatible TOML config, applied after the app and repository configs
+    #[arg(long, value_name = "FILE")]
+    config: Option<PathBuf>,
+    /// Scan the lines added by every commit in the repository history instead of the working tree
+    #[arg(long, conflicts_with = "staged")]
+    history: bool,
+    /// Limit --history to a revision range (a..b) or a single ref and its ancestors
+    #[arg(long, value_name = "RANGE", requires = "history")]
+    range: Option<String>,
+    /// Scan only the lines added in the git index (used by the pre-commit hook)
+    #[arg(long)]
+    staged: bool,
+    /// Drop findings whose confidence score (0-100) is below this value
+    #[arg(long, value_name = "SCORE", value_parser = clap::value_parser!(u8).range(0..=100))]
+    min_confidence: Option<u8>,
+    /// Order of the findings
+    #[arg(long, value_enum, default_value_t = SortArg::Position)]
+    sort: SortArg,
+}
+
+#[derive(Args)]
+struct DiffArgs {
+    /// Old revision (commit, branch, tag, HEAD~1 ...)
+    old: String,
+    /// New revision, or WORKDIR for uncommitted changes
+    new: String,
+    /// Repository path
+    #[arg(long, default_value = ".")]
+    repo: PathBuf,
+    #[arg(long, value_enum, default_value_t = DiffFormatArg::Markdown)]
+    format: DiffFormatArg,
+    #[arg(long, value_enum, default_value_t = LayoutArg::Unified)]
+    layout: LayoutArg,
+    /// Only export these paths (relative to the repository root)
+    #[arg(long = "path", value_name = "PATH")]
+    paths: Vec<String>,
+    #[arg(short, long, value_name = "FILE")]
+    output: Option<PathBuf>,
+}
+
+#[derive(Args)]
+struct RepoMapArgs {
+    #[arg(default_value = ".")]
+    path: PathBuf,
+    /// Keep only the most referenced symbols that fit into this many tokens
+    #[arg(long, value_name = "TOKENS")]
+    max_tokens: Option<usize>,
+    #[arg(long, value_enum, default_value_t = TokenizerArg::Cl100k)]
+    tokenizer: TokenizerArg,
+    /// Print files and symbols as JSON
+    #[arg(long)]
+    json: bool,
+}
+
+#[derive(Subcommand)]
+enum PromptCommand {
+    /// Print a saved prompt by title (case-insensitive)
+    Get {
+        title: String,
+        /// Print the whole record as JSON
+        #[arg(long)]
+        json: bool,
+    },
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum FormatArg { Xml, Markdown, Json, Plain }
+
+#[derive(Clone, Copy, ValueEnum)]
+enum OverflowArg { Truncate, Drop }
+
+#[derive(Clone, Copy, ValueEnum)]
+enum TokenizerArg {
+    Cl100k,
+    O200k,
+    /// Estimate: cl100k count scaled by a fixed ratio
+    Claude,
+    /// Estimate: cl100k count scaled by a fixed ratio
+    Gemini,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum DiffFormatArg { Markdown, Json, Xml, Txt }
+
+#[derive(Clone, Copy, ValueEnum)]
+enum LayoutArg { Unified, Split, GitPatch }
+
+#[derive(Clone, Copy, ValueEnum)]
+enum ReportFormatArg { Sarif, Json, Csv }
+
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum SortArg {
+    /// File and line order (commit order for --history)
+    Position,
+    /// Highest severity first, then highest confidence
+    Risk,
+}
+
+impl From<FormatArg> for OutputFormat {
+    fn from(f: FormatArg) -> Self {
+        match f {
+            FormatArg::Xml => Self::Xml,
+            FormatArg::Markdown => Self::Markdown,
+            FormatArg::Json => Self::Json,
+            FormatArg::Plain => Self::Plain,
+        }
+    }
+}
+
+impl From<OverflowArg> for OverflowStrategy {
+    fn from(o: OverflowArg) -> Self {
+        match o {
+            OverflowArg::Truncate => Self::Truncate,
+            OverflowArg::Drop => Self::Drop,
+        }
+    }
+}
+
+impl From<TokenizerArg> for TokenizerKind {
+    fn from(t: TokenizerArg) -> Self {
+        match t {
+            TokenizerArg::Cl100k => Self::Cl100k,
+            TokenizerArg::O200k => Self::O200k,
+            TokenizerArg::Claude => Self::Claude,
+            TokenizerArg::Gemini => Self::Gemini,
+        }
+    }
+}
+
+impl From<DiffFormatArg> for export::ExportFormat {
+    fn from(f: DiffFormatArg) -> Self {
+        match f {
+            DiffFormatArg::Markdown => Self::Markdown,
+            DiffFormatArg::Json => Self::Json,
+            DiffFormatArg::Xml => Self::Xml,
+            DiffFormatArg::Txt => Self::Txt,
+        }
+    }
+}
+
+impl From<LayoutArg> for export::ExportLayout {
+    fn from(l: LayoutArg) -> Self {
+        match l {
+            LayoutArg::Unified => Self::Unified,
+            LayoutArg::Split => Self::Split,
+            LayoutArg::GitPatch => Self::GitPatch,
+        }
+    }
+}
+
+impl From<ReportFormatArg> for ReportFormat {
+    fn from(f: ReportFormatArg) -> Self {
+        match f {
+            ReportFormatArg::Sarif => Self::Sarif,
+            ReportFormatArg::Json => Self::Json,
+            ReportFormatArg::Csv => Self::Csv,
+        }
+    }
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    let db_path = cli.db.or_else(default_db_path);
+
+    let result = match cli.command {
+        Command::Context(args) => run_context(args, db_path.as_deref()),
+        Command::Scan(args) => run_scan(args, db_path.as_deref()),
+        Command::Diff(args) => run_diff(args),
+        Command::RepoMap(args) => run_repo_map(args),
+        Command::Prompt { command: PromptCommand::Get { title, json } } => run_prompt_get(&title, json, db_path.as_deref()),
+    };
+
+    match result {
+        Ok(code) => code,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            ExitCode::from(2)
+        }
+    }
+}
+
+fn default_db_path() -> Option<PathBuf> {
+    dirs::data_local_dir().map(|dir| dir.join(APP_IDENTIFIER).join(db::init::DB_FILE_NAME))
+}
+
+/// 只读打开应用数据库，不执行迁移，避免命令行改动桌面应用的数据
+fn open_db(db_path: Option<&Path>) -> Result<Connection, String> {
+    let path = db_path.ok_or("Cannot determine the app data directory; pass --db")?;
+    if !path.exists() {
+        return Err(format!("Database not found: {}", path.display()));
+    }
+    Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_ONLY)
+        .map_err(|e| format!("Failed to open {}: {}", path.display(), e))
+}
+
+/// 打开输出目标：指定了文件就写文件，否则写 stdout
+fn output_writer(output: Option<&Path>) -> Result<Box<dyn Write>, String> {
+    Ok(match output {
+        Some(path) => {
+            let file = File::create(path).map_err(|e| format!("Failed to create {}: {}", path.display(), e))?;
+            Box::new(BufWriter::new(file))
+        }
+        None => Box::new(BufWriter::new(io::stdout().lock())),
+    })
+}
+
+// ============================================================================
+// context
+// ============================================================================
+
+fn run_context(args: ContextArgs, db_path: Option<&Path>) -> Result<ExitCode, String> {
+    let (paths, structure) = collect_files(&args.paths)?;
+    if paths.is_empty() {
+        return Err("No files to include".to_string());
+    }
+
+    let options = ProcessOptions {
+        remove_comments: args.remove_comments,
+        keep_doc_comments: args.keep_doc_comments,
+        format: args.format.into(),
+        skeleton: SkeletonOptions { paths: HashSet::new(), threshold: args.skeleton_threshold },
+        notebook: match args.notebook_outputs {
+            Some(max_output_chars) => NotebookOptions { include_outputs: true, max_output_chars },
+            None => NotebookOptions::default(),
+        },
+    };
+    let tokenizer: TokenizerKind = args.tokenizer.into();
+    let mut header = context_header(paths.len(), args.remove_comments, structure);
+    if let Some(max_tokens) = args.repo_map {
+        // 只给了一个目录时路径相对该目录显示，否则保持命令行上的写法
+        let root = match args.paths.as_slice() {
+            [dir] if dir.is_dir() => dir.clone(),
+            _ => PathBuf::new(),
+        };
+        header.repo_map = repo_map::generate(&root, &paths, &RepoMapOptions { max_tokens: Some(max_tokens) }, tokenizer).content;
+    }
+    let mut out = output_writer(args.output.as_deref())?;
+    let mut redactor = if args.redact { Some(context_redactor(&args.paths, db_path)) } else { None };
+
+    if let Some(max_tokens) = args.budget {
+        let budget_options = BudgetOptions {
+            max_tokens,
+            priority: args.priority,
+            overflow: args.overflow.into(),
+        };
+        let result = budget::assemble_with_budget(paths, &header, options, &budget_options, tokenizer, redactor.as_mut());
+        out.write_all(result.content.as_bytes()).map_err(|e| e.to_string())?;
+        out.flush().map_err(|e| e.to_string())?;
+
+        let report = result.report;
+        eprintln!(
+            "{}{} tokens ({}): {} included, {} truncated, {} dropped",
+            if tokenizer.is_exact() { "" } else { "~" }, report.total_tokens, tokenizer.as_str(), report.included.len(), report.truncated.len(), report.dropped.len()
+        );
+        print_redaction_summary(redactor);
+        return Ok(ExitCode::SUCCESS);
+    }
+
+    let written = stream::write_context(&mut out, &paths, &header, &options, &AtomicBool::new(false), redactor.as_mut(), |_, _| {})?;
+    drop(out);
+
+    eprintln!("{} files, {} bytes", paths.len(), written);
+    print_redaction_summary(redactor);
+    Ok(ExitCode::SUCCESS)
+}
+
+/// 只给了一个目录时把它当作项目根目录，叠加仓库的 `.gitleaks.toml` 和 `.gitleaksignore`
+fn context_redactor(paths: &[PathBuf], db_path: Option<&Path>) -> Redactor {
+    match paths {
+        [dir] if dir.is_dir() => {
+            let root = std::path::absolute(dir).unwrap_or_else(|_| dir.clone());
+            Redactor::for_repo(&root, allowlist(db_path, Some(&root)))
+        }
+        _ => Redactor::new(allowlist(db_path, None)),
+    }
+}
+
+fn print_redaction_summary(redactor: Option<Redactor>) {
+    if let Some(redactor) = redactor {
+        let report = redactor.into_report();
+        eprintln!("{} secrets redacted ({} unique)", report.total, report.unique_secrets);
+    }
+}
+
+/// 与前端 `generateHeader` 的说明文字保持一致
+fn context_header(file_count: usize, remove_comments: bool, structure: String) -> ContextHeader {
+    let mut parts = vec![
+        "This is a source code context provided by Code Forge AI.".to_string(),
+        format!("Total Files: {}", file_count),
+    ];
+    if remove_comments {
+        parts.push("Note: Comments have been stripped to save tokens.".to_string());
+    }
+    ContextHeader { summary: parts.join("\n"), structure, ..ContextHeader::default() }
+}
+
+/// 展开目录（跳过二进制文件），返回文件列表和 ASCII 目录树
+fn collect_files(inputs: &[PathBuf]) -> Result<(Vec<String>, String), String> {
+    let config = ProjectConfig { dirs: Vec::new(), files: Vec::new(), extensions: Vec::new() };
+    let mut paths = Vec::new();
+    let mut structure = String::new();
+
+    for input in inputs {
+        let name = input.file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_else(|| input.display().to_string());
+        if input.is_dir() {
+            let nodes = walker::walk_project(&input.to_string_lossy(), &config)?;
+            structure.push_str(&format!("{}/\n", name));
+            render_tree(&nodes, "", &mut structure, &mut paths);
+        } else if input.is_file() {
+            structure.push_str(&format!("{}\n", name));
+            paths.push(input.to_string_lossy().into_owned());
+        } else {
+            return Err(format!("No such file or directory: {}", input.display()));
+        }
+    }
+    Ok((paths, structure))
+}
+
+fn render_tree(nodes: &[WalkNode], prefix: &str, out: &mut String, paths: &mut Vec<String>) {
+    let visible: Vec<&WalkNode> = nodes.iter().filter(|n| has_text_files(n)).collect();
+    for (i, node) in visible.iter().enumerate() {
+        let last = i + 1 == visible.len();
+        let is_dir = node.kind == NodeKind::Dir;
+        out.push_str(&format!("{}{}{}{}\n", prefix, if last { "└── " } else { "├── " }, node.name, if is_dir { "/" } else { "" }));
+        match &node.children {
+            Some(children) => render_tree(children, &format!("{}{}", prefix, if last { "    " } else { "│   " }), out, paths),
+            None => paths.push(node.path.clone()),
+        }
+    }
+}
+
+fn has_text_files(node: &WalkNode) -> bool {
+    match &node.children {
+        Some(children) => children.iter().any(has_text_files),
+        None => node.kind == NodeKind::File && !node.is_binary,
+    }
+}
+
+// ============================================================================
+// scan
+// ============================================================================
+
+#[derive(Serialize)]
+struct Finding {
+    /// 只在 --history 时存在
+    #[serde(skip_serializing_if = "Option::is_none")]
+    commit: Option<String>,
+    path: String,
+    line_number: usize,
+    rule_id: String,
+    severity: Severity,
+    confidence: u8,
+    /// 只保留前几个字符，避免日志里出现完整密钥
+    masked: String,
+    /// 在编码片段中发现时，从外到内的编码
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    encodings: Vec<Encoding>,
+}
+
+impl From<&ReportFinding> for Finding {
+    fn from(f: &ReportFinding) -> Self {
+        Self {
+            commit: f.commit.clone(),
+            path: f.path.clone(),
+            line_number: f.secret.line_number,
+            rule_id: f.secret.kind.clone(),
+            severity: f.secret.risk_level,
+            confidence: f.secret.confidence,
+            masked: mask(&f.secret.value),
+            encodings: f.secret.encoded.as_ref().map(|e| e.encodings.clone()).unwrap_or_default(),
+        }
+    }
+}
+
+fn run_scan(args: ScanArgs, db_path: Option<&Path>) -> Result<ExitCode, String> {
+    // 白名单中的项目条目和 .gitleaksignore 都以扫描目录为根
+    let root = args.path.is_dir().then(|| std::path::absolute(&args.path).unwrap_or_else(|_| args.path.clone()));
+    let ignored = if args.no_allowlist { IgnoreSet::compile(&[], root.as_deref()) } else { allowlist(db_path, root.as_deref()) };
+    let rules = scan_rules(&args, db_path);
+    for err in &rules.errors {
+        eprintln!("warning: {}: {}{}", err.source, err.rule_id.as_ref().map(|id| format!("rule {}: ", id)).unwrap_or_default(), err.message);
+    }
+    if !rules.dropped_rules.is_empty() {
+        eprintln!("warning: {} rules disabled by config: {}", rules.dropped_rules.len(), rules.dropped_rules.join(", "));
+    }
+
+    let (mut findings, summary) = if args.history {
+        scan_history(&args, &rules, &ignored)?
+    } else if args.staged {
+        scan_staged(&args, &rules, &ignored)?
+    } else {
+        scan_files(&args, &rules, &ignored)?
+    };
+    if let Some(min) = args.min_confidence {
+        findings.retain(|f| f.secret.confidence >= min);
+    }
+    if args.sort == SortArg::Risk {
+        findings.sort_by(|a, b| confidence::by_risk(&a.secret, &b.secret));
+    }
+
+    if let Some(format) = args.report {
+        let mut out = output_writer(args.output.as_deref())?;
+        report::write_report(&findings, format.into(), &rules, &mut out)?;
+        out.flush().map_err(|e| e.to_string())?;
+    } else {
+        let mut out = output_writer(None)?;
+        let findings: Vec<Finding> = findings.iter().map(Finding::from).collect();
+        if args.json {
+            let json = serde_json::to_string_pretty(&findings).map_err(|e| e.to_string())?;
+            writeln!(out, "{}", json).map_err(|e| e.to_string())?;
+        } else {
+            for f in &findings {
+                if let Some(commit) = &f.commit {
+                    write!(out, "{} ", &commit[..commit.len().min(8)]).map_err(|e| e.to_string())?;
+                }
+                write!(out, "{}:{}: {} [{:?}, {}] {}", f.path, f.line_number, f.rule_id, f.severity, f.confidence, f.masked).map_err(|e| e.to_string())?;
+                if !f.encodings.is_empty() {
+                    let chain: Vec<String> = f.encodings.iter().map(|e| format!("{:?}", e).to_lowercase()).collect();
+                    write!(out, " (decoded from {})", chain.join(" > ")).map_err(|e| e.to_string())?;
+                }
+                writeln!(out).map_err(|e| e.to_string())?;
+            }
+        }
+        out.flush().map_err(|e| e.to_string())?;
+    }
+    if !args.json {
+        eprintln!("{}, {} secrets found", summary, findings.len());
+    }
+
+    Ok(if findings.is_empty() { ExitCode::SUCCESS } else { ExitCode::from(1) })
+}
+
+fn scan_files(args: &ScanArgs, rules: &RuleSet, ignored: &IgnoreSet) -> Result<(Vec<ReportFinding>, String), String> {
+    if args.path.is_file() {
+        let bytes = fs::read(&args.path).map_err(|e| e.to_string())?;
+        let path = args.path.to_string_lossy().into_owned();
+        let findings = gitleaks::scan_with(rules, &charset::decode(&bytes).text, Some(&path)).into_iter()
+            .filter(|m| !ignored.is_ignored(m, Some(&path), None))
+            .map(|secret| FileSecretMatch { secret, path: path.clone() }.into())
+            .collect();
+        return Ok((findings, "1 file scanned".to_string()));
+    }
+    if !args.path.is_dir() {
+        return Err(format!("No such file or directory: {}", args.path.display()));
+    }
+
+    let config = ProjectConfig { dirs: Vec::new(), files: Vec::new(), extensions: Vec::new() };
+    let scan = directory::scan_tree(&args.path, &config, &DirectoryScanOptions::default(), rules, ignored, &AtomicBool::new(false), |_, _| {})?;
+    let findings = scan.findings.into_iter()
+        .map(|f| FileSecretMatch { path: args.path.join(&f.path).to_string_lossy().into_owned(), ..f }.into())
+        .collect();
+    let mut summary = format!("{} files scanned", scan.files_scanned);
+    if scan.skipped_unreadable > 0 {
+        summary.push_str(&format!(", {} could not be read", scan.skipped_unreadable));
+    }
+    Ok((findings, summary))
+}
+
+/// 按引入密钥的提交从旧到新输出，同一密钥只报告一次
+fn scan_history(args: &ScanArgs, rules: &RuleSet, ignored: &IgnoreSet) -> Result<(Vec<ReportFinding>, String), String> {
+    let commits = {
+        let repo = Repository::open(&args.path).map_err(|e| format!("Cannot open repository: {}", e))?;
+        history::collect_commits(&repo, args.range.as_deref())?.len()
+    };
+    let findings = history::scan_history(&args.path, args.range.as_deref(), rules, ignored, &AtomicBool::new(false), |_, _| {})?
+        .into_iter()
+        .map(ReportFinding::from)
+        .collect();
+    Ok((findings, format!("{} commits scanned", commits)))
+}
+
+fn scan_staged(args: &ScanArgs, rules: &RuleSet, ignored: &IgnoreSet) -> Result<(Vec<ReportFinding>, String), String> {
+    let findings = hook::scan_staged(&args.path, rules, ignored)?
+        .into_iter()
+        .map(ReportFinding::from)
+        .collect();
+    Ok((findings, "Staged changes scanned".to_string()))
+}
+
+fn mask(value: &str) -> String {
+    let visible: String = value.chars().take(4).collect();
+    format!("{}{}", visible, "*".repeat(value.chars().count().saturating_sub(4).min(16)))
+}
+
+/// 内置规则 → 应用数据目录的 gitleaks.toml → 扫描目录下的 .gitleaks.toml → --config
+fn scan_rules(args: &ScanArgs, db_path: Option<&Path>) -> RuleSet {
+    if let Some(app_dir) = db_path.and_then(Path::parent) {
+        gitleaks::load_user_rules(app_dir);
+    }
+    let rules = if args.path.is_dir() {
+        gitleaks::rules_for_repo(&args.path)
+    } else {
+        gitleaks::active_rules()
+    };
+    match &args.config {
+        Some(config) if !config.is_file() => {
+            let mut rules = (*rules).clone();
+            rules.errors.push(RuleError {
+                source: config.to_string_lossy().into_owned(),
+                rule_id: None,
+                message: "File not found".to_string(),
+            });
+            rules
+        }
+        Some(config) => gitleaks::config::load_layer(&rules, config),
+        None => (*rules).clone(),
+    }
+}
+
+/// 应用数据库中未过期的密钥白名单加上 `root` 下的 .gitleaksignore；数据库不存在时只有后者。
+/// 数据库存在但无法读取时在 stderr 警告，按空白名单继续（结果只会更严格）
+fn allowlist(db_path: Option<&Path>, root: Option<&Path>) -> IgnoreSet {
+    let entries = match db_path.filter(|p| p.exists()) {
+        None => Vec::new(),
+        Some(_) => open_db(db_path)
+            .and_then(|conn| db::secrets::get_active_ignored_internal(&conn).map_err(|e| e.to_string()))
+            .unwrap_or_else(|e| {
+                eprintln!("warning: secret allowlist not loaded: {}", e);
+                Vec::new()
+            }),
+    };
+    IgnoreSet::compile(&entries, root)
+}
+
+// ============================================================================
+// repo-map
+// ============================================================================
+
+fn run_repo_map(args: RepoMapArgs) -> Result<ExitCode, String> {
+    let config = ProjectConfig { dirs: Vec::new(), files: Vec::new(), extensions: Vec::new() };
+    let root = args.path.to_string_lossy();
+    let options = RepoMapOptions { max_tokens: args.max_tokens };
+    let map = repo_map::generate_for_project(&root, &config, &options, args.tokenizer.into())?;
+
+    let text = if args.json { serde_json::to_string_pretty(&map).map_err(|e| e.to_string())? } else { map.content.clone() };
+    let mut out = output_writer(None)?;
+    writeln!(out, "{}", text.trim_end()).and_then(|_| out.flush()).map_err(|e| e.to_string())?;
+
+    let shown: usize = map.files.iter().flat_map(|f| &f.symbols).map(|s| 1 + s.members.len()).sum();
+    eprintln!("{} files, {} of {} symbols, {} tokens", map.files.len(), shown, map.total_symbols, map.tokens);
+    Ok(ExitCode::SUCCESS)
+}
+
+// ============================================================================
+// diff
+// ============================================================================
+
+/// `get_git_diff` 中表示工作区的特殊值
+const WORK_DIR: &str = "__WORK_DIR__";
+
+fn run_diff(args: DiffArgs) -> Result<ExitCode, String> {
+    let repo_path = args.repo.to_string_lossy().into_owned();
+    let repo = Repository::discover(&args.repo).map_err(|e| format!("Failed to open repo: {}", e))?;
+    let old = resolve_commit(&repo, &args.old)?;
+    let new = if args.new.eq_ignore_ascii_case("workdir") { WORK_DIR.to_string() } else { resolve_commit(&repo, &args.new)? };
+    let root = repo.workdir().map(|p| p.to_string_lossy().into_owned()).unwrap_or(repo_path);
+
+    let mut files = git::get_git_diff(root, old, new)?;
+    if !args.paths.is_empty() {
+        files.retain(|f| args.paths.iter().any(|p| f.path == *p || f.path.starts_with(&format!("{}/", p.trim_end_matches('/')))));
+    }
+    if files.is_empty() {
+        return Err("No changes to export".to_string());
+    }
+
+    let count = files.len();
+    let content = export::generate_export_content(files, args.format.into(), args.layout.into());
+    let mut out = output_writer(args.output.as_deref())?;
+    out.write_all(content.as_bytes()).map_err(|e| e.to_string())?;
+    out.flush().map_err(|e| e.to_string())?;
+
+    eprintln!("{} files, {} tokens", count, TokenizerKind::default().count(&content));
+    Ok(ExitCode::SUCCESS)
+}
+
+fn resolve_commit(repo: &Repository, rev: &str) -> Result<String, String> {
+    let commit = repo.revparse_single(rev)
+        .and_then(|obj| obj.peel_to_commit())
+        .map_err(|e| format!("Invalid revision '{}': {}", rev, e))?;
+    Ok(commit.id().to_string())
+}
+
+// ============================================================================
+// prompt
+// ============================================================================
+
+fn run_prompt_get(title: &str, json: bool, db_path: Option<&Path>) -> Result<ExitCode, String> {
+    let conn = open_db(db_path)?;
+    let prompt = db::prompts::find_prompt_by_title(&conn, title)
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("Prompt not found: {}", title))?;
+
+    let text = if json { serde_json::to_string_pretty(&prompt).map_err(|e| e.to_string())? } else { prompt.content };
+    let mut out = output_writer(None)?;
+    writeln!(out, "{}", text).and_then(|_| out.flush()).map_err(|e| e.to_string())?;
+    Ok(ExitCode::SUCCESS)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn budgeted_context_can_be_redacted() {
+        let dir = std::env::temp_dir().join(format!("ctxrun-cli-test-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let source = dir.join("config.js");
+        let secret = format!("ghp_{}", "A1b2C3d4E5f6G7h8I9j0K1l2M3n4O5p6Q7r8");
+        fs::write(&source, format!("const token = \"{}\";\n", secret)).unwrap();
+        let output = dir.join("context.txt");
+
+        let cli = Cli::try_parse_from([
+            "ctxrun-cli",
+            "--db", dir.join("missing.db").to_str().unwrap(),
+            "context", source.to_str().unwrap(),
+            "--budget", "1000",
+            "--redact",
+            "-o", output.to_str().unwrap(),
+        ]).unwrap();
+        let Command::Context(args) = cli.command else { panic!("expected the context command") };
+        run_context(args, cli.db.as_deref()).unwrap();
+
+        let content = fs::read_to_string(&output).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+        assert!(!content.contains(&secret), "{}", content);
+        assert!(content.contains("[REDACTED:github-pat-classic#1]"), "{}", content);
+    }
+}
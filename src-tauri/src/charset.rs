@@ -0,0 +1,102 @@
+use encoding_rs::{Encoding, UTF_16BE, UTF_16LE, UTF_8};
+use chardetng::EncodingDetector;
+
+// ============================================================================
+// Charset Detection
+// ============================================================================
+//
+// 上下文、Git diff 和预览读取文本文件时共用：
+// 1. 有 BOM 按 BOM（UTF-8 / UTF-16LE / UTF-16BE）
+// 2. 没有 BOM 但 NUL 字节集中在奇数或偶数位置，按 UTF-16 处理
+// 3. 合法 UTF-8 直接使用
+// 4. 其余交给 chardetng 猜测（GBK、Shift_JIS、Big5、EUC-KR、Windows-125x ...）
+
+/// 二进制判断和 UTF-16 特征检测只看文件开头这么多字节
+pub const SNIFF_BYTES: usize = 8000;
+
+pub struct Decoded {
+    pub text: String,
+    pub encoding: &'static Encoding,
+    /// 解码时遇到非法字节（已替换为 U+FFFD）
+    pub had_errors: bool,
+}
+
+impl Decoded {
+    /// 编码名称（WHATWG 标准名，如 "GBK"、"Shift_JIS"、"UTF-16LE"）
+    pub fn encoding_name(&self) -> &'static str {
+        self.encoding.name()
+    }
+}
+
+/// 检测编码并转成 UTF-8，BOM 会被去掉
+pub fn decode(bytes: &[u8]) -> Decoded {
+    let encoding = detect(bytes);
+    let (text, encoding, had_errors) = encoding.decode(bytes);
+    Decoded { text: text.into_owned(), encoding, had_errors }
+}
+
+pub fn detect(bytes: &[u8]) -> &'static Encoding {
+    detect_inner(bytes, true)
+}
+
+/// 只拿到文件开头一段时使用：末尾被截断的 UTF-8 多字节字符不算非法
+pub fn detect_prefix(head: &[u8]) -> &'static Encoding {
+    detect_inner(head, false)
+}
+
+fn detect_inner(bytes: &[u8], last: bool) -> &'static Encoding {
+    if let Some((encoding, _)) = Encoding::for_bom(bytes) {
+        return encoding;
+    }
+    if let Some(encoding) = guess_utf16(bytes) {
+        return encoding;
+    }
+    match std::str::from_utf8(bytes) {
+        Ok(_) => return UTF_8,
+        Err(e) if !last && e.error_len().is_none() => return UTF_8,
+        Err(_) => {}
+    }
+    let mut detector = EncodingDetector::new();
+    detector.feed(bytes, last);
+    detector.guess(None, true)
+}
+
+/// 开头出现 NUL 即视为二进制文件，但 UTF-16 文本（有 BOM 或符合 UTF-16 特征）除外
+pub fn is_binary(bytes: &[u8]) -> bool {
+    let head = &bytes[..bytes.len().min(SNIFF_BYTES)];
+    if !head.contains(&0) {
+        return false;
+    }
+    !matches!(Encoding::for_bom(head), Some((enc, _)) if enc == UTF_16LE || enc == UTF_16BE)
+        && guess_utf16(head).is_none()
+}
+
+/// 没有 BOM 的 UTF-16：以 ASCII 为主的文本每个字符都有一个 0 字节，
+/// 位置固定在奇数（LE）或偶数（BE）上。再试着解码一次，排除恰好符合规律的二进制数据
+fn guess_utf16(bytes: &[u8]) -> Option<&'static Encoding> {
+    let head = &bytes[..bytes.len().min(SNIFF_BYTES) & !1];
+    let pairs = head.len() / 2;
+    if pairs < 2 {
+        return None;
+    }
+
+    let (mut even, mut odd) = (0, 0);
+    for (i, _) in head.iter().enumerate().filter(|(_, &b)| b == 0) {
+        if i % 2 == 0 { even += 1 } else { odd += 1 }
+    }
+    let mostly = |n: usize| n * 10 >= pairs * 3;
+    let rarely = |n: usize| n * 20 < pairs;
+    let encoding = if mostly(odd) && rarely(even) {
+        UTF_16LE
+    } else if mostly(even) && rarely(odd) {
+        UTF_16BE
+    } else {
+        return None;
+    };
+
+    let (text, _) = encoding.decode_without_bom_handling(head);
+    let suspicious = text.chars()
+        .filter(|&c| c == char::REPLACEMENT_CHARACTER || (c.is_control() && !matches!(c, '\n' | '\r' | '\t')))
+        .count();
+    (suspicious * 20 < pairs).then_some(encoding)
+}
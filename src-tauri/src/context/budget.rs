@@ -0,0 +1,238 @@
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use super::core::{self, FileBody, ProcessOptions};
+use super::format::{ContextHeader, OutputFormat};
+use super::redact::Redactor;
+use super::tokenizer::TokenizerKind;
+
+/// 放不下的文件如何处理
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OverflowStrategy {
+    /// 保留开头和结尾，中间用标记代替
+    #[default]
+    Truncate,
+    /// 整个文件只留一行说明
+    Drop,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct BudgetOptions {
+    pub max_tokens: usize,
+    /// 路径或目录前缀，越靠前越优先放入
+    #[serde(default)]
+    pub priority: Vec<String>,
+    #[serde(default)]
+    pub overflow: OverflowStrategy,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BudgetStatus {
+    Included,
+    Truncated,
+    Dropped,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BudgetEntry {
+    pub path: String,
+    pub status: BudgetStatus,
+    /// 完整文件块的 token 数
+    pub original_tokens: usize,
+    /// 实际写入上下文的 token 数（截断后 / 说明行）
+    pub tokens: usize,
+    /// 截断时省略的行数
+    pub omitted_lines: usize,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BudgetReport {
+    pub max_tokens: usize,
+    pub tokenizer: TokenizerKind,
+    pub total_tokens: usize,
+    pub included: Vec<String>,
+    pub truncated: Vec<String>,
+    pub dropped: Vec<String>,
+    /// 按输出顺序排列的逐文件明细
+    pub files: Vec<BudgetEntry>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BudgetedContext {
+    pub content: String,
+    pub report: BudgetReport,
+}
+
+/// 截断时开头保留的比例，其余留给结尾
+const HEAD_RATIO: f64 = 0.7;
+
+struct Candidate {
+    path: String,
+    body: FileBody,
+    block: String,
+    tokens: usize,
+    /// 丢弃时替换成的说明块
+    note: String,
+    note_tokens: usize,
+}
+
+/// 在 token 预算内组装上下文：
+/// 1. 按 `priority` 排序，未命中的文件保持原有顺序排在最后
+/// 2. 依次整文件放入，直到预算用完
+/// 3. 放不下的文件按 `overflow` 截断成首尾片段，或替换为一行说明
+///
+/// 输出仍按原始选择顺序排列，报告记录每个文件的去向。
+/// 说明行同样计入预算；只有预算连 header 和说明行都放不下时总数才会超出 `max_tokens`。
+/// 开启脱敏时先替换密钥再计数
+pub fn assemble_with_budget(
+    paths: Vec<String>,
+    header: &ContextHeader,
+    options: ProcessOptions,
+    budget: &BudgetOptions,
+    tokenizer: TokenizerKind,
+    redactor: Option<&mut Redactor>,
+) -> BudgetedContext {
+    let max_tokens = budget.max_tokens;
+    let bodies = core::load_bodies(&paths, &options, redactor);
+    let candidates: Vec<Candidate> = paths.par_iter()
+        .zip(bodies.into_par_iter())
+        .map(|(path, body)| {
+            let block = options.format.render_file(path, body.as_str());
+            let tokens = tokenizer.count(&block);
+            let note = options.format.render_file(path, &format!("[Omitted: exceeds token budget ({} tokens)]", tokens));
+            let note_tokens = tokenizer.count(&note);
+            Candidate { path: path.clone(), body, block, tokens, note, note_tokens }
+        })
+        .collect();
+
+    let mut order: Vec<usize> = (0..candidates.len()).collect();
+    order.sort_by_key(|&i| priority_rank(&candidates[i].path, &budget.priority));
+
+    // header 和外层标签也占预算；尚未处理的文件预留说明行的位置，保证丢弃时也放得下
+    let mut used = tokenizer.count(&options.format.render_document(header, &[]));
+    let mut reserved: usize = candidates.iter().map(|c| c.note_tokens).sum();
+    let mut decided: Vec<Option<(String, BudgetEntry)>> = (0..candidates.len()).map(|_| None).collect();
+
+    for i in order {
+        let c = &candidates[i];
+        reserved -= c.note_tokens;
+        let remaining = max_tokens.saturating_sub(used + reserved);
+
+        let (block, entry) = if c.tokens <= remaining {
+            let entry = BudgetEntry {
+                path: c.path.clone(),
+                status: BudgetStatus::Included,
+                original_tokens: c.tokens,
+                tokens: c.tokens,
+                omitted_lines: 0,
+            };
+            (c.block.clone(), entry)
+        } else {
+            let truncated = match (&c.body, budget.overflow) {
+                (FileBody::Text(text), OverflowStrategy::Truncate) => truncate_to_fit(&c.path, text, remaining, tokenizer, options.format),
+                _ => None,
+            };
+            match truncated {
+                Some((block, tokens, omitted_lines)) => {
+                    let entry = BudgetEntry {
+                        path: c.path.clone(),
+                        status: BudgetStatus::Truncated,
+                        original_tokens: c.tokens,
+                        tokens,
+                        omitted_lines,
+                    };
+                    (block, entry)
+                }
+                None => {
+                    let entry = BudgetEntry {
+                        path: c.path.clone(),
+                        status: BudgetStatus::Dropped,
+                        original_tokens: c.tokens,
+                        tokens: c.note_tokens,
+                        omitted_lines: 0,
+                    };
+                    (c.note.clone(), entry)
+                }
+            }
+        };
+
+        used += entry.tokens;
+        decided[i] = Some((block, entry));
+    }
+
+    let mut blocks = Vec::with_capacity(decided.len());
+    let mut report = BudgetReport {
+        max_tokens,
+        tokenizer,
+        total_tokens: used,
+        included: Vec::new(),
+        truncated: Vec::new(),
+        dropped: Vec::new(),
+        files: Vec::with_capacity(decided.len()),
+    };
+    for (block, entry) in decided.into_iter().flatten() {
+        match entry.status {
+            BudgetStatus::Included => report.included.push(entry.path.clone()),
+            BudgetStatus::Truncated => report.truncated.push(entry.path.clone()),
+            BudgetStatus::Dropped => report.dropped.push(entry.path.clone()),
+        }
+        blocks.push(block);
+        report.files.push(entry);
+    }
+
+    BudgetedContext {
+        content: options.format.render_document(header, &blocks),
+        report,
+    }
+}
+
+/// 命中的第一个优先级条目的下标；整段路径或以 `/`、`\` 分隔的目录前缀都算命中
+fn priority_rank(path: &str, priority: &[String]) -> usize {
+    priority.iter()
+        .position(|p| {
+            let p = p.trim_end_matches(['/', '\\']);
+            !p.is_empty()
+                && path.starts_with(p)
+                && matches!(path.as_bytes().get(p.len()), None | Some(b'/') | Some(b'\\'))
+        })
+        .unwrap_or(priority.len())
+}
+
+/// 保留尽可能多的首尾行使整个文件块不超过 `budget`，返回（文件块, token 数, 省略行数）。
+/// 一行都放不下时返回 None
+fn truncate_to_fit(
+    path: &str,
+    text: &str,
+    budget: usize,
+    tokenizer: TokenizerKind,
+    format: OutputFormat,
+) -> Option<(String, usize, usize)> {
+    let lines: Vec<&str> = text.lines().collect();
+    let build = |keep: usize| {
+        let head = ((keep as f64) * HEAD_RATIO).ceil() as usize;
+        let tail = keep - head;
+        let omitted = lines.len() - keep;
+        let mut body = lines[..head].join("\n");
+        body.push_str(&format!("\n... [truncated: {} lines omitted] ...\n", omitted));
+        body.push_str(&lines[lines.len() - tail..].join("\n"));
+        let block = format.render_file(path, &body);
+        let tokens = tokenizer.count(&block);
+        (block, tokens, omitted)
+    };
+
+    // 保留行数越多 token 越多，二分找最大的可行值；至少省略一行，至少保留一行
+    let (mut lo, mut hi) = (1usize, lines.len().saturating_sub(1));
+    let mut best = None;
+    while lo <= hi {
+        let mid = lo + (hi - lo) / 2;
+        let candidate = build(mid);
+        if candidate.1 <= budget {
+            best = Some(candidate);
+            lo = mid + 1;
+        } else {
+            hi = mid - 1;
+        }
+    }
+    best
+}
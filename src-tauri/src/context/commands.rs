@@ -1,58 +1,380 @@
-use std::fs::File;
-use std::io::Write;
-use super::core::{self, ContextStats};
+use std::fs::{self, File};
+use std::io::{BufWriter, Write};
+use std::path::Path;
+use std::sync::atomic::AtomicBool;
+use super::core::{self, ContextStats, ProcessOptions, SkeletonOptions};
+use super::budget::{self, BudgetOptions, BudgetedContext};
+use super::format::{ContextHeader, OutputFormat};
+use super::git_context::{self, GitContext, GitSelection};
+use super::redact::{RedactedContext, RedactionReport, Redactor};
+use super::repo_map::{self, RepoMap, RepoMapOptions};
+use super::split::{self, ContextPart};
+use super::stream::{self, ExportProgress};
+use super::tokenizer::TokenizerKind;
+use super::walker::{self, WalkNode};
+use crate::db::{DbState, ProjectConfig};
+use crate::db::secrets;
+use crate::db::token_cache::{self, TokenCacheScope};
+use crate::gitleaks::IgnoreSet;
+use crate::jobs::JobRegistry;
+use crate::notebook::NotebookOptions;
 use arboard::Clipboard;
+use tauri::{AppHandle, Emitter, State};
+
+fn process_options(
+    remove_comments: bool,
+    keep_doc_comments: Option<bool>,
+    format: Option<OutputFormat>,
+    skeleton: Option<SkeletonOptions>,
+    notebook: Option<NotebookOptions>
+) -> ProcessOptions {
+    ProcessOptions {
+        remove_comments,
+        keep_doc_comments: keep_doc_comments.unwrap_or(false),
+        format: format.unwrap_or_default(),
+        skeleton: skeleton.unwrap_or_default(),
+        notebook: notebook.unwrap_or_default(),
+    }
+}
+
+/// 按白名单（ignored_secrets）创建 `Redactor`；知道项目根目录时叠加仓库的 `.gitleaks.toml` 和 `.gitleaksignore`
+fn new_redactor(state: &State<'_, DbState>, project_root: Option<&str>) -> Result<Redactor, String> {
+    let conn = state.conn.lock().map_err(|e| e.to_string())?;
+    let entries = secrets::get_active_ignored_internal(&conn).map_err(|e| e.to_string())?;
+    Ok(match project_root.map(Path::new) {
+        Some(root) => Redactor::for_repo(root, IgnoreSet::compile(&entries, Some(root))),
+        None => Redactor::new(IgnoreSet::compile(&entries, None)),
+    })
+}
+
+/// `redact_secrets` 未开启时返回 None
+fn optional_redactor(
+    state: &State<'_, DbState>,
+    redact_secrets: Option<bool>,
+    project_root: Option<&str>
+) -> Result<Option<Redactor>, String> {
+    if redact_secrets.unwrap_or(false) {
+        new_redactor(state, project_root).map(Some)
+    } else {
+        Ok(None)
+    }
+}
 
 #[tauri::command]
+#[allow(clippy::too_many_arguments)]
 pub async fn calculate_context_stats(
     paths: Vec<String>,
-    remove_comments: bool
+    remove_comments: bool,
+    keep_doc_comments: Option<bool>,
+    format: Option<OutputFormat>,
+    skeleton: Option<SkeletonOptions>,
+    notebook: Option<NotebookOptions>,
+    tokenizer: Option<TokenizerKind>,
+    state: State<'_, DbState>
 ) -> Result<ContextStats, String> {
-    let stats = tauri::async_runtime::spawn_blocking(move || {
-        core::calculate_stats_parallel(paths, remove_comments)
+    let options = process_options(remove_comments, keep_doc_comments, format, skeleton, notebook);
+    let tokenizer = tokenizer.unwrap_or_default();
+    let scope = TokenCacheScope {
+        tokenizer: tokenizer.as_str(),
+        format: options.format.as_str(),
+        remove_comments: options.remove_comments,
+        keep_doc_comments: options.keep_doc_comments,
+    };
+
+    // 1. 读取缓存（读库很快，不必放进 blocking 线程）
+    let cache = {
+        let conn = state.conn.lock().map_err(|e| e.to_string())?;
+        token_cache::load_entries(&conn, scope, &paths).map_err(|e| e.to_string())?
+    };
+
+    // 2. 只对变化过的文件重新读取和分词
+    let result = tauri::async_runtime::spawn_blocking(move || {
+        core::calculate_stats_cached(paths, options, tokenizer, &cache)
     }).await.map_err(|e| e.to_string())?;
 
-    Ok(stats)
+    // 3. 写回缓存；失败只影响下次的速度，不影响本次结果
+    if let Ok(mut conn) = state.conn.lock() {
+        if let Err(e) = token_cache::store_entries(&mut conn, scope, &result.updated, &result.hits, &result.missing) {
+            eprintln!("[TokenCache] Failed to update cache: {}", e);
+        }
+    }
+
+    Ok(result.stats)
 }
 
 #[tauri::command]
+#[allow(clippy::too_many_arguments)]
 pub async fn get_context_content(
     paths: Vec<String>,
-    header: String,
-    remove_comments: bool
+    header: ContextHeader,
+    remove_comments: bool,
+    keep_doc_comments: Option<bool>,
+    format: Option<OutputFormat>,
+    skeleton: Option<SkeletonOptions>,
+    notebook: Option<NotebookOptions>,
+    redact_secrets: Option<bool>,
+    project_root: Option<String>,
+    state: State<'_, DbState>
 ) -> Result<String, String> {
-    let content = tauri::async_runtime::spawn_blocking(move || {
-        core::assemble_context_parallel(paths, header, remove_comments)
+    let options = process_options(remove_comments, keep_doc_comments, format, skeleton, notebook);
+    let redactor = optional_redactor(&state, redact_secrets, project_root.as_deref())?;
+    let content = tauri::async_runtime::spawn_blocking(move || match redactor {
+        Some(mut redactor) => core::assemble_context_redacted(paths, &header, options, &mut redactor),
+        None => core::assemble_context_parallel(paths, &header, options),
     }).await.map_err(|e| e.to_string())?;
 
     Ok(content)
 }
 
+/// 组装上下文并把其中的密钥替换成 `[REDACTED:<rule>#<n>]`，同时返回脱敏报告。
+/// 白名单（ignored_secrets）中的值保持原样
 #[tauri::command]
-pub async fn copy_context_to_clipboard(
+#[allow(clippy::too_many_arguments)]
+pub async fn get_redacted_context(
     paths: Vec<String>,
-    header: String,
-    remove_comments: bool
-) -> Result<String, String> {
+    header: ContextHeader,
+    remove_comments: bool,
+    keep_doc_comments: Option<bool>,
+    format: Option<OutputFormat>,
+    skeleton: Option<SkeletonOptions>,
+    notebook: Option<NotebookOptions>,
+    project_root: Option<String>,
+    state: State<'_, DbState>
+) -> Result<RedactedContext, String> {
+    let options = process_options(remove_comments, keep_doc_comments, format, skeleton, notebook);
+    let mut redactor = new_redactor(&state, project_root.as_deref())?;
+    tauri::async_runtime::spawn_blocking(move || {
+        let content = core::assemble_context_redacted(paths, &header, options, &mut redactor);
+        RedactedContext { content, report: redactor.into_report() }
+    }).await.map_err(|e| e.to_string())
+}
+
+/// 在 token 预算内组装上下文，并返回每个文件被完整包含、截断还是丢弃
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub async fn get_budgeted_context(
+    paths: Vec<String>,
+    header: ContextHeader,
+    remove_comments: bool,
+    keep_doc_comments: Option<bool>,
+    format: Option<OutputFormat>,
+    skeleton: Option<SkeletonOptions>,
+    notebook: Option<NotebookOptions>,
+    budget_options: BudgetOptions,
+    tokenizer: Option<TokenizerKind>,
+    redact_secrets: Option<bool>,
+    project_root: Option<String>,
+    state: State<'_, DbState>
+) -> Result<BudgetedContext, String> {
+    let options = process_options(remove_comments, keep_doc_comments, format, skeleton, notebook);
+    let mut redactor = optional_redactor(&state, redact_secrets, project_root.as_deref())?;
+    let result = tauri::async_runtime::spawn_blocking(move || {
+        budget::assemble_with_budget(paths, &header, options, &budget_options, tokenizer.unwrap_or_default(), redactor.as_mut())
+    }).await.map_err(|e| e.to_string())?;
+
+    Ok(result)
+}
+
+/// 以相对某个提交 / 分支 / 分叉点的 Git 变更作为上下文
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub async fn get_git_context(
+    project_path: String,
+    selection: GitSelection,
+    header: ContextHeader,
+    remove_comments: bool,
+    keep_doc_comments: Option<bool>,
+    format: Option<OutputFormat>,
+    skeleton: Option<SkeletonOptions>,
+    notebook: Option<NotebookOptions>,
+    redact_secrets: Option<bool>,
+    state: State<'_, DbState>
+) -> Result<GitContext, String> {
+    let options = process_options(remove_comments, keep_doc_comments, format, skeleton, notebook);
+    let mut redactor = optional_redactor(&state, redact_secrets, Some(&project_path))?;
+    tauri::async_runtime::spawn_blocking(move || {
+        git_context::assemble_git_context(&project_path, &selection, &header, options, redactor.as_mut())
+    }).await.map_err(|e| e.to_string())?
+}
+
+/// 按 token 上限把上下文拆成多个部分，每部分带“第 i 部分，共 N 部分”的说明
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub async fn get_split_context(
+    paths: Vec<String>,
+    header: ContextHeader,
+    remove_comments: bool,
+    keep_doc_comments: Option<bool>,
+    format: Option<OutputFormat>,
+    skeleton: Option<SkeletonOptions>,
+    notebook: Option<NotebookOptions>,
+    max_tokens: usize,
+    tokenizer: Option<TokenizerKind>,
+    redact_secrets: Option<bool>,
+    project_root: Option<String>,
+    state: State<'_, DbState>
+) -> Result<Vec<ContextPart>, String> {
+    let options = process_options(remove_comments, keep_doc_comments, format, skeleton, notebook);
+    let mut redactor = optional_redactor(&state, redact_secrets, project_root.as_deref())?;
     tauri::async_runtime::spawn_blocking(move || {
-        let content = core::assemble_context_parallel(paths, header, remove_comments);
+        split::split_context(paths, &header, options, max_tokens, tokenizer.unwrap_or_default(), redactor.as_mut())
+    }).await.map_err(|e| e.to_string())?
+}
+
+/// 把各部分保存为编号文件：`context.xml` -> `context_part1.xml`、`context_part2.xml`……
+/// 返回写入的文件路径
+#[tauri::command]
+pub async fn save_context_parts(parts: Vec<String>, save_path: String) -> Result<Vec<String>, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let base = Path::new(&save_path);
+        let stem = base.file_stem().map(|s| s.to_string_lossy().into_owned()).unwrap_or_else(|| "context".to_string());
+        let ext = base.extension().map(|e| format!(".{}", e.to_string_lossy())).unwrap_or_default();
+        let width = parts.len().to_string().len();
+
+        let mut written = Vec::with_capacity(parts.len());
+        for (i, content) in parts.iter().enumerate() {
+            let path = base.with_file_name(format!("{}_part{:0width$}{}", stem, i + 1, ext, width = width));
+            fs::write(&path, content).map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+            written.push(path.to_string_lossy().into_owned());
+        }
+        Ok(written)
+    }).await.map_err(|e| e.to_string())?
+}
+
+/// 流式写出上下文，每批文件写完后向前端发送进度事件
+#[allow(clippy::too_many_arguments)]
+fn stream_to<W: Write>(
+    out: &mut W,
+    app: &AppHandle,
+    job_id: &str,
+    cancel: &AtomicBool,
+    paths: &[String],
+    header: &ContextHeader,
+    options: &ProcessOptions,
+    redactor: Option<&mut Redactor>
+) -> Result<u64, String> {
+    let total = paths.len();
+    stream::write_context(out, paths, header, options, cancel, redactor, |done, bytes| {
+        let progress = ExportProgress { job_id: job_id.to_string(), done, total, bytes };
+        if let Err(e) = app.emit(stream::PROGRESS_EVENT, progress) {
+            eprintln!("[Context] Failed to emit export progress: {}", e);
+        }
+    })
+}
+
+/// 开启 `redact_secrets` 时返回脱敏报告
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub async fn copy_context_to_clipboard(
+    app: AppHandle,
+    jobs: State<'_, JobRegistry>,
+    state: State<'_, DbState>,
+    paths: Vec<String>,
+    header: ContextHeader,
+    remove_comments: bool,
+    keep_doc_comments: Option<bool>,
+    format: Option<OutputFormat>,
+    skeleton: Option<SkeletonOptions>,
+    notebook: Option<NotebookOptions>,
+    redact_secrets: Option<bool>,
+    project_root: Option<String>,
+    job_id: Option<String>
+) -> Result<Option<RedactionReport>, String> {
+    let options = process_options(remove_comments, keep_doc_comments, format, skeleton, notebook);
+    let mut redactor = optional_redactor(&state, redact_secrets, project_root.as_deref())?;
+    let job_id = job_id.unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+    let cancel = jobs.register(&job_id);
+
+    let id = job_id.clone();
+    let result = tauri::async_runtime::spawn_blocking(move || {
+        // 剪贴板只接受完整字符串，但不再同时持有所有文件块
+        let mut buffer = Vec::new();
+        stream_to(&mut buffer, &app, &id, &cancel, &paths, &header, &options, redactor.as_mut())?;
+        let content = String::from_utf8(buffer).map_err(|e| e.to_string())?;
         let mut clipboard = Clipboard::new().map_err(|e| format!("Clipboard init failed: {}", e))?;
         clipboard.set_text(content).map_err(|e| format!("Clipboard write failed: {}", e))?;
-        Ok("Success".to_string())
-    }).await.map_err(|e| e.to_string())?
+        Ok(redactor.map(Redactor::into_report))
+    }).await.map_err(|e| e.to_string());
+
+    jobs.finish(&job_id);
+    result?
 }
 
+/// 开启 `redact_secrets` 时返回脱敏报告
 #[tauri::command]
+#[allow(clippy::too_many_arguments)]
 pub async fn save_context_to_file(
+    app: AppHandle,
+    jobs: State<'_, JobRegistry>,
+    state: State<'_, DbState>,
     paths: Vec<String>,
-    header: String,
+    header: ContextHeader,
     remove_comments: bool,
-    save_path: String
-) -> Result<(), String> {
+    keep_doc_comments: Option<bool>,
+    format: Option<OutputFormat>,
+    skeleton: Option<SkeletonOptions>,
+    notebook: Option<NotebookOptions>,
+    redact_secrets: Option<bool>,
+    project_root: Option<String>,
+    save_path: String,
+    job_id: Option<String>
+) -> Result<Option<RedactionReport>, String> {
+    let options = process_options(remove_comments, keep_doc_comments, format, skeleton, notebook);
+    let mut redactor = optional_redactor(&state, redact_secrets, project_root.as_deref())?;
+    let job_id = job_id.unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+    let cancel = jobs.register(&job_id);
+
+    let id = job_id.clone();
+    let result = tauri::async_runtime::spawn_blocking(move || {
+        // 先写到临时文件，完成后再改名，取消或失败时不会留下半个文件
+        let part_path = format!("{}.part", save_path);
+        let file = File::create(&part_path).map_err(|e| format!("Failed to create file: {}", e))?;
+        let mut writer = BufWriter::new(file);
+        let written = stream_to(&mut writer, &app, &id, &cancel, &paths, &header, &options, redactor.as_mut());
+        drop(writer);
+
+        match written {
+            Ok(_) => {
+                fs::rename(&part_path, &save_path).map_err(|e| format!("Failed to write file: {}", e))?;
+                Ok(redactor.map(Redactor::into_report))
+            }
+            Err(e) => {
+                let _ = fs::remove_file(&part_path);
+                Err(e)
+            }
+        }
+    }).await.map_err(|e| e.to_string());
+
+    jobs.finish(&job_id);
+    result?
+}
+
+/// 取消正在进行的复制 / 保存，返回是否找到了该任务
+#[tauri::command]
+pub fn cancel_context_export(job_id: String, jobs: State<'_, JobRegistry>) -> bool {
+    jobs.cancel(&job_id)
+}
+
+/// 生成项目的符号地图，结果的 `content` 可以作为 `ContextHeader::repo_map` 放在上下文开头
+#[tauri::command]
+pub async fn get_repo_map(
+    root: String,
+    config: ProjectConfig,
+    options: Option<RepoMapOptions>,
+    tokenizer: Option<TokenizerKind>
+) -> Result<RepoMap, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        repo_map::generate_for_project(&root, &config, &options.unwrap_or_default(), tokenizer.unwrap_or_default())
+    }).await.map_err(|e| e.to_string())?
+}
+
+/// 在 Rust 端遍历项目目录（遵守 .gitignore 等规则），返回带大小和二进制标记的目录树
+#[tauri::command]
+pub async fn scan_project_tree(
+    root: String,
+    config: ProjectConfig
+) -> Result<Vec<WalkNode>, String> {
     tauri::async_runtime::spawn_blocking(move || {
-        let content = core::assemble_context_parallel(paths, header, remove_comments);
-        let mut file = File::create(save_path).map_err(|e| format!("Failed to create file: {}", e))?;
-        file.write_all(content.as_bytes()).map_err(|e| format!("Failed to write file: {}", e))?;
-        Ok(())
+        walker::walk_project(&root, &config)
     }).await.map_err(|e| e.to_string())?
 }
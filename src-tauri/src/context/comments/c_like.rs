@@ -0,0 +1,669 @@
+use super::{is_ident_byte, is_space, Scanner};
+
+/// `//` + `/* */` 注释家族的方言差异：字符串前缀、字符字面量、模板插值、块注释嵌套等
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum Dialect {
+    Rust,
+    /// C / C++ / Objective-C
+    C,
+    Go,
+    Java,
+    Kotlin,
+    Swift,
+    Dart,
+    CSharp,
+    /// JavaScript / TypeScript / JSX
+    JavaScript,
+}
+
+/// 字符串内部的插值语法
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Interp {
+    None,
+    /// `${ ... }`（JS 模板、Kotlin、Dart）
+    Dollar,
+    /// `\( ... )`（Swift）
+    Paren,
+    /// `{ ... }`，`{{` 为转义（C# `$"..."`）
+    Brace,
+}
+
+const JS_REGEX_KEYWORDS: &[&[u8]] = &[
+    b"return", b"typeof", b"instanceof", b"in", b"of", b"new", b"delete",
+    b"void", b"throw", b"case", b"do", b"else", b"yield", b"await",
+];
+
+pub(super) fn strip(src: &str, dialect: Dialect, keep_docs: bool) -> Vec<u8> {
+    let mut lexer = Lexer {
+        s: Scanner::new(src),
+        dialect,
+        keep_docs,
+        regex_ok: true,
+    };
+    lexer.s.copy_shebang();
+    lexer.code(None);
+    lexer.s.finish()
+}
+
+struct Lexer<'a> {
+    s: Scanner<'a>,
+    dialect: Dialect,
+    keep_docs: bool,
+    /// JS 中 `/` 此处是否应被解析为正则字面量（而不是除号）
+    regex_ok: bool,
+}
+
+impl Lexer<'_> {
+    fn nested_blocks(&self) -> bool {
+        matches!(self.dialect, Dialect::Rust | Dialect::Kotlin | Dialect::Swift | Dialect::Dart)
+    }
+
+    /// 扫描代码；`close` 为插值结束符（`}` 或 `)`），遇到同层级的结束符后返回
+    fn code(&mut self, close: Option<u8>) {
+        let mut depth = 0usize;
+
+        while !self.s.eof() {
+            let c = self.s.cur();
+            match c {
+                b'/' if self.s.at(1) == b'/' => self.line_comment(),
+                b'/' if self.s.at(1) == b'*' => self.block_comment(),
+                b'/' if self.dialect == Dialect::JavaScript && self.regex_ok => self.regex(),
+                b'"' => self.double_quote(),
+                b'\'' => self.single_quote(),
+                b'`' => self.backtick(),
+                b'@' | b'$' if self.dialect == Dialect::CSharp => {
+                    if !self.csharp_string() {
+                        self.s.copy(1);
+                    }
+                }
+                b'#' if self.dialect == Dialect::Swift && matches!(self.s.at(1), b'#' | b'"') => self.swift_raw(),
+                _ if c.is_ascii_digit() => self.number(),
+                _ if is_ident_byte(c) => self.ident(),
+                b'{' | b'(' | b'[' => {
+                    depth += 1;
+                    self.s.copy(1);
+                    self.regex_ok = true;
+                }
+                b'}' | b')' | b']' => {
+                    if depth == 0 && Some(c) == close {
+                        self.s.copy(1);
+                        return;
+                    }
+                    depth = depth.saturating_sub(1);
+                    self.s.copy(1);
+                    self.regex_ok = c == b'}';
+                }
+                _ => {
+                    self.s.copy(1);
+                    if !is_space(c) {
+                        // `$` 在 JS 中是标识符字符
+                        self.regex_ok = c != b'$';
+                    }
+                }
+            }
+        }
+    }
+
+    // ------------------------------------------------------------------
+    // Comments
+    // ------------------------------------------------------------------
+
+    fn line_comment(&mut self) {
+        let start = self.s.pos;
+        let end = self.line_comment_end(start);
+        let text = &self.s.src[start..end];
+
+        let is_doc = match self.dialect {
+            Dialect::Rust => (text.starts_with(b"///") && !text.starts_with(b"////")) || text.starts_with(b"//!"),
+            Dialect::C | Dialect::CSharp | Dialect::Dart | Dialect::Swift | Dialect::JavaScript => {
+                text.starts_with(b"///") && !text.starts_with(b"////")
+            }
+            _ => false,
+        };
+
+        if self.is_directive(text) || (is_doc && self.keep_docs) || self.precedes_cgo_import(end) {
+            self.s.copy_to(end);
+        } else {
+            self.s.drop_to(end);
+        }
+    }
+
+    /// C 预处理中，行尾反斜杠会把 `//` 注释延续到下一行
+    fn line_comment_end(&self, start: usize) -> usize {
+        let mut end = self.s.line_end(start);
+        if self.dialect == Dialect::C {
+            while end < self.s.src.len() && end > start && self.s.src[end - 1] == b'\\' {
+                let next = self.s.next_line_start(end);
+                if next >= self.s.src.len() {
+                    break;
+                }
+                end = self.s.line_end(next);
+            }
+        }
+        end
+    }
+
+    /// 会影响编译/构建结果的“注释”必须保留
+    fn is_directive(&self, text: &[u8]) -> bool {
+        match self.dialect {
+            Dialect::Go => {
+                text.starts_with(b"//go:")
+                    || text.starts_with(b"//line ")
+                    || text.starts_with(b"//export ")
+                    || text.starts_with(b"//extern ")
+                    || text.starts_with(b"// +build")
+            }
+            Dialect::JavaScript => {
+                text.starts_with(b"/// <reference")
+                    || text.starts_with(b"/// <amd")
+                    || trimmed_comment_body(text).starts_with(b"@ts-")
+            }
+            _ => false,
+        }
+    }
+
+    /// cgo：紧贴在 `import "C"` 之前的注释是 C 代码序言
+    fn precedes_cgo_import(&self, end: usize) -> bool {
+        if self.dialect != Dialect::Go {
+            return false;
+        }
+        let src = self.s.src;
+        let mut i = end;
+        loop {
+            if i < src.len() && src[i] == b'\r' { i += 1; }
+            if i >= src.len() || src[i] != b'\n' {
+                return false;
+            }
+            i += 1;
+            while i < src.len() && (src[i] == b' ' || src[i] == b'\t') {
+                i += 1;
+            }
+            let rest = &src[i..];
+            if rest.starts_with(b"import \"C\"") {
+                return true;
+            }
+            if rest.starts_with(b"//") {
+                i = self.s.line_end(i);
+                continue;
+            }
+            return false;
+        }
+    }
+
+    fn block_comment(&mut self) {
+        let start = self.s.pos;
+        let end = self.block_comment_end(start);
+        let text = &self.s.src[start..end];
+
+        let is_doc = (text.starts_with(b"/**") && !text.starts_with(b"/**/") && !text.starts_with(b"/***"))
+            || text.starts_with(b"/*!");
+
+        if (is_doc && self.keep_docs) || self.precedes_cgo_import(end) {
+            self.s.copy_to(end);
+        } else {
+            self.s.drop_to(end);
+        }
+    }
+
+    fn block_comment_end(&self, start: usize) -> usize {
+        let src = self.s.src;
+        if !self.nested_blocks() {
+            return self.s.find(b"*/", start + 2).map(|i| i + 2).unwrap_or(src.len());
+        }
+
+        let mut depth = 0usize;
+        let mut i = start;
+        while i < src.len() {
+            if src[i] == b'/' && src.get(i + 1) == Some(&b'*') {
+                depth += 1;
+                i += 2;
+            } else if src[i] == b'*' && src.get(i + 1) == Some(&b'/') {
+                depth -= 1;
+                i += 2;
+                if depth == 0 {
+                    return i;
+                }
+            } else {
+                i += 1;
+            }
+        }
+        src.len()
+    }
+
+    // ------------------------------------------------------------------
+    // Literals
+    // ------------------------------------------------------------------
+
+    fn ident(&mut self) {
+        let start = self.s.pos;
+        let end = self.s.ident_end(start);
+        let word = &self.s.src[start..end];
+        let next = self.s.src.get(end).copied().unwrap_or(0);
+
+        match self.dialect {
+            Dialect::Rust => {
+                if matches!(word, b"r" | b"br" | b"cr") && (next == b'"' || (next == b'#' && self.rust_raw_follows(end))) {
+                    self.s.copy_to(end);
+                    self.rust_raw_string();
+                    return;
+                }
+                if matches!(word, b"b" | b"c") && next == b'"' {
+                    self.s.copy_to(end);
+                    self.quoted(b'"', true, Interp::None);
+                    return;
+                }
+                if word == b"b" && next == b'\'' {
+                    self.s.copy_to(end);
+                    self.char_literal();
+                    return;
+                }
+            }
+            Dialect::C if matches!(word, b"R" | b"u8R" | b"uR" | b"UR" | b"LR") && next == b'"' => {
+                self.s.copy_to(end);
+                self.cpp_raw_string();
+                return;
+            }
+            Dialect::Dart if word == b"r" && (next == b'"' || next == b'\'') => {
+                self.s.copy_to(end);
+                self.dart_raw_string();
+                return;
+            }
+            _ => {}
+        }
+
+        self.regex_ok = self.dialect == Dialect::JavaScript && JS_REGEX_KEYWORDS.contains(&word);
+        self.s.copy_to(end);
+    }
+
+    fn number(&mut self) {
+        let src = self.s.src;
+        let mut i = self.s.pos;
+        while i < src.len() {
+            let c = src[i];
+            let separator = c == b'\'' && self.dialect == Dialect::C
+                && src.get(i + 1).is_some_and(|b| b.is_ascii_alphanumeric());
+            if c.is_ascii_alphanumeric() || c == b'_' || c == b'.' || separator {
+                i += 1;
+            } else {
+                break;
+            }
+        }
+        self.s.copy_to(i);
+        self.regex_ok = false;
+    }
+
+    fn double_quote(&mut self) {
+        match self.dialect {
+            Dialect::Java if self.s.starts_with(b"\"\"\"") => self.triple(b'"', true, Interp::None),
+            Dialect::Kotlin if self.s.starts_with(b"\"\"\"") => self.triple(b'"', false, Interp::Dollar),
+            Dialect::Swift if self.s.starts_with(b"\"\"\"") => self.triple(b'"', true, Interp::Paren),
+            Dialect::Dart if self.s.starts_with(b"\"\"\"") => self.triple(b'"', true, Interp::Dollar),
+            Dialect::CSharp if self.s.starts_with(b"\"\"\"") => self.csharp_raw(),
+            Dialect::Kotlin | Dialect::Dart => self.quoted(b'"', true, Interp::Dollar),
+            Dialect::Swift => self.quoted(b'"', true, Interp::Paren),
+            _ => self.quoted(b'"', true, Interp::None),
+        }
+    }
+
+    fn single_quote(&mut self) {
+        match self.dialect {
+            Dialect::Rust => {
+                if self.rust_char_follows() {
+                    self.char_literal();
+                } else {
+                    // 生命周期 / 标签：`'a`、`'static`
+                    self.s.copy(1);
+                    self.regex_ok = false;
+                }
+            }
+            Dialect::Dart if self.s.starts_with(b"'''") => self.triple(b'\'', true, Interp::Dollar),
+            Dialect::Dart => self.quoted(b'\'', true, Interp::Dollar),
+            Dialect::JavaScript => self.quoted(b'\'', true, Interp::None),
+            _ => self.char_literal(),
+        }
+    }
+
+    fn backtick(&mut self) {
+        match self.dialect {
+            Dialect::JavaScript => self.template_literal(),
+            Dialect::Go => {
+                let end = self.s.find(b"`", self.s.pos + 1).map(|i| i + 1).unwrap_or(self.s.src.len());
+                self.s.copy_to(end);
+            }
+            Dialect::Kotlin | Dialect::Swift => {
+                // 反引号标识符，如 fun `test name`()
+                let end = self.s.quoted_end(self.s.pos, b'`', false, false);
+                self.s.copy_to(end);
+            }
+            _ => self.s.copy(1),
+        }
+        self.regex_ok = false;
+    }
+
+    /// 单行或多行（取决于方言）的普通字符串，支持插值
+    fn quoted(&mut self, quote: u8, escapes: bool, interp: Interp) {
+        let multiline = self.dialect == Dialect::Rust;
+        self.s.copy(1);
+
+        while !self.s.eof() {
+            let c = self.s.cur();
+            if escapes && c == b'\\' {
+                if interp == Interp::Paren && self.s.at(1) == b'(' {
+                    self.s.copy(2);
+                    self.hole(b')');
+                } else if self.s.at(1) == b'\r' && self.s.at(2) == b'\n' {
+                    self.s.copy(3);
+                } else {
+                    self.s.copy(2);
+                }
+                continue;
+            }
+            if c == quote {
+                self.s.copy(1);
+                break;
+            }
+            if c == b'\n' && !multiline {
+                break;
+            }
+            if self.try_hole(interp) {
+                continue;
+            }
+            self.s.copy(1);
+        }
+        self.regex_ok = false;
+    }
+
+    /// 三引号多行字符串（Java 文本块、Kotlin、Swift、Dart）
+    fn triple(&mut self, quote: u8, escapes: bool, interp: Interp) {
+        self.s.copy(3);
+        let closing = [quote; 3];
+
+        while !self.s.eof() {
+            let c = self.s.cur();
+            if escapes && c == b'\\' {
+                if interp == Interp::Paren && self.s.at(1) == b'(' {
+                    self.s.copy(2);
+                    self.hole(b')');
+                } else {
+                    self.s.copy(2);
+                }
+                continue;
+            }
+            if self.s.starts_with(&closing) {
+                // Kotlin 允许 `""""` 这种“引号 + 结束符”的写法
+                while self.s.cur() == quote {
+                    self.s.copy(1);
+                }
+                break;
+            }
+            if self.try_hole(interp) {
+                continue;
+            }
+            self.s.copy(1);
+        }
+        self.regex_ok = false;
+    }
+
+    fn try_hole(&mut self, interp: Interp) -> bool {
+        match interp {
+            Interp::Dollar if self.s.cur() == b'$' && self.s.at(1) == b'{' => {
+                self.s.copy(2);
+                self.hole(b'}');
+                true
+            }
+            Interp::Brace if self.s.cur() == b'{' => {
+                if self.s.at(1) == b'{' {
+                    self.s.copy(2);
+                } else {
+                    self.s.copy(1);
+                    self.hole(b'}');
+                }
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// 插值表达式里是完整的代码（可以再嵌套字符串和注释）
+    fn hole(&mut self, close: u8) {
+        let saved = self.regex_ok;
+        self.regex_ok = true;
+        self.code(Some(close));
+        self.regex_ok = saved;
+    }
+
+    fn char_literal(&mut self) {
+        let end = self.s.quoted_end(self.s.pos, b'\'', true, false);
+        self.s.copy_to(end);
+        self.regex_ok = false;
+    }
+
+    fn template_literal(&mut self) {
+        self.s.copy(1);
+        while !self.s.eof() {
+            match self.s.cur() {
+                b'\\' => self.s.copy(2),
+                b'`' => {
+                    self.s.copy(1);
+                    break;
+                }
+                b'$' if self.s.at(1) == b'{' => {
+                    self.s.copy(2);
+                    self.hole(b'}');
+                }
+                _ => self.s.copy(1),
+            }
+        }
+    }
+
+    fn regex(&mut self) {
+        let src = self.s.src;
+        let mut i = self.s.pos + 1;
+        let mut in_class = false;
+        while i < src.len() {
+            match src[i] {
+                b'\\' => i += 1,
+                b'[' => in_class = true,
+                b']' => in_class = false,
+                b'/' if !in_class => {
+                    i += 1;
+                    break;
+                }
+                b'\n' => break,
+                _ => {}
+            }
+            i += 1;
+        }
+        self.s.copy_to(i.min(src.len()));
+        self.regex_ok = false;
+    }
+
+    // ------------------------------------------------------------------
+    // Dialect specific strings
+    // ------------------------------------------------------------------
+
+    fn rust_raw_follows(&self, from: usize) -> bool {
+        let src = self.s.src;
+        let mut i = from;
+        while i < src.len() && src[i] == b'#' {
+            i += 1;
+        }
+        src.get(i) == Some(&b'"')
+    }
+
+    /// `'x'`、`'\n'`、`'\u{1F600}'` 是字符；`'a`、`'static` 是生命周期
+    fn rust_char_follows(&self) -> bool {
+        let src = self.s.src;
+        let i = self.s.pos + 1;
+        match src.get(i) {
+            Some(b'\\') => true,
+            Some(&b) => {
+                let width = utf8_width(b);
+                src.get(i + width) == Some(&b'\'')
+            }
+            None => false,
+        }
+    }
+
+    fn rust_raw_string(&mut self) {
+        let src = self.s.src;
+        let mut i = self.s.pos;
+        let mut hashes = 0;
+        while i < src.len() && src[i] == b'#' {
+            hashes += 1;
+            i += 1;
+        }
+        i += 1; // 开头的引号
+
+        let mut closing = vec![b'"'];
+        closing.extend(std::iter::repeat_n(b'#', hashes));
+        let end = self.s.find(&closing, i).map(|j| j + closing.len()).unwrap_or(src.len());
+        self.s.copy_to(end);
+        self.regex_ok = false;
+    }
+
+    /// C++11 原始字符串：R"delim( ... )delim"
+    fn cpp_raw_string(&mut self) {
+        let src = self.s.src;
+        let open = self.s.pos;
+        let delim_end = src[open + 1..]
+            .iter()
+            .take(17)
+            .position(|&b| b == b'(')
+            .map(|i| open + 1 + i);
+
+        let Some(paren) = delim_end else {
+            self.quoted(b'"', true, Interp::None);
+            return;
+        };
+        let delim = &src[open + 1..paren];
+        if delim.iter().any(|&b| is_space(b) || b == b')' || b == b'\\') {
+            self.quoted(b'"', true, Interp::None);
+            return;
+        }
+
+        let mut closing = vec![b')'];
+        closing.extend_from_slice(delim);
+        closing.push(b'"');
+        let end = self.s.find(&closing, paren + 1).map(|j| j + closing.len()).unwrap_or(src.len());
+        self.s.copy_to(end);
+        self.regex_ok = false;
+    }
+
+    fn dart_raw_string(&mut self) {
+        let quote = self.s.cur();
+        let triple = [quote; 3];
+        let end = if self.s.starts_with(&triple) {
+            self.s.find(&triple, self.s.pos + 3).map(|i| i + 3).unwrap_or(self.s.src.len())
+        } else {
+            self.s.quoted_end(self.s.pos, quote, false, false)
+        };
+        self.s.copy_to(end);
+    }
+
+    /// Swift 扩展分隔符字符串：#"..."#、#"""..."""#
+    fn swift_raw(&mut self) {
+        let src = self.s.src;
+        let start = self.s.pos;
+        let mut i = start;
+        while i < src.len() && src[i] == b'#' {
+            i += 1;
+        }
+        if src.get(i) != Some(&b'"') {
+            self.s.copy_to(i);
+            return;
+        }
+        let hashes = i - start;
+        let quotes = if src[i..].starts_with(b"\"\"\"") { 3 } else { 1 };
+
+        let mut closing = vec![b'"'; quotes];
+        closing.extend(std::iter::repeat_n(b'#', hashes));
+        let end = self.s.find(&closing, i + quotes).map(|j| j + closing.len()).unwrap_or(src.len());
+        self.s.copy_to(end);
+    }
+
+    /// C# 逐字字符串 `@"..."`（`""` 转义）与插值字符串 `$"..."`、`$@"..."`
+    fn csharp_string(&mut self) -> bool {
+        let src = self.s.src;
+        let start = self.s.pos;
+        let mut i = start;
+        let mut verbatim = false;
+        let mut interpolated = false;
+        while i < src.len() && (src[i] == b'@' || src[i] == b'$') {
+            verbatim |= src[i] == b'@';
+            interpolated |= src[i] == b'$';
+            i += 1;
+        }
+        if src.get(i) != Some(&b'"') || i - start > 3 {
+            return false;
+        }
+
+        if src[i..].starts_with(b"\"\"\"") {
+            self.s.copy_to(i);
+            self.csharp_raw();
+            return true;
+        }
+
+        self.s.copy_to(i + 1);
+        while !self.s.eof() {
+            let c = self.s.cur();
+            if verbatim && c == b'"' && self.s.at(1) == b'"' {
+                self.s.copy(2);
+                continue;
+            }
+            if !verbatim && c == b'\\' {
+                self.s.copy(2);
+                continue;
+            }
+            if c == b'"' {
+                self.s.copy(1);
+                break;
+            }
+            if !verbatim && c == b'\n' {
+                break;
+            }
+            if interpolated && self.try_hole(Interp::Brace) {
+                continue;
+            }
+            self.s.copy(1);
+        }
+        self.regex_ok = false;
+        true
+    }
+
+    /// C# 11 原始字符串：三个及以上引号开头，以同样数量的引号结束
+    fn csharp_raw(&mut self) {
+        let src = self.s.src;
+        let start = self.s.pos;
+        let mut i = start;
+        while i < src.len() && src[i] == b'"' {
+            i += 1;
+        }
+        let closing = vec![b'"'; i - start];
+        let end = self.s.find(&closing, i).map(|j| j + closing.len()).unwrap_or(src.len());
+        self.s.copy_to(end);
+        self.regex_ok = false;
+    }
+}
+
+fn utf8_width(first: u8) -> usize {
+    match first {
+        0xF0..=0xFF => 4,
+        0xE0..=0xEF => 3,
+        0xC0..=0xDF => 2,
+        _ => 1,
+    }
+}
+
+/// 去掉注释标记和前导空白，得到注释正文
+fn trimmed_comment_body(text: &[u8]) -> &[u8] {
+    let mut body = text;
+    while let Some((&first, rest)) = body.split_first() {
+        if first == b'/' || is_space(first) {
+            body = rest;
+        } else {
+            break;
+        }
+    }
+    body
+}
@@ -0,0 +1,168 @@
+use super::Scanner;
+
+pub(super) fn strip_toml(src: &str) -> Vec<u8> {
+    let mut s = Scanner::new(src);
+
+    while !s.eof() {
+        match s.cur() {
+            b'#' => {
+                let end = s.line_end(s.pos);
+                s.drop_to(end);
+            }
+            b'"' if s.starts_with(b"\"\"\"") => {
+                let end = multiline_end(&s, b'"', true);
+                s.copy_to(end);
+            }
+            b'\'' if s.starts_with(b"'''") => {
+                let end = multiline_end(&s, b'\'', false);
+                s.copy_to(end);
+            }
+            b'"' => {
+                let end = s.quoted_end(s.pos, b'"', true, false);
+                s.copy_to(end);
+            }
+            b'\'' => {
+                let end = s.quoted_end(s.pos, b'\'', false, false);
+                s.copy_to(end);
+            }
+            _ => s.copy(1),
+        }
+    }
+
+    s.finish()
+}
+
+/// TOML 多行字符串：结束符后面最多还能紧跟两个引号（属于内容）
+fn multiline_end(s: &Scanner, quote: u8, escapes: bool) -> usize {
+    let src = s.src;
+    let closing = [quote; 3];
+    let mut i = s.pos + 3;
+    while i < src.len() {
+        if escapes && src[i] == b'\\' {
+            i += 2;
+            continue;
+        }
+        if src[i..].starts_with(&closing) {
+            let mut end = i + 3;
+            while end < src.len() && end < i + 5 && src[end] == quote {
+                end += 1;
+            }
+            return end;
+        }
+        i += 1;
+    }
+    src.len()
+}
+
+/// YAML：`#` 只有在行首或空白之后才是注释；引号标量和块标量（`|` / `>`）里的内容原样保留
+pub(super) fn strip_yaml(src: &str) -> Vec<u8> {
+    let mut s = Scanner::new(src);
+    let mut block_parent: Option<usize> = None;
+    let mut line_start = 0usize;
+    let mut comment_at: Option<usize> = None;
+
+    while !s.eof() {
+        if s.at_line_start() {
+            line_start = s.pos;
+            comment_at = None;
+
+            if let Some(parent) = block_parent {
+                let (indent, blank) = line_indent(&s, s.pos);
+                if blank || indent > parent {
+                    let next = s.next_line_start(s.pos);
+                    s.copy_to(next);
+                    continue;
+                }
+                block_parent = None;
+            }
+        }
+
+        let c = s.cur();
+        let token_start = matches!(s.prev(), None | Some(b' ' | b'\t' | b'\n' | b'[' | b'{' | b','));
+        match c {
+            b'#' if matches!(s.prev(), None | Some(b' ' | b'\t' | b'\n')) => {
+                comment_at = Some(s.pos);
+                let end = s.line_end(s.pos);
+                s.drop_to(end);
+            }
+            b'\'' if token_start => {
+                let end = single_quoted_end(&s);
+                s.copy_to(end);
+            }
+            b'"' if token_start => {
+                let end = s.quoted_end(s.pos, b'"', true, true);
+                s.copy_to(end);
+            }
+            b'\n' => {
+                let code_end = comment_at.unwrap_or(s.pos);
+                let line = &s.src[line_start..code_end];
+                block_parent = block_scalar_parent(line);
+                s.copy(1);
+            }
+            _ => s.copy(1),
+        }
+    }
+
+    s.finish()
+}
+
+/// 单引号标量：`''` 表示一个引号，可跨行
+fn single_quoted_end(s: &Scanner) -> usize {
+    let src = s.src;
+    let mut i = s.pos + 1;
+    while i < src.len() {
+        if src[i] == b'\'' {
+            if src.get(i + 1) == Some(&b'\'') {
+                i += 2;
+                continue;
+            }
+            return i + 1;
+        }
+        i += 1;
+    }
+    src.len()
+}
+
+fn line_indent(s: &Scanner, from: usize) -> (usize, bool) {
+    let src = s.src;
+    let mut i = from;
+    while i < src.len() && src[i] == b' ' {
+        i += 1;
+    }
+    let blank = i >= src.len() || matches!(src[i], b'\n' | b'\r');
+    (i - from, blank)
+}
+
+/// 如果这一行以块标量指示符结尾（`key: |`、`- >-`），返回其内容必须超过的缩进列
+fn block_scalar_parent(line: &[u8]) -> Option<usize> {
+    let mut end = line.len();
+    while end > 0 && matches!(line[end - 1], b' ' | b'\t' | b'\r') {
+        end -= 1;
+    }
+    let line = &line[..end];
+
+    let token_start = line.iter().rposition(|&b| b == b' ' || b == b'\t').map(|i| i + 1).unwrap_or(0);
+    let token = &line[token_start..];
+    let (&first, modifiers) = token.split_first()?;
+    if !(first == b'|' || first == b'>') || !modifiers.iter().all(|&b| b == b'+' || b == b'-' || (b'1'..=b'9').contains(&b)) {
+        return None;
+    }
+
+    // 父级列：跳过缩进和序列项的 `- `；如果这一行只有 `- |`，以最后一个 `-` 为准
+    let mut i = 0;
+    while i < line.len() && line[i] == b' ' {
+        i += 1;
+    }
+    let mut last_dash = None;
+    while line[i..].starts_with(b"- ") || line[i..].starts_with(b"-\t") {
+        last_dash = Some(i);
+        i += 2;
+        while i < line.len() && (line[i] == b' ' || line[i] == b'\t') {
+            i += 1;
+        }
+    }
+    if i >= token_start {
+        return Some(last_dash.unwrap_or(0));
+    }
+    Some(i)
+}
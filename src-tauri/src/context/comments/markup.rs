@@ -0,0 +1,143 @@
+use super::{is_space, strip_raw, Scanner};
+use crate::context::language::Language;
+
+/// HTML / XML / Vue / Svelte：删除 `<!-- -->`，保留 IE 条件注释和 CDATA；
+/// `<script>`、`<style>` 的内容交给对应语言的扫描器处理
+pub(super) fn strip(src: &str, keep_docs: bool) -> Vec<u8> {
+    let mut s = Scanner::new(src);
+
+    while !s.eof() {
+        if s.cur() != b'<' {
+            s.copy(1);
+            continue;
+        }
+
+        if s.starts_with(b"<!--") {
+            let end = s.find(b"-->", s.pos + 4).map(|i| i + 3).unwrap_or(s.src.len());
+            if s.starts_with(b"<!--[if") || s.starts_with(b"<!--<![endif]") {
+                s.copy_to(end);
+            } else {
+                s.drop_to(end);
+            }
+        } else if s.starts_with(b"<![CDATA[") {
+            let end = s.find(b"]]>", s.pos + 9).map(|i| i + 3).unwrap_or(s.src.len());
+            s.copy_to(end);
+        } else if is_tag(&s, b"script") {
+            let tag_end = tag_end(&s);
+            let embedded = script_language(&s.src[s.pos..tag_end]);
+            s.copy_to(tag_end);
+            embedded_body(&mut s, b"</script", embedded, keep_docs);
+        } else if is_tag(&s, b"style") {
+            let tag_end = tag_end(&s);
+            let line_comments = matches!(
+                attr_value(&s.src[s.pos..tag_end], b"lang").as_deref(),
+                Some("scss" | "sass" | "less" | "stylus")
+            );
+            s.copy_to(tag_end);
+            let lang = if line_comments { Language::Scss } else { Language::Css };
+            embedded_body(&mut s, b"</style", Some(lang), keep_docs);
+        } else if s.at(1).is_ascii_alphabetic() || matches!(s.at(1), b'/' | b'!' | b'?') {
+            let end = tag_end(&s);
+            s.copy_to(end);
+        } else {
+            s.copy(1);
+        }
+    }
+
+    s.finish()
+}
+
+/// 当前位置是否是 `<name` 开始标签（大小写不敏感）
+fn is_tag(s: &Scanner, name: &[u8]) -> bool {
+    let rest = &s.src[s.pos + 1..];
+    rest.len() > name.len()
+        && rest[..name.len()].eq_ignore_ascii_case(name)
+        && (is_space(rest[name.len()]) || matches!(rest[name.len()], b'>' | b'/'))
+}
+
+/// 标签结束位置（`>` 之后），属性值里的 `>` 不算
+fn tag_end(s: &Scanner) -> usize {
+    let src = s.src;
+    let mut i = s.pos + 1;
+    let mut quote = None;
+    while i < src.len() {
+        let c = src[i];
+        match quote {
+            Some(q) if c == q => quote = None,
+            Some(_) => {}
+            None if c == b'"' || c == b'\'' => quote = Some(c),
+            None if c == b'>' => return i + 1,
+            None => {}
+        }
+        i += 1;
+    }
+    src.len()
+}
+
+/// 取属性值（小写）。只做够用的解析：`name="v"`、`name='v'`、`name=v`
+fn attr_value(tag: &[u8], name: &[u8]) -> Option<String> {
+    let mut i = 0;
+    while i + name.len() < tag.len() {
+        let boundary = i > 0 && is_space(tag[i - 1]);
+        if boundary && tag[i..i + name.len()].eq_ignore_ascii_case(name) {
+            let mut j = i + name.len();
+            while j < tag.len() && is_space(tag[j]) {
+                j += 1;
+            }
+            if tag.get(j) == Some(&b'=') {
+                j += 1;
+                while j < tag.len() && is_space(tag[j]) {
+                    j += 1;
+                }
+                let (start, end) = match tag.get(j) {
+                    Some(&q) if q == b'"' || q == b'\'' => {
+                        let end = tag[j + 1..].iter().position(|&b| b == q).map(|p| p + j + 1).unwrap_or(tag.len());
+                        (j + 1, end)
+                    }
+                    _ => {
+                        let end = tag[j..]
+                            .iter()
+                            .position(|&b| is_space(b) || b == b'>' || b == b'/')
+                            .map(|p| p + j)
+                            .unwrap_or(tag.len());
+                        (j, end)
+                    }
+                };
+                return Some(String::from_utf8_lossy(&tag[start..end]).to_ascii_lowercase());
+            }
+        }
+        i += 1;
+    }
+    None
+}
+
+/// `<script>` 的语言；JSON、模板等非脚本内容返回 None（原样保留）
+fn script_language(tag: &[u8]) -> Option<Language> {
+    if let Some(lang) = attr_value(tag, b"lang") {
+        return match lang.as_str() {
+            "ts" | "tsx" | "typescript" => Some(Language::TypeScript),
+            "js" | "jsx" | "javascript" => Some(Language::JavaScript),
+            _ => None,
+        };
+    }
+    match attr_value(tag, b"type").as_deref() {
+        None | Some("" | "module" | "text/babel" | "text/jsx") => Some(Language::JavaScript),
+        Some(t) if t.contains("javascript") || t.contains("ecmascript") => Some(Language::JavaScript),
+        Some(t) if t.contains("typescript") => Some(Language::TypeScript),
+        Some(_) => None,
+    }
+}
+
+/// 处理 `<script>` / `<style>` 的内容直到对应的结束标签
+fn embedded_body(s: &mut Scanner, closing: &[u8], lang: Option<Language>, keep_docs: bool) {
+    let end = s.find_ci(closing, s.pos).unwrap_or(s.src.len());
+    match lang {
+        Some(lang) => {
+            // 两端都在 ASCII 字节上，切片一定是合法的 UTF-8
+            let body = std::str::from_utf8(&s.src[s.pos..end]).unwrap_or_default();
+            s.out.extend_from_slice(&strip_raw(body, lang, keep_docs));
+            s.pos = end;
+        }
+        None => s.copy_to(end),
+    }
+}
@@ -0,0 +1,266 @@
+// ============================================================================
+// Comment Stripping
+// ============================================================================
+//
+// 每种语言一个轻量词法扫描器：只识别字符串、字符字面量、注释这几类 token，
+// 其余字节原样拷贝。被删掉的注释会保留其中的换行，使输出与原文逐行对齐，
+// 最后由 `tidy` 统一清理“整行都是注释”的空行和行尾空白。
+
+mod c_like;
+mod config;
+mod markup;
+mod python;
+mod script;
+mod shell;
+mod sql;
+mod styles;
+#[cfg(test)]
+mod tests;
+
+use super::language::Language;
+use c_like::Dialect;
+
+pub fn strip(content: &str, lang: Language, keep_docs: bool) -> String {
+    let stripped = strip_raw(content, lang, keep_docs);
+    tidy(content, stripped)
+}
+
+/// 不做整理的原始输出，供 HTML 等内嵌语言复用（保证行数不变）
+fn strip_raw(content: &str, lang: Language, keep_docs: bool) -> Vec<u8> {
+    match lang {
+        Language::Rust => c_like::strip(content, Dialect::Rust, keep_docs),
+        Language::C | Language::Cpp => c_like::strip(content, Dialect::C, keep_docs),
+        Language::Go => c_like::strip(content, Dialect::Go, keep_docs),
+        Language::Java => c_like::strip(content, Dialect::Java, keep_docs),
+        Language::Kotlin => c_like::strip(content, Dialect::Kotlin, keep_docs),
+        Language::Swift => c_like::strip(content, Dialect::Swift, keep_docs),
+        Language::Dart => c_like::strip(content, Dialect::Dart, keep_docs),
+        Language::CSharp => c_like::strip(content, Dialect::CSharp, keep_docs),
+        Language::JavaScript | Language::TypeScript => c_like::strip(content, Dialect::JavaScript, keep_docs),
+        Language::Python => python::strip(content, keep_docs),
+        Language::Shell => shell::strip(content),
+        Language::Dockerfile => shell::strip_dockerfile(content),
+        Language::Ruby => script::strip_ruby(content, keep_docs),
+        Language::Perl => script::strip_perl(content, keep_docs),
+        Language::Yaml => config::strip_yaml(content),
+        Language::Toml => config::strip_toml(content),
+        Language::Html | Language::Xml | Language::Vue | Language::Svelte => markup::strip(content, keep_docs),
+        Language::Css => styles::strip(content, keep_docs, false),
+        Language::Scss | Language::Less => styles::strip(content, keep_docs, true),
+        Language::Sql => sql::strip(content),
+    }
+}
+
+/// 逐行对比原文与剥离结果：
+/// - 未变化的行原样保留（包括作者自己留的空行）
+/// - 变化过的行去掉行尾空白（保留原有的 `\r`）
+/// - 原本有内容、剥离后变空的行直接删除
+fn tidy(original: &str, stripped: Vec<u8>) -> String {
+    let stripped = String::from_utf8(stripped)
+        .unwrap_or_else(|e| String::from_utf8_lossy(e.as_bytes()).into_owned());
+
+    if original.matches('\n').count() != stripped.matches('\n').count() {
+        return stripped;
+    }
+
+    let mut result = String::with_capacity(stripped.len());
+    let mut first = true;
+
+    for (old, new) in original.split('\n').zip(stripped.split('\n')) {
+        if old == new {
+            if !first { result.push('\n'); }
+            result.push_str(new);
+            first = false;
+            continue;
+        }
+
+        let trimmed = new.trim_end();
+        if trimmed.trim_start().is_empty() && !old.trim().is_empty() {
+            continue;
+        }
+
+        if !first { result.push('\n'); }
+        result.push_str(trimmed);
+        if old.ends_with('\r') {
+            result.push('\r');
+        }
+        first = false;
+    }
+
+    if original.ends_with('\n') && !result.is_empty() && !result.ends_with('\n') {
+        result.push('\n');
+    }
+
+    result
+}
+
+fn is_ident_byte(b: u8) -> bool {
+    b.is_ascii_alphanumeric() || b == b'_' || b >= 0x80
+}
+
+fn is_space(b: u8) -> bool {
+    b == b' ' || b == b'\t' || b == b'\r' || b == b'\n' || b == 0x0c
+}
+
+/// 所有扫描器共享的游标：`src` 只读，`out` 只追加
+struct Scanner<'a> {
+    src: &'a [u8],
+    pos: usize,
+    out: Vec<u8>,
+}
+
+impl<'a> Scanner<'a> {
+    fn new(src: &'a str) -> Self {
+        Self {
+            src: src.as_bytes(),
+            pos: 0,
+            out: Vec::with_capacity(src.len()),
+        }
+    }
+
+    fn eof(&self) -> bool {
+        self.pos >= self.src.len()
+    }
+
+    /// 当前位置之后第 `off` 个字节，越界返回 0
+    fn at(&self, off: usize) -> u8 {
+        self.src.get(self.pos + off).copied().unwrap_or(0)
+    }
+
+    fn cur(&self) -> u8 {
+        self.at(0)
+    }
+
+    fn prev(&self) -> Option<u8> {
+        self.pos.checked_sub(1).map(|i| self.src[i])
+    }
+
+    fn at_line_start(&self) -> bool {
+        matches!(self.prev(), None | Some(b'\n'))
+    }
+
+    fn starts_with(&self, pat: &[u8]) -> bool {
+        self.src[self.pos..].starts_with(pat)
+    }
+
+    fn starts_with_ci(&self, pat: &[u8]) -> bool {
+        let rest = &self.src[self.pos..];
+        rest.len() >= pat.len() && rest[..pat.len()].eq_ignore_ascii_case(pat)
+    }
+
+    fn find(&self, pat: &[u8], from: usize) -> Option<usize> {
+        if from >= self.src.len() || pat.is_empty() {
+            return None;
+        }
+        self.src[from..].windows(pat.len()).position(|w| w == pat).map(|i| from + i)
+    }
+
+    fn find_ci(&self, pat: &[u8], from: usize) -> Option<usize> {
+        if from >= self.src.len() || pat.is_empty() {
+            return None;
+        }
+        self.src[from..].windows(pat.len()).position(|w| w.eq_ignore_ascii_case(pat)).map(|i| from + i)
+    }
+
+    /// 从 `from` 开始找到本行结尾（`\r\n` 或 `\n` 之前）
+    fn line_end(&self, from: usize) -> usize {
+        let nl = self.src[from.min(self.src.len())..]
+            .iter()
+            .position(|&b| b == b'\n')
+            .map(|i| from + i)
+            .unwrap_or(self.src.len());
+        if nl > from && self.src[nl - 1] == b'\r' { nl - 1 } else { nl }
+    }
+
+    /// 跳过当前行（含换行符），返回下一行起点
+    fn next_line_start(&self, from: usize) -> usize {
+        match self.src[from.min(self.src.len())..].iter().position(|&b| b == b'\n') {
+            Some(i) => from + i + 1,
+            None => self.src.len(),
+        }
+    }
+
+    fn copy(&mut self, n: usize) {
+        let end = (self.pos + n).min(self.src.len());
+        self.out.extend_from_slice(&self.src[self.pos..end]);
+        self.pos = end;
+    }
+
+    fn copy_to(&mut self, end: usize) {
+        let end = end.clamp(self.pos, self.src.len());
+        self.out.extend_from_slice(&self.src[self.pos..end]);
+        self.pos = end;
+    }
+
+    fn copy_rest(&mut self) {
+        self.copy_to(self.src.len());
+    }
+
+    /// 删除 `[pos, end)`：保留其中的换行；单行注释夹在两个 token 之间时补一个空格，
+    /// 避免 `a/**/b` 变成 `ab`
+    fn drop_to(&mut self, end: usize) {
+        self.replace_to(end, b"");
+    }
+
+    /// 与 `drop_to` 相同，但先写入 `replacement`（例如 Python 的 `pass`）
+    fn replace_to(&mut self, end: usize, replacement: &[u8]) {
+        let end = end.clamp(self.pos, self.src.len());
+        let newlines = self.src[self.pos..end].iter().filter(|&&b| b == b'\n').count();
+
+        self.out.extend_from_slice(replacement);
+        if newlines > 0 {
+            self.out.extend(std::iter::repeat_n(b'\n', newlines));
+        } else if replacement.is_empty() {
+            let before = self.out.last().copied();
+            let after = self.src.get(end).copied();
+            if let (Some(a), Some(b)) = (before, after) {
+                let joins = |c: u8| !is_space(c) && !b"()[]{},;.".contains(&c);
+                if joins(a) && joins(b) {
+                    self.out.push(b' ');
+                }
+            }
+        }
+        self.pos = end;
+    }
+
+    /// 第一行的 `#!` 解释器声明原样保留（Rust 的 `#![...]` 属性除外）
+    fn copy_shebang(&mut self) {
+        if self.pos == 0 && self.starts_with(b"#!") && !self.starts_with(b"#![") {
+            let end = self.line_end(0);
+            self.copy_to(end);
+        }
+    }
+
+    fn ident_end(&self, from: usize) -> usize {
+        let mut i = from;
+        while i < self.src.len() && is_ident_byte(self.src[i]) {
+            i += 1;
+        }
+        i
+    }
+
+    /// 扫描一个带反斜杠转义的引号串，返回结束位置（闭合引号之后）。
+    /// `multiline == false` 时遇到未转义的换行即视为未闭合，停在换行之前。
+    fn quoted_end(&self, from: usize, quote: u8, escapes: bool, multiline: bool) -> usize {
+        let mut i = from + 1;
+        while i < self.src.len() {
+            let c = self.src[i];
+            if escapes && c == b'\\' {
+                i += 2;
+                continue;
+            }
+            if c == quote {
+                return i + 1;
+            }
+            if c == b'\n' && !multiline {
+                return if i > from + 1 && self.src[i - 1] == b'\r' { i - 1 } else { i };
+            }
+            i += 1;
+        }
+        self.src.len()
+    }
+
+    fn finish(self) -> Vec<u8> {
+        self.out
+    }
+}
@@ -0,0 +1,167 @@
+use super::{is_ident_byte, Scanner};
+
+const STRING_PREFIXES: &[&[u8]] = &[
+    b"r", b"u", b"b", b"f", b"t", b"br", b"rb", b"fr", b"rf", b"tr", b"rt",
+];
+
+pub(super) fn strip(src: &str, keep_docs: bool) -> Vec<u8> {
+    let mut lexer = Lexer {
+        s: Scanner::new(src),
+        keep_docs,
+        depth: 0,
+        stmt_start: true,
+    };
+    lexer.s.copy_shebang();
+    lexer.run();
+    lexer.s.finish()
+}
+
+struct Lexer<'a> {
+    s: Scanner<'a>,
+    keep_docs: bool,
+    /// 括号嵌套深度：括号内的换行不结束语句
+    depth: usize,
+    /// 当前位置是否是一条逻辑语句的开头（只有缩进）
+    stmt_start: bool,
+}
+
+impl Lexer<'_> {
+    fn run(&mut self) {
+        while !self.s.eof() {
+            let c = self.s.cur();
+            match c {
+                b'#' => self.comment(),
+                b'\n' => {
+                    self.s.copy(1);
+                    if self.depth == 0 {
+                        self.stmt_start = true;
+                    }
+                }
+                b'\\' if self.s.at(1) == b'\r' => self.s.copy(3),
+                b'\\' => self.s.copy(2),
+                b' ' | b'\t' | b'\r' | 0x0c => self.s.copy(1),
+                b'"' | b'\'' => self.string(self.s.pos),
+                _ if is_ident_byte(c) => {
+                    let start = self.s.pos;
+                    let end = self.s.ident_end(start);
+                    let word = self.s.src[start..end].to_ascii_lowercase();
+                    let next = self.s.src.get(end).copied().unwrap_or(0);
+                    if (next == b'"' || next == b'\'') && STRING_PREFIXES.contains(&word.as_slice()) {
+                        // 前缀还没拷贝，`string` 会从 `start` 开始处理
+                        self.s.pos = end;
+                        self.string(start);
+                    } else {
+                        self.s.copy_to(end);
+                        self.stmt_start = false;
+                    }
+                }
+                b'(' | b'[' | b'{' => {
+                    self.depth += 1;
+                    self.s.copy(1);
+                    self.stmt_start = false;
+                }
+                b')' | b']' | b'}' => {
+                    self.depth = self.depth.saturating_sub(1);
+                    self.s.copy(1);
+                    self.stmt_start = false;
+                }
+                _ => {
+                    self.s.copy(1);
+                    self.stmt_start = false;
+                }
+            }
+        }
+    }
+
+    /// 前两行的编码声明（PEP 263）会影响解析，必须保留
+    fn comment(&mut self) {
+        let end = self.s.line_end(self.s.pos);
+        let line_no = self.s.src[..self.s.pos].iter().filter(|&&b| b == b'\n').count();
+        let text = &self.s.src[self.s.pos..end];
+        let is_cookie = line_no < 2 && (contains(text, b"coding:") || contains(text, b"coding="));
+
+        if is_cookie {
+            self.s.copy_to(end);
+        } else {
+            self.s.drop_to(end);
+        }
+    }
+
+    /// `token_start` 指向前缀（如 `rb"..."` 中的 `r`），`self.s.pos` 指向引号
+    fn string(&mut self, token_start: usize) {
+        let quote = self.s.cur();
+        let triple = [quote; 3];
+        let end = if self.s.starts_with(&triple) {
+            self.triple_end(self.s.pos + 3, &triple)
+        } else {
+            self.s.quoted_end(self.s.pos, quote, true, false)
+        };
+
+        let is_statement = self.stmt_start && self.depth == 0 && self.ends_statement(end);
+        self.s.pos = token_start;
+
+        if is_statement && !self.keep_docs {
+            // 独立的字符串语句（docstring）：删除后如果代码块变空，用 `pass` 占位
+            let indent = self.indent_of(token_start);
+            let needs_pass = indent > 0 && self.next_code_indent(end).is_none_or(|next| next < indent);
+            let replacement: &[u8] = if needs_pass { b"pass" } else { b"" };
+            self.s.replace_to(end, replacement);
+        } else {
+            self.s.copy_to(end);
+        }
+        self.stmt_start = false;
+    }
+
+    fn triple_end(&self, from: usize, closing: &[u8]) -> usize {
+        let src = self.s.src;
+        let mut i = from;
+        while i < src.len() {
+            if src[i] == b'\\' {
+                i += 2;
+                continue;
+            }
+            if src[i..].starts_with(closing) {
+                return i + closing.len();
+            }
+            i += 1;
+        }
+        src.len()
+    }
+
+    /// 字符串之后只剩空白或注释，才算一条独立语句
+    fn ends_statement(&self, end: usize) -> bool {
+        let src = self.s.src;
+        let mut i = end;
+        while i < src.len() && (src[i] == b' ' || src[i] == b'\t') {
+            i += 1;
+        }
+        i >= src.len() || matches!(src[i], b'\n' | b'\r' | b'#')
+    }
+
+    fn indent_of(&self, pos: usize) -> usize {
+        let src = self.s.src;
+        let line_start = src[..pos].iter().rposition(|&b| b == b'\n').map(|i| i + 1).unwrap_or(0);
+        pos - line_start
+    }
+
+    /// 从 `from` 所在行之后找到下一行真正的代码，返回其缩进宽度
+    fn next_code_indent(&self, from: usize) -> Option<usize> {
+        let src = self.s.src;
+        let mut line = self.s.next_line_start(from);
+        while line < src.len() {
+            let mut i = line;
+            while i < src.len() && matches!(src[i], b' ' | b'\t' | 0x0c) {
+                i += 1;
+            }
+            if i < src.len() && !matches!(src[i], b'\n' | b'\r' | b'#') {
+                return Some(i - line);
+            }
+            line = self.s.next_line_start(i);
+        }
+        None
+    }
+}
+
+fn contains(haystack: &[u8], needle: &[u8]) -> bool {
+    haystack.windows(needle.len()).any(|w| w == needle)
+}
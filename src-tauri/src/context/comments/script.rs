@@ -0,0 +1,367 @@
+use super::{is_ident_byte, is_space, Scanner};
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Flavor {
+    Ruby,
+    Perl,
+}
+
+const RUBY_REGEX_KEYWORDS: &[&[u8]] = &[
+    b"if", b"unless", b"when", b"and", b"or", b"not", b"return", b"then", b"elsif", b"while", b"until",
+];
+
+const PERL_REGEX_KEYWORDS: &[&[u8]] = &[
+    b"split", b"grep", b"map", b"join", b"return", b"and", b"or", b"not", b"if", b"unless", b"while", b"until",
+];
+
+const PERL_QUOTE_OPS: &[&[u8]] = &[b"q", b"qq", b"qw", b"qr", b"m", b"s", b"tr", b"y"];
+
+pub(super) fn strip_ruby(src: &str, keep_docs: bool) -> Vec<u8> {
+    run(src, Flavor::Ruby, keep_docs)
+}
+
+pub(super) fn strip_perl(src: &str, keep_docs: bool) -> Vec<u8> {
+    run(src, Flavor::Perl, keep_docs)
+}
+
+fn run(src: &str, flavor: Flavor, keep_docs: bool) -> Vec<u8> {
+    let mut lexer = Lexer {
+        s: Scanner::new(src),
+        flavor,
+        keep_docs,
+        regex_ok: true,
+        pending: Vec::new(),
+    };
+    lexer.s.copy_shebang();
+    lexer.code(None);
+    lexer.s.finish()
+}
+
+struct Heredoc {
+    delim: Vec<u8>,
+    indented: bool,
+}
+
+struct Lexer<'a> {
+    s: Scanner<'a>,
+    flavor: Flavor,
+    keep_docs: bool,
+    regex_ok: bool,
+    pending: Vec<Heredoc>,
+}
+
+impl Lexer<'_> {
+    fn code(&mut self, close: Option<u8>) {
+        let mut depth = 0usize;
+
+        while !self.s.eof() {
+            if self.s.at_line_start() && self.line_start_block() {
+                continue;
+            }
+
+            let c = self.s.cur();
+            match c {
+                b'#' => {
+                    let end = self.s.line_end(self.s.pos);
+                    self.s.drop_to(end);
+                }
+                b'\n' => {
+                    self.s.copy(1);
+                    self.heredoc_bodies();
+                }
+                b'\\' => self.s.copy(2),
+                b'\'' => self.string(b'\'', false),
+                b'"' | b'`' => self.string(c, self.flavor == Flavor::Ruby),
+                b'$' => {
+                    // `$#array`、`$'`、`$"` 等特殊变量
+                    let next = self.s.at(1);
+                    let n = if b"#'\"`/;\\".contains(&next) { 2 } else { 1 };
+                    self.s.copy(n);
+                    self.regex_ok = false;
+                }
+                b'<' if self.s.starts_with(b"<<") && self.heredoc_start() => {}
+                b'/' if self.regex_ok => self.delimited(b'/', 1),
+                b'/' => {
+                    // 除号，或 Perl 的 `//` / `//=` 运算符
+                    let n = if self.s.at(1) == b'/' { 2 } else { 1 };
+                    self.s.copy(n);
+                    self.regex_ok = true;
+                }
+                b'%' if self.flavor == Flavor::Ruby && self.regex_ok && self.percent_literal() => {}
+                _ if c.is_ascii_digit() => {
+                    let end = self.s.ident_end(self.s.pos);
+                    self.s.copy_to(end);
+                    self.regex_ok = false;
+                }
+                _ if is_ident_byte(c) => self.word(),
+                b'{' | b'(' | b'[' => {
+                    depth += 1;
+                    self.s.copy(1);
+                    self.regex_ok = true;
+                }
+                b'}' | b')' | b']' => {
+                    if depth == 0 && close == Some(c) {
+                        self.s.copy(1);
+                        return;
+                    }
+                    depth = depth.saturating_sub(1);
+                    self.s.copy(1);
+                    self.regex_ok = false;
+                }
+                _ => {
+                    self.s.copy(1);
+                    if !is_space(c) {
+                        self.regex_ok = true;
+                    }
+                }
+            }
+        }
+    }
+
+    /// 行首结构：Ruby `=begin ... =end`、Perl POD、`__END__` 数据段。处理过返回 true
+    fn line_start_block(&mut self) -> bool {
+        let line_end = self.s.line_end(self.s.pos);
+        let line = &self.s.src[self.s.pos..line_end];
+
+        let data_marker = match self.flavor {
+            Flavor::Ruby => line == b"__END__",
+            Flavor::Perl => line == b"__END__" || line == b"__DATA__",
+        };
+        if data_marker {
+            self.s.copy_rest();
+            return true;
+        }
+
+        let (is_block, terminator, is_doc): (bool, &[u8], bool) = match self.flavor {
+            Flavor::Ruby => (
+                line.starts_with(b"=begin") && line.get(6).is_none_or(|&b| is_space(b)),
+                b"=end",
+                false,
+            ),
+            Flavor::Perl => (
+                line.len() > 1 && line[0] == b'=' && line[1].is_ascii_alphabetic(),
+                b"=cut",
+                true,
+            ),
+        };
+        if !is_block {
+            return false;
+        }
+
+        let mut end = self.s.src.len();
+        let mut next = self.s.next_line_start(self.s.pos);
+        while next < self.s.src.len() {
+            if self.s.src[next..].starts_with(terminator) {
+                end = self.s.line_end(next);
+                break;
+            }
+            next = self.s.next_line_start(next);
+        }
+
+        if is_doc && self.keep_docs {
+            self.s.copy_to(end);
+        } else {
+            self.s.drop_to(end);
+        }
+        true
+    }
+
+    fn string(&mut self, quote: u8, interpolate: bool) {
+        self.s.copy(1);
+        while !self.s.eof() {
+            let c = self.s.cur();
+            if c == b'\\' {
+                self.s.copy(2);
+                continue;
+            }
+            if c == quote {
+                self.s.copy(1);
+                break;
+            }
+            if interpolate && c == b'#' && self.s.at(1) == b'{' {
+                self.s.copy(2);
+                let saved = self.regex_ok;
+                self.regex_ok = true;
+                self.code(Some(b'}'));
+                self.regex_ok = saved;
+                continue;
+            }
+            self.s.copy(1);
+        }
+        self.regex_ok = false;
+    }
+
+    fn word(&mut self) {
+        let start = self.s.pos;
+        let end = self.s.ident_end(start);
+        let word = &self.s.src[start..end];
+
+        if self.flavor == Flavor::Perl && PERL_QUOTE_OPS.contains(&word) && self.perl_quote_op(start, end) {
+            return;
+        }
+
+        let keywords = match self.flavor {
+            Flavor::Ruby => RUBY_REGEX_KEYWORDS,
+            Flavor::Perl => PERL_REGEX_KEYWORDS,
+        };
+        self.regex_ok = keywords.contains(&word);
+        self.s.copy_to(end);
+    }
+
+    /// Perl 引用操作符：q() qq{} qw// qr// m// s/// tr/// y///
+    fn perl_quote_op(&mut self, start: usize, end: usize) -> bool {
+        let src = self.s.src;
+        // `$s`、`->y`、`-s $file` 这类是变量/方法/文件测试，不是操作符
+        if start > 0 && b"$@%&*>:-".contains(&src[start - 1]) {
+            return false;
+        }
+
+        let mut i = end;
+        while i < src.len() && (src[i] == b' ' || src[i] == b'\t') {
+            i += 1;
+        }
+        let Some(&delim) = src.get(i) else { return false };
+        if delim == b'#' && i != end {
+            return false;
+        }
+        if delim.is_ascii_alphanumeric() || delim == b'_' || is_space(delim) || b"=;)}]>".contains(&delim) {
+            return false;
+        }
+
+        let parts = if matches!(&src[start..end], b"s" | b"tr" | b"y") { 2 } else { 1 };
+        self.s.copy_to(i);
+        self.delimited(delim, parts);
+        true
+    }
+
+    /// 从当前位置的开界符开始，拷贝 `parts` 段由分隔符包围的内容（支持成对括号嵌套）
+    fn delimited(&mut self, open: u8, parts: usize) {
+        let src = self.s.src;
+        let mut i = self.s.pos;
+        let mut open = open;
+
+        for part in 0..parts {
+            let close = closing_bracket(open);
+            let mut depth = 0usize;
+            i += 1;
+            while i < src.len() {
+                let c = src[i];
+                if c == b'\\' {
+                    i += 2;
+                    continue;
+                }
+                if close != open && c == open {
+                    depth += 1;
+                } else if c == close {
+                    if depth == 0 {
+                        break;
+                    }
+                    depth -= 1;
+                } else if c == b'\n' && open == b'/' && parts == 1 && self.flavor == Flavor::Ruby {
+                    // 没闭合的 `/` 多半是除号
+                    break;
+                }
+                i += 1;
+            }
+
+            // s/a/b/：中间的分隔符同时是第二段的开界符；
+            // s{...}{...}：第二段可以换一种括号，中间允许空白
+            if part + 1 < parts && close != open {
+                i += 1;
+                while i < src.len() && is_space(src[i]) {
+                    i += 1;
+                }
+                match src.get(i) {
+                    Some(&next) => open = next,
+                    None => break,
+                }
+            }
+        }
+
+        self.s.copy_to((i + 1).min(src.len()));
+        self.regex_ok = false;
+    }
+
+    /// Ruby %-字面量：%w[...]、%q(...)、%r{...}、%(...)
+    fn percent_literal(&mut self) -> bool {
+        let src = self.s.src;
+        let mut i = self.s.pos + 1;
+        if src.get(i).is_some_and(|b| b"qQwWiIrsx".contains(b)) {
+            i += 1;
+        }
+        let Some(&delim) = src.get(i) else { return false };
+        if delim.is_ascii_alphanumeric() || is_space(delim) || delim == b'=' {
+            return false;
+        }
+        self.s.copy_to(i);
+        self.delimited(delim, 1);
+        true
+    }
+
+    /// `<<~EOS`、`<<-'SQL'`、`<<"EOF"`；标识符必须以大写字母或下划线开头，避免误判 `a << b`
+    fn heredoc_start(&mut self) -> bool {
+        let src = self.s.src;
+        let mut i = self.s.pos + 2;
+        let mut indented = false;
+        if matches!(src.get(i), Some(b'~') | Some(b'-')) {
+            indented = true;
+            i += 1;
+        }
+
+        let (delim, end) = match src.get(i) {
+            Some(&q) if q == b'\'' || q == b'"' || q == b'`' => {
+                let Some(close) = src[i + 1..].iter().position(|&b| b == q || b == b'\n').map(|p| p + i + 1) else {
+                    return false;
+                };
+                if src[close] != q {
+                    return false;
+                }
+                (src[i + 1..close].to_vec(), close + 1)
+            }
+            Some(&b) if b.is_ascii_uppercase() || b == b'_' => {
+                let end = self.s.ident_end(i);
+                (src[i..end].to_vec(), end)
+            }
+            _ => return false,
+        };
+
+        if delim.is_empty() {
+            return false;
+        }
+        self.pending.push(Heredoc { delim, indented });
+        self.s.copy_to(end);
+        self.regex_ok = false;
+        true
+    }
+
+    fn heredoc_bodies(&mut self) {
+        for doc in std::mem::take(&mut self.pending) {
+            while !self.s.eof() {
+                let src = self.s.src;
+                let line_start = self.s.pos;
+                let end = self.s.line_end(line_start);
+                let next = self.s.next_line_start(line_start);
+                let mut line = &src[line_start..end];
+                if doc.indented {
+                    while let Some((&b, rest)) = line.split_first() {
+                        if b == b' ' || b == b'\t' { line = rest } else { break }
+                    }
+                }
+                self.s.copy_to(next);
+                if line == doc.delim.as_slice() {
+                    break;
+                }
+            }
+        }
+    }
+}
+
+fn closing_bracket(open: u8) -> u8 {
+    match open {
+        b'(' => b')',
+        b'[' => b']',
+        b'{' => b'}',
+        b'<' => b'>',
+        other => other,
+    }
+}
@@ -0,0 +1,253 @@
+use super::{is_space, Scanner};
+
+/// 等待读取正文的 here-document（`<<EOF` / `<<-'EOF'`）
+struct Heredoc {
+    delim: Vec<u8>,
+    strip_tabs: bool,
+}
+
+pub(super) fn strip(src: &str) -> Vec<u8> {
+    let mut lexer = Lexer {
+        s: Scanner::new(src),
+        pending: Vec::new(),
+    };
+    lexer.s.copy_shebang();
+    lexer.code(None);
+    lexer.s.finish()
+}
+
+/// Dockerfile 只有整行注释；文件开头的解析器指令（`# syntax=` 等）必须保留
+pub(super) fn strip_dockerfile(src: &str) -> Vec<u8> {
+    let mut s = Scanner::new(src);
+    let mut in_directives = true;
+
+    while !s.eof() {
+        let line_start = s.pos;
+        let end = s.line_end(line_start);
+        let next = s.next_line_start(line_start);
+        let line = &s.src[line_start..end];
+        let trimmed = trim_start(line);
+
+        if trimmed.starts_with(b"#") {
+            if in_directives && is_parser_directive(trimmed) {
+                s.copy_to(next);
+                continue;
+            }
+            in_directives = false;
+            s.drop_to(end);
+            s.copy_to(next);
+            continue;
+        }
+        in_directives = false;
+
+        let mut pending = Vec::new();
+        let mut i = line_start;
+        while let Some(at) = s.find(b"<<", i).filter(|&at| at < end) {
+            match parse_heredoc(s.src, at) {
+                Some((doc, doc_end)) => {
+                    pending.push(doc);
+                    i = doc_end;
+                }
+                None => i = at + 2,
+            }
+        }
+
+        s.copy_to(next);
+        copy_heredoc_bodies(&mut s, &mut pending);
+    }
+
+    s.finish()
+}
+
+struct Lexer<'a> {
+    s: Scanner<'a>,
+    pending: Vec<Heredoc>,
+}
+
+impl Lexer<'_> {
+    fn code(&mut self, close: Option<u8>) {
+        let mut depth = 0usize;
+
+        while !self.s.eof() {
+            match self.s.cur() {
+                b'#' if self.word_start() => {
+                    let end = self.s.line_end(self.s.pos);
+                    self.s.drop_to(end);
+                }
+                b'\\' => self.s.copy(2),
+                b'\'' => {
+                    let end = self.s.find(b"'", self.s.pos + 1).map(|i| i + 1).unwrap_or(self.s.src.len());
+                    self.s.copy_to(end);
+                }
+                b'"' => self.double_quoted(),
+                b'`' => self.backquoted(),
+                b'$' => self.dollar(),
+                b'<' if self.s.starts_with(b"<<") && !self.s.starts_with(b"<<<") => {
+                    match parse_heredoc(self.s.src, self.s.pos) {
+                        Some((doc, end)) => {
+                            self.pending.push(doc);
+                            self.s.copy_to(end);
+                        }
+                        None => self.s.copy(2),
+                    }
+                }
+                b'\n' => {
+                    self.s.copy(1);
+                    copy_heredoc_bodies(&mut self.s, &mut self.pending);
+                }
+                b'(' => {
+                    depth += 1;
+                    self.s.copy(1);
+                }
+                b')' => {
+                    if depth == 0 && close == Some(b')') {
+                        self.s.copy(1);
+                        return;
+                    }
+                    depth = depth.saturating_sub(1);
+                    self.s.copy(1);
+                }
+                _ => self.s.copy(1),
+            }
+        }
+    }
+
+    /// `#` 只有出现在单词开头时才是注释（`$#`、`${#v}`、`a#b` 都不是）
+    fn word_start(&self) -> bool {
+        match self.s.prev() {
+            None => true,
+            Some(b) => is_space(b) || b";&|()".contains(&b),
+        }
+    }
+
+    fn double_quoted(&mut self) {
+        self.s.copy(1);
+        while !self.s.eof() {
+            match self.s.cur() {
+                b'\\' => self.s.copy(2),
+                b'"' => {
+                    self.s.copy(1);
+                    return;
+                }
+                b'$' => self.dollar(),
+                b'`' => self.backquoted(),
+                _ => self.s.copy(1),
+            }
+        }
+    }
+
+    fn backquoted(&mut self) {
+        self.s.copy(1);
+        while !self.s.eof() {
+            match self.s.cur() {
+                b'\\' => self.s.copy(2),
+                b'`' => {
+                    self.s.copy(1);
+                    return;
+                }
+                _ => self.s.copy(1),
+            }
+        }
+    }
+
+    fn dollar(&mut self) {
+        match self.s.at(1) {
+            b'\'' => {
+                // ANSI-C 字符串 $'...'
+                self.s.copy(1);
+                let end = self.s.quoted_end(self.s.pos, b'\'', true, true);
+                self.s.copy_to(end);
+            }
+            b'(' => {
+                self.s.copy(2);
+                self.code(Some(b')'));
+            }
+            b'{' => {
+                // 参数展开 ${var#prefix}：整体拷贝
+                let src = self.s.src;
+                let mut i = self.s.pos + 2;
+                let mut depth = 1;
+                while i < src.len() && depth > 0 {
+                    match src[i] {
+                        b'\\' => i += 1,
+                        b'{' => depth += 1,
+                        b'}' => depth -= 1,
+                        _ => {}
+                    }
+                    i += 1;
+                }
+                self.s.copy_to(i);
+            }
+            _ => self.s.copy(1),
+        }
+    }
+}
+
+/// 解析 `<<` 处的 here-document 标记，返回标记和标记结束位置
+fn parse_heredoc(src: &[u8], at: usize) -> Option<(Heredoc, usize)> {
+    let mut i = at + 2;
+    let strip_tabs = src.get(i) == Some(&b'-');
+    if strip_tabs {
+        i += 1;
+    }
+    while i < src.len() && (src[i] == b' ' || src[i] == b'\t') {
+        i += 1;
+    }
+
+    let quote = src.get(i).copied().filter(|&b| b == b'\'' || b == b'"');
+    let (delim, end) = if let Some(q) = quote {
+        let close = src[i + 1..].iter().position(|&b| b == q || b == b'\n')? + i + 1;
+        if src[close] != q {
+            return None;
+        }
+        (src[i + 1..close].to_vec(), close + 1)
+    } else {
+        if src.get(i) == Some(&b'\\') {
+            i += 1;
+        }
+        let start = i;
+        while i < src.len() && !is_space(src[i]) && !b";&|<>()".contains(&src[i]) {
+            i += 1;
+        }
+        (src[start..i].to_vec(), i)
+    };
+
+    if delim.is_empty() {
+        return None;
+    }
+    Some((Heredoc { delim, strip_tabs }, end))
+}
+
+/// 在换行之后原样拷贝所有挂起的 here-document 正文（含结束标记行）
+fn copy_heredoc_bodies(s: &mut Scanner, pending: &mut Vec<Heredoc>) {
+    for doc in pending.drain(..) {
+        while !s.eof() {
+            let line_start = s.pos;
+            let end = s.line_end(line_start);
+            let next = s.next_line_start(line_start);
+            let mut line = &s.src[line_start..end];
+            if doc.strip_tabs {
+                while let Some((b'\t', rest)) = line.split_first() {
+                    line = rest;
+                }
+            }
+            s.copy_to(next);
+            if line == doc.delim.as_slice() {
+                break;
+            }
+        }
+    }
+}
+
+fn trim_start(line: &[u8]) -> &[u8] {
+    let skip = line.iter().take_while(|&&b| b == b' ' || b == b'\t').count();
+    &line[skip..]
+}
+
+fn is_parser_directive(comment: &[u8]) -> bool {
+    let body = trim_start(&comment[1..]);
+    let lower = body.to_ascii_lowercase();
+    [b"syntax".as_slice(), b"escape", b"check"].iter().any(|name| {
+        lower.starts_with(name) && trim_start(&lower[name.len()..]).starts_with(b"=")
+    })
+}
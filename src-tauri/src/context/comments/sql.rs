@@ -0,0 +1,82 @@
+use super::{is_ident_byte, Scanner};
+
+/// SQL：`--` 行注释和 `/* */` 块注释。优化器提示 `/*+ ... */` 和 MySQL 条件注释 `/*! ... */`
+/// 会被数据库执行，必须保留
+pub(super) fn strip(src: &str) -> Vec<u8> {
+    let mut s = Scanner::new(src);
+
+    while !s.eof() {
+        match s.cur() {
+            b'-' if s.at(1) == b'-' => {
+                let end = s.line_end(s.pos);
+                s.drop_to(end);
+            }
+            b'/' if s.at(1) == b'*' => {
+                let end = s.find(b"*/", s.pos + 2).map(|i| i + 2).unwrap_or(s.src.len());
+                if matches!(s.at(2), b'+' | b'!') {
+                    s.copy_to(end);
+                } else {
+                    s.drop_to(end);
+                }
+            }
+            b'\'' => {
+                // PostgreSQL 的 E'...' 支持反斜杠转义，标准字符串只用 '' 转义
+                let escapes = matches!(s.prev(), Some(b'e' | b'E'))
+                    && !s.pos.checked_sub(2).is_some_and(|i| is_ident_byte(s.src[i]));
+                let end = doubled_quote_end(&s, b'\'', escapes);
+                s.copy_to(end);
+            }
+            b'"' | b'`' => {
+                let end = doubled_quote_end(&s, s.cur(), false);
+                s.copy_to(end);
+            }
+            b'$' if !s.prev().is_some_and(is_ident_byte) => match dollar_quote_end(&s) {
+                Some(end) => s.copy_to(end),
+                None => s.copy(1),
+            },
+            _ => s.copy(1),
+        }
+    }
+
+    s.finish()
+}
+
+fn doubled_quote_end(s: &Scanner, quote: u8, escapes: bool) -> usize {
+    let src = s.src;
+    let mut i = s.pos + 1;
+    while i < src.len() {
+        let c = src[i];
+        if escapes && c == b'\\' {
+            i += 2;
+            continue;
+        }
+        if c == quote {
+            if src.get(i + 1) == Some(&quote) {
+                i += 2;
+                continue;
+            }
+            return i + 1;
+        }
+        i += 1;
+    }
+    src.len()
+}
+
+/// PostgreSQL 美元引用：`$$ ... $$`、`$body$ ... $body$`（`$1` 这类占位符除外）
+fn dollar_quote_end(s: &Scanner) -> Option<usize> {
+    let src = s.src;
+    let start = s.pos;
+    let mut i = start + 1;
+    if src.get(i).is_some_and(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    while i < src.len() && (src[i].is_ascii_alphanumeric() || src[i] == b'_') {
+        i += 1;
+    }
+    if src.get(i) != Some(&b'$') {
+        return None;
+    }
+    let tag = &src[start..=i];
+    let end = s.find(tag, i + 1).map(|j| j + tag.len()).unwrap_or(src.len());
+    Some(end)
+}
@@ -0,0 +1,50 @@
+use super::{is_ident_byte, Scanner};
+
+/// CSS 只有 `/* */`；SCSS / Less 额外支持 `//` 行注释。`/*! */` 视为需要保留的文档/版权注释
+pub(super) fn strip(src: &str, keep_docs: bool, line_comments: bool) -> Vec<u8> {
+    let mut s = Scanner::new(src);
+
+    while !s.eof() {
+        match s.cur() {
+            b'/' if s.at(1) == b'*' => {
+                let end = s.find(b"*/", s.pos + 2).map(|i| i + 2).unwrap_or(s.src.len());
+                if keep_docs && s.starts_with(b"/*!") {
+                    s.copy_to(end);
+                } else {
+                    s.drop_to(end);
+                }
+            }
+            b'/' if line_comments && s.at(1) == b'/' => {
+                let end = s.line_end(s.pos);
+                s.drop_to(end);
+            }
+            b'"' | b'\'' => {
+                let end = s.quoted_end(s.pos, s.cur(), true, false);
+                s.copy_to(end);
+            }
+            b'u' | b'U' if s.starts_with_ci(b"url(") && !s.prev().is_some_and(is_ident_byte) => {
+                s.copy(4);
+                copy_url_body(&mut s);
+            }
+            _ => s.copy(1),
+        }
+    }
+
+    s.finish()
+}
+
+/// 未加引号的 `url(http://...)` 里的 `//` 不是注释
+fn copy_url_body(s: &mut Scanner) {
+    while s.pos < s.src.len() && matches!(s.cur(), b' ' | b'\t') {
+        s.copy(1);
+    }
+    if matches!(s.cur(), b'"' | b'\'') {
+        return;
+    }
+    let end = s.src[s.pos..]
+        .iter()
+        .position(|&b| b == b')' || b == b'\n')
+        .map(|i| s.pos + i)
+        .unwrap_or(s.src.len());
+    s.copy_to(end);
+}
@@ -0,0 +1,296 @@
+use std::io::Write;
+use std::process::{Command, Stdio};
+use super::strip;
+use crate::context::language::Language;
+
+fn strip_code(src: &str, lang: Language) -> String {
+    strip(src, lang, false)
+}
+
+fn assert_parses_as_rust(code: &str) {
+    if let Err(e) = syn::parse_file(code) {
+        panic!("stripped Rust no longer parses: {}\n---\n{}", e, code);
+    }
+}
+
+/// 用 `node --check` 检查语法；没有安装 node 时跳过
+fn assert_parses_as_js(code: &str) {
+    let child = Command::new("node")
+        .args(["--check", "--input-type=module"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn();
+    let Ok(mut child) = child else {
+        eprintln!("node not found, skipping JS syntax check");
+        return;
+    };
+    child.stdin.take().unwrap().write_all(code.as_bytes()).unwrap();
+    let output = child.wait_with_output().unwrap();
+    assert!(
+        output.status.success(),
+        "stripped JS no longer parses: {}\n---\n{}",
+        String::from_utf8_lossy(&output.stderr),
+        code
+    );
+}
+
+// ---------------------------------------------------------------------------
+// Rust
+// ---------------------------------------------------------------------------
+
+const RUST_SRC: &str = r####"#!/usr/bin/env run-cargo-script
+//! Crate docs
+#![allow(dead_code)]
+
+/* outer /* nested */ still comment */
+/// Adds one.
+fn add_one(x: u32) -> u32 {
+    x + 1 // trailing
+}
+
+fn literals() -> Vec<String> {
+    let url = "http://example.com/*not a comment*/";
+    let raw = r#"raw "// still string" /* here */"#;
+    let raw2 = r##"a "# b // c"##;
+    let bytes = b"//bytes";
+    let slash = '/';
+    let quote = '"';
+    let lifetime_user: &'static str = "x";
+    /**/
+    vec![url.into(), raw.into(), raw2.into(), String::from_utf8_lossy(bytes).into_owned(), slash.to_string(), quote.to_string(), lifetime_user.into()]
+}
+"####;
+
+#[test]
+fn rust_keeps_literals_and_strips_comments() {
+    let out = strip_code(RUST_SRC, Language::Rust);
+    assert!(out.starts_with("#!/usr/bin/env run-cargo-script\n"));
+    assert!(out.contains("#![allow(dead_code)]"));
+    assert!(out.contains(r#""http://example.com/*not a comment*/""#));
+    assert!(out.contains(r##"r#"raw "// still string" /* here */"#"##));
+    assert!(out.contains(r###"r##"a "# b // c"##"###));
+    assert!(out.contains(r#"b"//bytes""#));
+    assert!(out.contains("'/'"));
+    assert!(out.contains(r#"'"'"#));
+    assert!(out.contains("&'static str"));
+    assert!(!out.contains("Crate docs"));
+    assert!(!out.contains("nested"));
+    assert!(!out.contains("still comment"));
+    assert!(!out.contains("Adds one"));
+    assert!(!out.contains("trailing"));
+    assert!(!out.contains("/**/"));
+}
+
+#[test]
+fn rust_output_still_parses() {
+    // 去掉 shebang 行，syn 不接受非 `#![` 开头的 `#!`
+    let src = RUST_SRC.split_once('\n').unwrap().1;
+    assert_parses_as_rust(src);
+    assert_parses_as_rust(&strip_code(src, Language::Rust));
+    assert_parses_as_rust(&strip(src, Language::Rust, true));
+}
+
+#[test]
+fn rust_keep_docs_keeps_doc_comments_only() {
+    let out = strip(RUST_SRC, Language::Rust, true);
+    assert!(out.contains("//! Crate docs"));
+    assert!(out.contains("/// Adds one."));
+    assert!(!out.contains("trailing"));
+    assert!(!out.contains("nested"));
+}
+
+#[test]
+fn rust_unterminated_nested_block_swallows_rest() {
+    let out = strip_code("fn a() {}\n/* /* */\nfn b() {}\n", Language::Rust);
+    assert_eq!(out, "fn a() {}\n");
+}
+
+// ---------------------------------------------------------------------------
+// C / C++
+// ---------------------------------------------------------------------------
+
+#[test]
+fn c_keeps_preprocessor_and_literals() {
+    let src = "#include <stdio.h> // io\n#define SLASH '/' /* char */\nint main(void) {\n    /* block */\n    printf(\"// %s /* */\\n\", \"x\");\n    char c = '\\'';\n    return 0;\n}\n";
+    let out = strip_code(src, Language::C);
+    assert_eq!(
+        out,
+        "#include <stdio.h>\n#define SLASH '/'\nint main(void) {\n    printf(\"// %s /* */\\n\", \"x\");\n    char c = '\\'';\n    return 0;\n}\n"
+    );
+}
+
+#[test]
+fn c_block_comments_do_not_nest() {
+    let out = strip_code("int a; /* /* */ int b; */\n", Language::C);
+    // 两侧的空白原样保留
+    assert_eq!(out, "int a;  int b; */\n");
+}
+
+#[test]
+fn cpp_raw_string_keeps_markers() {
+    let src = "auto s = R\"delim(// not )\" /* not */)delim\"; // yes\n";
+    let out = strip_code(src, Language::Cpp);
+    assert_eq!(out, "auto s = R\"delim(// not )\" /* not */)delim\";\n");
+}
+
+// ---------------------------------------------------------------------------
+// JavaScript / TypeScript
+// ---------------------------------------------------------------------------
+
+const JS_SRC: &str = r#"#!/usr/bin/env node
+/**
+ * Module docs
+ */
+import fs from "node:fs"; // default import
+
+const url = 'http://example.com'; // trailing
+const re = /\/\/ not a comment/g;
+const ratio = 10 / 2 / 5; /* division */
+const tpl = `a ${url /* inside hole */} // still template ${`nested // ${re}`}`;
+const str = "/* not a comment */";
+export function run() {
+  /* block
+     over lines */
+  return [fs, url, re.source, ratio, tpl, str];
+}
+"#;
+
+#[test]
+fn js_keeps_literals_and_strips_comments() {
+    let out = strip_code(JS_SRC, Language::JavaScript);
+    assert!(out.starts_with("#!/usr/bin/env node\n"));
+    assert!(out.contains("'http://example.com';"));
+    assert!(out.contains(r"/\/\/ not a comment/g"));
+    assert!(out.contains("10 / 2 / 5;"));
+    assert!(out.contains("// still template ${`nested // ${re}`}`"));
+    assert!(out.contains(r#""/* not a comment */""#));
+    for gone in ["Module docs", "default import", "trailing", "division", "inside hole", "over lines"] {
+        assert!(!out.contains(gone), "`{}` was not stripped:\n{}", gone, out);
+    }
+}
+
+#[test]
+fn js_output_still_parses() {
+    let src = JS_SRC.split_once('\n').unwrap().1;
+    assert_parses_as_js(src);
+    assert_parses_as_js(&strip_code(src, Language::JavaScript));
+    assert_parses_as_js(&strip(src, Language::TypeScript, true));
+}
+
+#[test]
+fn js_keep_docs_keeps_jsdoc() {
+    let out = strip(JS_SRC, Language::JavaScript, true);
+    assert!(out.contains("* Module docs"));
+    assert!(!out.contains("trailing"));
+}
+
+#[test]
+fn js_inline_block_comment_does_not_join_tokens() {
+    assert_eq!(strip_code("let a/**/= 1;\nreturn/**/x;\n", Language::JavaScript), "let a = 1;\nreturn x;\n");
+}
+
+// ---------------------------------------------------------------------------
+// Python
+// ---------------------------------------------------------------------------
+
+const PY_SRC: &str = r##"#!/usr/bin/env python3
+"""Module docstring."""
+import re  # regex
+
+
+def only_doc():
+    """Nothing but a docstring."""
+
+
+def documented(x):
+    '''Docstring with # hash.'''
+    s = "# not a comment"
+    r = r'\d+ # not either'
+    f = f"{x!r} # still string"
+    t = """
+    # inside triple-quoted string
+    """
+    return s, r, f, t  # trailing
+"##;
+
+#[test]
+fn python_strips_comments_and_docstrings() {
+    let out = strip_code(PY_SRC, Language::Python);
+    assert!(out.starts_with("#!/usr/bin/env python3\n"));
+    assert!(out.contains("import re\n"));
+    assert!(out.contains(r##""# not a comment""##));
+    assert!(out.contains(r"r'\d+ # not either'"));
+    assert!(out.contains(r#"f"{x!r} # still string""#));
+    assert!(out.contains("    # inside triple-quoted string\n"));
+    assert!(out.contains("return s, r, f, t\n"));
+    for gone in ["Module docstring", "Nothing but", "Docstring with", "# regex", "# trailing"] {
+        assert!(!out.contains(gone), "`{}` was not stripped:\n{}", gone, out);
+    }
+    // 只有 docstring 的函数体需要补 `pass`，否则缩进块为空
+    assert!(out.contains("def only_doc():\n    pass\n"), "{}", out);
+}
+
+#[test]
+fn python_keep_docs_keeps_docstrings() {
+    let out = strip(PY_SRC, Language::Python, true);
+    assert!(out.contains(r#""""Module docstring.""""#));
+    assert!(out.contains("'''Docstring with # hash.'''"));
+    assert!(!out.contains("# regex"));
+}
+
+// ---------------------------------------------------------------------------
+// Shell / config / markup
+// ---------------------------------------------------------------------------
+
+#[test]
+fn shell_keeps_shebang_and_quoted_hashes() {
+    let src = "#!/bin/bash\n# comment\necho \"#not\" '#not' ${#arr[@]} a#b # yes\n";
+    assert_eq!(strip_code(src, Language::Shell), "#!/bin/bash\necho \"#not\" '#not' ${#arr[@]} a#b\n");
+}
+
+#[test]
+fn css_keeps_hex_colors_and_urls() {
+    let src = "a { color: #fff; /* white */ background: url(http://x.test/a.png); }\n/*! license */\n";
+    assert_eq!(
+        strip_code(src, Language::Css),
+        "a { color: #fff;  background: url(http://x.test/a.png); }\n"
+    );
+    assert!(strip(src, Language::Css, true).contains("/*! license */"));
+}
+
+#[test]
+fn scss_strips_line_comments() {
+    let src = "$c: #abcdef; // primary\n.a { color: $c; }\n";
+    assert_eq!(strip_code(src, Language::Scss), "$c: #abcdef;\n.a { color: $c; }\n");
+}
+
+#[test]
+fn toml_and_yaml_keep_hashes_in_strings() {
+    let toml = "# header\nkey = \"a # b\" # trailing\ncolor = '#fff'\n";
+    assert_eq!(strip_code(toml, Language::Toml), "key = \"a # b\"\ncolor = '#fff'\n");
+    let yaml = "# header\nkey: \"a # b\" # trailing\nurl: http://x#frag\n";
+    assert_eq!(strip_code(yaml, Language::Yaml), "key: \"a # b\"\nurl: http://x#frag\n");
+}
+
+#[test]
+fn html_strips_comments_and_embedded_script_comments() {
+    let src = "<!-- note -->\n<p title=\"<!-- not -->\">x</p>\n<script>\nlet a = 1; // js comment\n</script>\n";
+    let out = strip_code(src, Language::Html);
+    assert!(out.contains("title=\"<!-- not -->\""));
+    assert!(!out.contains("note"));
+    assert!(!out.contains("js comment"));
+    assert!(out.contains("let a = 1;"));
+}
+
+#[test]
+fn sql_keeps_quoted_markers() {
+    let src = "SELECT '--not', \"/*not*/\" -- yes\nFROM t /* block */;\n";
+    assert_eq!(strip_code(src, Language::Sql), "SELECT '--not', \"/*not*/\"\nFROM t ;\n");
+}
+
+#[test]
+fn crlf_line_endings_are_preserved() {
+    let out = strip_code("let a = 1; // x\r\nlet b = 2;\r\n", Language::JavaScript);
+    assert_eq!(out, "let a = 1;\r\nlet b = 2;\r\n");
+}
@@ -1,82 +1,465 @@
+use std::collections::{HashMap, HashSet};
 use std::fs;
+use std::io::Read;
 use std::path::Path;
+use std::time::UNIX_EPOCH;
 use rayon::prelude::*;
-use serde::Serialize;
-use super::{tokenizer, processing};
+use serde::{Deserialize, Serialize};
+use super::format::{ContextHeader, OutputFormat};
+use super::processing;
+use super::redact::Redactor;
+use super::skeleton;
+use super::tokenizer::TokenizerKind;
+use super::language::Language;
+use crate::charset;
+use crate::notebook::{self, Notebook, NotebookOptions};
+use crate::db::token_cache::TokenCacheEntry;
 
 #[derive(Debug, Serialize)]
 pub struct ContextStats {
     pub file_count: usize,
     pub total_size: usize,
     pub total_tokens: usize,
+    pub tokenizer: TokenizerKind,
+    /// false 表示 `total_tokens` 是估算值
+    pub exact: bool,
+    /// 每个文件的明细，按 token 数从大到小排列
+    pub files: Vec<FileStats>,
+    /// 按所在目录汇总
+    pub by_directory: Vec<StatsGroup>,
+    /// 按扩展名汇总，没有扩展名的文件归入空字符串
+    pub by_extension: Vec<StatsGroup>,
 }
 
-const MAX_FILE_SIZE: u64 = 1024 * 1024;
-const BINARY_CHECK_BYTES: usize = 8000;
+/// 文件在上下文中的状态：正常包含，或只输出了一行说明
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FileStatus {
+    Included,
+    Binary,
+    TooLarge,
+    Error,
+}
 
-fn read_and_process_file(path: &str, remove_comments: bool) -> String {
-    let p = Path::new(path);
+impl FileStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            FileStatus::Included => "included",
+            FileStatus::Binary => "binary",
+            FileStatus::TooLarge => "too_large",
+            FileStatus::Error => "error",
+        }
+    }
 
-    if let Ok(meta) = fs::metadata(p) {
-        if meta.len() > MAX_FILE_SIZE {
-            return format!("<file path=\"{}\">\n[File too large: {} bytes]\n</file>", path, meta.len());
+    fn parse(s: &str) -> Self {
+        match s {
+            "binary" => FileStatus::Binary,
+            "too_large" => FileStatus::TooLarge,
+            "error" => FileStatus::Error,
+            _ => FileStatus::Included,
         }
+    }
+}
+
+/// 单个文件的统计。`bytes` / `tokens` 按渲染后的文件块计算，与总数口径一致；`lines` 是文件内容的行数
+#[derive(Debug, Clone, Serialize)]
+pub struct FileStats {
+    pub path: String,
+    pub language: Option<Language>,
+    pub status: FileStatus,
+    /// 检测到的文本编码（如 "UTF-8"、"GBK"、"UTF-16LE"），二进制或未读取的文件为 None
+    pub encoding: Option<String>,
+    pub bytes: usize,
+    pub lines: usize,
+    pub tokens: usize,
+}
+
+#[derive(Debug, Serialize)]
+pub struct StatsGroup {
+    pub key: String,
+    pub file_count: usize,
+    pub bytes: usize,
+    pub tokens: usize,
+}
+
+/// 单个文件的处理选项
+#[derive(Debug, Clone, Default)]
+pub struct ProcessOptions {
+    pub remove_comments: bool,
+    /// 剥离注释时保留文档注释（`///`、`/** */`、docstring 等）
+    pub keep_doc_comments: bool,
+    pub format: OutputFormat,
+    pub skeleton: SkeletonOptions,
+    pub notebook: NotebookOptions,
+}
+
+/// 骨架模式：只保留 import、类型声明和函数签名
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct SkeletonOptions {
+    /// 手动指定使用骨架模式的文件
+    #[serde(default)]
+    pub paths: HashSet<String>,
+    /// 超过该字节数的文件自动使用骨架模式
+    #[serde(default)]
+    pub threshold: Option<u64>,
+}
+
+impl SkeletonOptions {
+    fn applies(&self, path: &str, size: u64) -> bool {
+        self.paths.contains(path) || self.threshold.is_some_and(|t| size > t)
+    }
+}
+
+const MAX_FILE_SIZE: u64 = 1024 * 1024;
+/// 骨架模式只输出很小一部分内容，可以读取更大的文件
+const MAX_SKELETON_SOURCE_SIZE: u64 = 16 * 1024 * 1024;
+const SKELETON_NOTE: &str = "[Skeleton: function bodies omitted]";
+
+fn too_large(bytes: usize) -> FileBody {
+    FileBody::Notice(FileStatus::TooLarge, format!("[File too large: {} bytes]", bytes))
+}
+
+/// 读取前检查的原始文件大小上限：notebook 按提取后的 cell 文本判断，骨架模式按输出判断
+fn source_limit(p: &Path, skeleton: bool) -> u64 {
+    if notebook::is_notebook(p) {
+        notebook::MAX_NOTEBOOK_SOURCE_SIZE
+    } else if skeleton {
+        MAX_SKELETON_SOURCE_SIZE
     } else {
-        return format!("<file path=\"{}\">\n[Error: File not found]\n</file>", path);
+        MAX_FILE_SIZE
+    }
+}
+
+/// 只读取文件开头做二进制判断，读取失败按文本处理
+pub(super) fn sniff_binary(path: &Path) -> bool {
+    let Ok(file) = fs::File::open(path) else { return false };
+    let mut head = Vec::with_capacity(charset::SNIFF_BYTES);
+    match file.take(charset::SNIFF_BYTES as u64).read_to_end(&mut head) {
+        Ok(_) => charset::is_binary(&head),
+        Err(_) => false,
+    }
+}
+
+/// 读取并处理后的文件内容：正常文本，或一条说明（过大、二进制、读取失败）
+pub(super) enum FileBody {
+    Text(String),
+    Notice(FileStatus, String),
+}
+
+impl FileBody {
+    pub(super) fn as_str(&self) -> &str {
+        match self {
+            FileBody::Text(s) | FileBody::Notice(_, s) => s,
+        }
+    }
+
+    fn status(&self) -> FileStatus {
+        match self {
+            FileBody::Text(_) => FileStatus::Included,
+            FileBody::Notice(status, _) => *status,
+        }
+    }
+
+    fn lines(&self) -> usize {
+        match self {
+            FileBody::Text(s) => s.lines().count(),
+            FileBody::Notice(..) => 0,
+        }
+    }
+}
+
+pub(super) fn load_file(path: &str, options: &ProcessOptions) -> FileBody {
+    load_file_with_encoding(path, options).0
+}
+
+/// 同 `load_file`，并返回检测到的文本编码（非文本内容为 None）
+fn load_file_with_encoding(path: &str, options: &ProcessOptions) -> (FileBody, Option<&'static str>) {
+    let p = Path::new(path);
+
+    let Ok(meta) = fs::metadata(p) else {
+        return (FileBody::Notice(FileStatus::Error, "[Error: File not found]".to_string()), None);
+    };
+    let skeleton = options.skeleton.applies(path, meta.len());
+    if meta.len() > source_limit(p, skeleton) {
+        return (too_large(meta.len() as usize), None);
     }
 
     match fs::read(p) {
-        Ok(bytes) => {
-            if bytes.iter().take(BINARY_CHECK_BYTES).any(|&b| b == 0) {
-                return format!("<file path=\"{}\">\n[Binary file omitted]\n</file>", path);
+        Ok(bytes) => decode_body(p, &bytes, options, skeleton),
+        Err(e) => (FileBody::Notice(FileStatus::Error, format!("[Error reading file: {}]", e)), None)
+    }
+}
+
+/// 非 UTF-8 的文本（GBK、Shift_JIS、UTF-16 等）先转成 UTF-8 再处理
+fn decode_body(p: &Path, bytes: &[u8], options: &ProcessOptions, skeleton: bool) -> (FileBody, Option<&'static str>) {
+    if charset::is_binary(bytes) {
+        return (FileBody::Notice(FileStatus::Binary, "[Binary file omitted]".to_string()), None);
+    }
+
+    let decoded = charset::decode(bytes);
+    let encoding = Some(decoded.encoding_name());
+    let content = decoded.text;
+
+    // Notebook 只输出 cell 内容，不做骨架提取；解析失败时按普通 JSON 文件处理
+    if notebook::is_notebook(p) {
+        if let Ok(nb) = Notebook::parse(&content) {
+            let lang = nb.language.as_deref().and_then(Language::from_name);
+            let text = nb.render(&options.notebook, |source| {
+                if options.remove_comments {
+                    processing::strip_comments_with(source, lang, options.keep_doc_comments)
+                } else {
+                    source.to_string()
+                }
+            });
+            if text.len() as u64 > MAX_FILE_SIZE {
+                return (too_large(text.len()), None);
             }
+            return (FileBody::Text(text), encoding);
+        }
+        // 解析失败的 notebook 按普通文件的上限处理
+        if !skeleton && bytes.len() as u64 > MAX_FILE_SIZE {
+            return (too_large(bytes.len()), None);
+        }
+    }
 
-            let content = String::from_utf8_lossy(&bytes);
+    let lang = Language::from_path(p);
 
-            let final_content = if remove_comments {
-                let ext = p.extension().and_then(|s| s.to_str()).unwrap_or("").to_lowercase();
-                processing::strip_comments(&content, &ext)
-            } else {
-                content.to_string()
-            };
+    let final_content = if options.remove_comments {
+        processing::strip_comments_with(&content, lang, options.keep_doc_comments)
+    } else {
+        content
+    };
 
-            format!("<file path=\"{}\">\n{}\n</file>", path, final_content)
-        },
-        Err(e) => format!("<file path=\"{}\">\n[Error reading file: {}]\n</file>", path, e)
+    if skeleton {
+        if let Some(outline) = lang.and_then(|l| skeleton::extract(&final_content, l)) {
+            return (FileBody::Text(format!("{}\n{}", SKELETON_NOTE, outline)), encoding);
+        }
+        // 不支持的语言或解析失败：退回完整内容，但仍受普通大小限制
+        if bytes.len() as u64 > MAX_FILE_SIZE {
+            return (too_large(bytes.len()), None);
+        }
     }
+
+    (FileBody::Text(final_content), encoding)
 }
 
-pub fn calculate_stats_parallel(paths: Vec<String>, remove_comments: bool) -> ContextStats {
-    let (total_size, total_tokens) = paths.par_iter()
-        .map(|path| {
-            let xml_block = read_and_process_file(path, remove_comments);
-            let size = xml_block.len();
-            let tokens = tokenizer::count_tokens(&xml_block);
-            (size, tokens)
-        })
-        .reduce(|| (0, 0), |a, b| (a.0 + b.0, a.1 + b.1));
+pub(super) fn read_and_process_file(path: &str, options: &ProcessOptions) -> String {
+    options.format.render_file(path, load_file(path, options).as_str())
+}
 
-    ContextStats {
-        file_count: paths.len(),
-        total_size,
-        total_tokens,
-    }
+/// `calculate_stats_cached` 的结果，附带需要写回缓存的变化
+pub struct CachedStats {
+    pub stats: ContextStats,
+    pub updated: Vec<TokenCacheEntry>,
+    pub hits: Vec<String>,
+    pub missing: Vec<String>,
 }
 
-pub fn assemble_context_parallel(paths: Vec<String>, header: String, remove_comments: bool) -> String {
-    let file_blocks: Vec<String> = paths.par_iter()
-        .map(|path| read_and_process_file(path, remove_comments))
+enum CacheOutcome {
+    Hit,
+    Updated(TokenCacheEntry),
+    Missing,
+    /// 过大或读取失败，只有一行说明，不值得缓存
+    Skipped,
+}
+
+/// 统计选中文件的大小和 token 数。mtime + size 未变的文件直接使用缓存；
+/// mtime 变了时再比较内容 hash，内容没变也不重新分词
+pub fn calculate_stats_cached(
+    paths: Vec<String>,
+    options: ProcessOptions,
+    tokenizer: TokenizerKind,
+    cache: &HashMap<String, TokenCacheEntry>,
+) -> CachedStats {
+    let results: Vec<(FileStats, CacheOutcome)> = paths.par_iter()
+        .map(|path| cached_file_stats(path, &options, tokenizer, cache.get(path)))
         .collect();
 
-    let mut full_text = String::with_capacity(header.len() + file_blocks.iter().map(|s| s.len()).sum::<usize>() + 100);
+    let mut files = Vec::with_capacity(results.len());
+    let mut updated = Vec::new();
+    let mut hits = Vec::new();
+    let mut missing = Vec::new();
+    for (file, outcome) in results {
+        match outcome {
+            CacheOutcome::Hit => hits.push(file.path.clone()),
+            CacheOutcome::Updated(entry) => updated.push(entry),
+            CacheOutcome::Missing => missing.push(file.path.clone()),
+            CacheOutcome::Skipped => {}
+        }
+        files.push(file);
+    }
+    files.sort_by_key(|f| std::cmp::Reverse(f.tokens));
 
-    full_text.push_str(&header);
-    full_text.push_str("\n<source_files>\n");
-    for block in file_blocks {
-        full_text.push_str(&block);
-        full_text.push('\n');
+    let stats = ContextStats {
+        file_count: files.len(),
+        total_size: files.iter().map(|f| f.bytes).sum(),
+        total_tokens: files.iter().map(|f| f.tokens).sum(),
+        tokenizer,
+        exact: tokenizer.is_exact(),
+        by_directory: group_stats(&files, |f| {
+            Path::new(&f.path).parent().map(|d| d.to_string_lossy().into_owned()).unwrap_or_default()
+        }),
+        by_extension: group_stats(&files, |f| {
+            Path::new(&f.path).extension().map(|e| e.to_string_lossy().to_lowercase()).unwrap_or_default()
+        }),
+        files,
+    };
+    CachedStats { stats, updated, hits, missing }
+}
+
+/// 按 key 汇总，token 数多的组排在前面
+fn group_stats(files: &[FileStats], key: impl Fn(&FileStats) -> String) -> Vec<StatsGroup> {
+    let mut groups: HashMap<String, StatsGroup> = HashMap::new();
+    for f in files {
+        let k = key(f);
+        let g = groups.entry(k.clone()).or_insert(StatsGroup { key: k, file_count: 0, bytes: 0, tokens: 0 });
+        g.file_count += 1;
+        g.bytes += f.bytes;
+        g.tokens += f.tokens;
     }
-    full_text.push_str("</source_files>\n</project_context>");
+    let mut groups: Vec<StatsGroup> = groups.into_values().collect();
+    groups.sort_by(|a, b| b.tokens.cmp(&a.tokens).then_with(|| a.key.cmp(&b.key)));
+    groups
+}
+
+fn measure(
+    path: &str,
+    (body, encoding): (FileBody, Option<&'static str>),
+    options: &ProcessOptions,
+    tokenizer: TokenizerKind,
+) -> FileStats {
+    let block = options.format.render_file(path, body.as_str());
+    FileStats {
+        path: path.to_string(),
+        language: Language::from_path(Path::new(path)),
+        status: body.status(),
+        encoding: encoding.map(str::to_string),
+        bytes: block.len(),
+        lines: body.lines(),
+        tokens: tokenizer.count(&block),
+    }
+}
+
+fn cached_file_stats(
+    path: &str,
+    options: &ProcessOptions,
+    tokenizer: TokenizerKind,
+    cached: Option<&TokenCacheEntry>,
+) -> (FileStats, CacheOutcome) {
+    let uncached = |outcome| (measure(path, load_file_with_encoding(path, options), options, tokenizer), outcome);
+    let from_cache = |c: &TokenCacheEntry| FileStats {
+        path: path.to_string(),
+        language: Language::from_path(Path::new(path)),
+        status: FileStatus::parse(&c.status),
+        encoding: c.encoding.clone(),
+        bytes: c.bytes,
+        lines: c.lines,
+        tokens: c.tokens,
+    };
+
+    let p = Path::new(path);
+    let Ok(meta) = fs::metadata(p) else { return uncached(CacheOutcome::Missing) };
+    let mtime = meta.modified().ok()
+        .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+        .map(|d| d.as_millis() as i64)
+        .unwrap_or(0);
+    let size = meta.len() as i64;
+
+    // 骨架的输出很小，直接重新计算，不占用缓存；带输出的 notebook 结果取决于截断设置，同样不缓存
+    if options.skeleton.applies(path, meta.len()) || (options.notebook.include_outputs && notebook::is_notebook(p)) {
+        return uncached(CacheOutcome::Skipped);
+    }
+    if let Some(c) = cached.filter(|c| c.mtime == mtime && c.size == size) {
+        return (from_cache(c), CacheOutcome::Hit);
+    }
+    if meta.len() > source_limit(p, false) {
+        return uncached(CacheOutcome::Skipped);
+    }
+    let Ok(bytes) = fs::read(p) else { return uncached(CacheOutcome::Skipped) };
+
+    let hash = blake3::hash(&bytes).to_hex().to_string();
+    let file = match cached.filter(|c| c.hash == hash) {
+        Some(c) => from_cache(c),
+        None => measure(path, decode_body(p, &bytes, options, false), options, tokenizer),
+    };
+
+    let entry = TokenCacheEntry {
+        path: path.to_string(),
+        mtime,
+        size,
+        hash,
+        tokens: file.tokens,
+        bytes: file.bytes,
+        lines: file.lines,
+        status: file.status.as_str().to_string(),
+        encoding: file.encoding.clone(),
+    };
+    (file, CacheOutcome::Updated(entry))
+}
+
+pub fn assemble_context_parallel(paths: Vec<String>, header: &ContextHeader, options: ProcessOptions) -> String {
+    assemble_context_with_notes(paths, header, options, &HashMap::new(), None)
+}
+
+/// 与 `assemble_context_parallel` 相同，但先用 gitleaks 规则把文件中的密钥替换成占位符
+pub fn assemble_context_redacted(
+    paths: Vec<String>,
+    header: &ContextHeader,
+    options: ProcessOptions,
+    redactor: &mut Redactor,
+) -> String {
+    let file_blocks = load_redacted(&paths, &options, redactor);
+    options.format.render_document(header, &file_blocks)
+}
+
+/// 并行读取一组文件，顺序与 `paths` 一致；`redactor` 不为 None 时把其中的密钥替换成占位符
+pub(super) fn load_bodies(paths: &[String], options: &ProcessOptions, redactor: Option<&mut Redactor>) -> Vec<FileBody> {
+    let bodies: Vec<FileBody> = paths.par_iter()
+        .map(|path| load_file(path, options))
+        .collect();
+    maybe_redact(paths, bodies, redactor)
+}
+
+fn maybe_redact(paths: &[String], bodies: Vec<FileBody>, redactor: Option<&mut Redactor>) -> Vec<FileBody> {
+    match redactor {
+        Some(redactor) => redactor.redact_batch(paths.iter().map(String::as_str).zip(bodies).collect()),
+        None => bodies,
+    }
+}
+
+/// 读取、脱敏并渲染一组文件，输出顺序与 `paths` 一致
+pub(super) fn load_redacted(paths: &[String], options: &ProcessOptions, redactor: &mut Redactor) -> Vec<String> {
+    let bodies = load_bodies(paths, options, Some(redactor));
+    paths.par_iter()
+        .zip(bodies.par_iter())
+        .map(|(path, body)| options.format.render_file(path, body.as_str()))
+        .collect()
+}
+
+/// 与 `assemble_context_parallel` 相同，但可以在文件正文之后附加内容（例如 diff）。
+/// 文件已不存在时只输出附加内容。附加内容与正文一起脱敏
+pub fn assemble_context_with_notes(
+    paths: Vec<String>,
+    header: &ContextHeader,
+    options: ProcessOptions,
+    notes: &HashMap<String, String>,
+    redactor: Option<&mut Redactor>,
+) -> String {
+    let bodies: Vec<FileBody> = paths.par_iter()
+        .map(|path| match notes.get(path) {
+            None => load_file(path, &options),
+            Some(note) if !Path::new(path).exists() => FileBody::Text(note.clone()),
+            Some(note) => {
+                let body = load_file(path, &options);
+                FileBody::Text(format!("{}\n\n{}", body.as_str().trim_end(), note))
+            }
+        })
+        .collect();
+    let bodies = maybe_redact(&paths, bodies, redactor);
+    let file_blocks: Vec<String> = paths.par_iter()
+        .zip(bodies.par_iter())
+        .map(|(path, body)| options.format.render_file(path, body.as_str()))
+        .collect();
 
-    full_text
+    options.format.render_document(header, &file_blocks)
 }
@@ -0,0 +1,225 @@
+use std::path::Path;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use super::language::Language;
+
+/// 上下文的输出格式。复制、保存、预览、统计都走同一个渲染器，保证 token 数与实际输出一致
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OutputFormat {
+    #[default]
+    Xml,
+    Markdown,
+    Json,
+    Plain,
+}
+
+/// 文档开头的说明文字、目录树和符号地图（内容由调用方提供，按输出格式渲染）
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct ContextHeader {
+    #[serde(default)]
+    pub summary: String,
+    #[serde(default)]
+    pub structure: String,
+    /// `repo_map::generate` 生成的符号地图，为空时不输出
+    #[serde(default)]
+    pub repo_map: String,
+}
+
+const PLAIN_RULE: &str = "================================================================";
+
+impl OutputFormat {
+    /// 与 serde 序列化一致的名称，用作缓存键
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Xml => "xml",
+            Self::Markdown => "markdown",
+            Self::Json => "json",
+            Self::Plain => "plain",
+        }
+    }
+
+    /// 渲染单个文件块
+    pub fn render_file(&self, path: &str, body: &str) -> String {
+        match self {
+            Self::Xml => format!("<file path=\"{}\">\n{}\n</file>", escape_xml(path, true), cdata(body)),
+            Self::Markdown => {
+                let fence = fence_for(body);
+                let body = body.strip_suffix('\n').unwrap_or(body);
+                format!("### `{}`\n\n{}{}\n{}\n{}\n", path, fence, fence_tag(path), body, fence)
+            }
+            Self::Json => json!({
+                "path": path,
+                "language": Some(fence_tag(path)).filter(|t| !t.is_empty()),
+                "content": body,
+            }).to_string(),
+            Self::Plain => format!("{}\nFile: {}\n{}\n{}\n", PLAIN_RULE, path, PLAIN_RULE, body),
+        }
+    }
+
+    /// 把 header 和已渲染的文件块拼成完整文档
+    pub fn render_document(&self, header: &ContextHeader, blocks: &[String]) -> String {
+        let capacity = header.summary.len() + header.structure.len() + blocks.iter().map(|b| b.len() + 8).sum::<usize>() + 200;
+        let mut out = self.document_head(header);
+        out.reserve(capacity.saturating_sub(out.len()));
+
+        for (i, block) in blocks.iter().enumerate() {
+            out.push_str(self.block_prefix(i));
+            out.push_str(block);
+            out.push_str(self.block_suffix());
+        }
+        out.push_str(self.document_tail(blocks.len()));
+        out
+    }
+
+    /// 第一个文件块之前的部分：文档开头、说明文字和目录树。
+    /// 与 `block_prefix` / `block_suffix` / `document_tail` 一起使用，可以边生成边写出
+    pub fn document_head(&self, header: &ContextHeader) -> String {
+        let mut out = String::with_capacity(header.summary.len() + header.structure.len() + header.repo_map.len() + 200);
+
+        match self {
+            Self::Xml => {
+                out.push_str("<project_context>\n");
+                push_section(&mut out, &escape_xml(&header.summary, false));
+                if !header.structure.is_empty() {
+                    out.push_str("<project_structure>\n");
+                    out.push_str(&escape_xml(&header.structure, false));
+                    out.push_str("\n</project_structure>\n\n");
+                }
+                if !header.repo_map.is_empty() {
+                    out.push_str("<repo_map>\n");
+                    out.push_str(&escape_xml(header.repo_map.trim_end(), false));
+                    out.push_str("\n</repo_map>\n\n");
+                }
+                out.push_str("<source_files>\n");
+            }
+            Self::Markdown => {
+                out.push_str("# Project Context\n\n");
+                push_section(&mut out, &header.summary);
+                if !header.structure.is_empty() {
+                    let fence = fence_for(&header.structure);
+                    out.push_str(&format!("## Project Structure\n\n{}text\n{}\n{}\n\n", fence, header.structure, fence));
+                }
+                if !header.repo_map.is_empty() {
+                    let fence = fence_for(&header.repo_map);
+                    out.push_str(&format!("## Repository Map\n\n{}text\n{}\n{}\n\n", fence, header.repo_map.trim_end(), fence));
+                }
+                out.push_str("## Source Files\n\n");
+            }
+            Self::Json => {
+                out.push_str("{\n");
+                out.push_str(&format!("  \"summary\": {},\n", json!(header.summary)));
+                out.push_str(&format!("  \"structure\": {},\n", json!(header.structure)));
+                if !header.repo_map.is_empty() {
+                    out.push_str(&format!("  \"repo_map\": {},\n", json!(header.repo_map)));
+                }
+                out.push_str("  \"files\": [");
+            }
+            Self::Plain => {
+                push_section(&mut out, &header.summary);
+                if !header.structure.is_empty() {
+                    out.push_str("Project Structure:\n");
+                    out.push_str(&header.structure);
+                    out.push_str("\n\n");
+                }
+                if !header.repo_map.is_empty() {
+                    out.push_str("Repository Map:\n");
+                    out.push_str(header.repo_map.trim_end());
+                    out.push_str("\n\n");
+                }
+            }
+        }
+
+        out
+    }
+
+    /// 第 `index` 个文件块之前的分隔
+    pub fn block_prefix(&self, index: usize) -> &'static str {
+        match self {
+            Self::Json if index == 0 => "\n    ",
+            Self::Json => ",\n    ",
+            _ => "",
+        }
+    }
+
+    /// 每个文件块之后的分隔
+    pub fn block_suffix(&self) -> &'static str {
+        match self {
+            Self::Json => "",
+            _ => "\n",
+        }
+    }
+
+    /// 最后一个文件块之后的部分
+    pub fn document_tail(&self, block_count: usize) -> &'static str {
+        match self {
+            Self::Xml => "</source_files>\n</project_context>",
+            Self::Json if block_count == 0 => "]\n}",
+            Self::Json => "\n  ]\n}",
+            Self::Markdown | Self::Plain => "",
+        }
+    }
+}
+
+fn push_section(out: &mut String, text: &str) {
+    let text = text.trim_end();
+    if !text.is_empty() {
+        out.push_str(text);
+        out.push_str("\n\n");
+    }
+}
+
+fn escape_xml(text: &str, attribute: bool) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' if attribute => out.push_str("&quot;"),
+            _ if !is_xml_char(c) => out.push(char::REPLACEMENT_CHARACTER),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+/// 源码放进 CDATA：不用逐字符转义（`&lt;` 之类会让 token 数暴涨），
+/// 内容里的 `]]>` 拆成两段 CDATA，保证文档仍然是合法 XML
+fn cdata(body: &str) -> String {
+    let body: String = body.chars()
+        .map(|c| if is_xml_char(c) { c } else { char::REPLACEMENT_CHARACTER })
+        .collect();
+    format!("<![CDATA[\n{}\n]]>", body.replace("]]>", "]]]]><![CDATA[>"))
+}
+
+/// XML 1.0 不允许出现除 tab / 换行 / 回车以外的控制字符，即使在 CDATA 里也不行
+fn is_xml_char(c: char) -> bool {
+    !matches!(c, '\u{0}'..='\u{8}' | '\u{b}' | '\u{c}' | '\u{e}'..='\u{1f}' | '\u{fffe}' | '\u{ffff}')
+}
+
+/// 比内容里最长的连续反引号多一个，至少三个
+fn fence_for(body: &str) -> String {
+    let mut longest = 0;
+    let mut run = 0;
+    for b in body.bytes() {
+        if b == b'`' {
+            run += 1;
+            longest = longest.max(run);
+        } else {
+            run = 0;
+        }
+    }
+    "`".repeat((longest + 1).max(3))
+}
+
+/// 代码块的语言标记：能识别的语言用标准名称，否则退回小写扩展名
+fn fence_tag(path: &str) -> String {
+    let p = Path::new(path);
+    match Language::from_path(p) {
+        Some(lang) => lang.fence_tag().to_string(),
+        None => p.extension()
+            .map(|e| e.to_string_lossy().to_lowercase())
+            .unwrap_or_default(),
+    }
+}
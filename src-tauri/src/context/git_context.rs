@@ -0,0 +1,93 @@
+use std::collections::HashMap;
+use std::path::Path;
+use git2::Repository;
+use serde::{Deserialize, Serialize};
+use super::core::{self, ProcessOptions};
+use super::format::ContextHeader;
+use super::redact::Redactor;
+use crate::git::{self, ChangedFile};
+
+/// 按 Git 变更选择上下文文件的选项
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct GitSelection {
+    /// 提交哈希、分支或标签；为空时使用 main / master
+    #[serde(default)]
+    pub base_ref: Option<String>,
+    /// 与 HEAD 的分叉点作为基准，只看当前分支上的改动
+    #[serde(default)]
+    pub merge_base: bool,
+    /// 在每个文件之后附上 diff
+    #[serde(default)]
+    pub include_diff: bool,
+    /// 不输出已删除的文件
+    #[serde(default)]
+    pub skip_deleted: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct GitContext {
+    pub base_commit: String,
+    pub files: Vec<ChangedFile>,
+    pub content: String,
+}
+
+/// 收集相对基准提交变化的文件（含未提交的修改），按普通上下文组装；
+/// 重命名、删除和 diff 作为附加内容跟在对应文件之后；开启脱敏时 diff 中的密钥同样被替换
+pub fn assemble_git_context(
+    project_path: &str,
+    selection: &GitSelection,
+    header: &ContextHeader,
+    options: ProcessOptions,
+    redactor: Option<&mut Redactor>,
+) -> Result<GitContext, String> {
+    let repo = Repository::open(project_path).map_err(|e| format!("Failed to open repo: {}", e))?;
+    let base = git::resolve_base_commit(&repo, selection.base_ref.as_deref(), selection.merge_base)?;
+    let short = &base.to_string()[..8];
+
+    let mut files = git::changed_files_since(&repo, base, selection.include_diff)?;
+    if selection.skip_deleted {
+        files.retain(|f| f.status != "Deleted");
+    }
+    // 仓库根目录不一定是项目目录，路径以仓库工作区为准
+    let root = repo.workdir().unwrap_or_else(|| Path::new(project_path));
+
+    let mut paths = Vec::with_capacity(files.len());
+    let mut notes = HashMap::new();
+    for file in &mut files {
+        let path = root.join(&file.path).to_string_lossy().to_string();
+        let mut note = Vec::new();
+        match (file.status.as_str(), &file.old_path) {
+            ("Deleted", _) => note.push(format!("[Deleted since {}]", short)),
+            ("Renamed", Some(old)) => note.push(format!("[Renamed from {}]", old)),
+            _ => {}
+        }
+        if let Some(patch) = file.patch.take() {
+            note.push(format!("[Diff since {}]\n{}", short, patch.trim_end()));
+        }
+        if !note.is_empty() {
+            notes.insert(path.clone(), note.join("\n\n"));
+        }
+        paths.push(path);
+    }
+
+    let header = ContextHeader {
+        summary: format!("{}\n{}", header.summary.trim_end(), change_summary(&files, short)).trim_start().to_string(),
+        ..header.clone()
+    };
+    let content = core::assemble_context_with_notes(paths, &header, options, &notes, redactor);
+
+    Ok(GitContext { base_commit: base.to_string(), files, content })
+}
+
+fn change_summary(files: &[ChangedFile], short: &str) -> String {
+    let count = |status: &str| files.iter().filter(|f| f.status == status).count();
+    format!(
+        "Changes since {}: {} files ({} added, {} modified, {} renamed, {} deleted)",
+        short,
+        files.len(),
+        count("Added"),
+        count("Modified"),
+        count("Renamed"),
+        count("Deleted"),
+    )
+}
@@ -0,0 +1,135 @@
+use std::path::Path;
+use serde::Serialize;
+
+/// 上下文处理所识别的源码语言（注释剥离、骨架提取等共用）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Language {
+    Rust,
+    C,
+    Cpp,
+    Go,
+    Java,
+    Kotlin,
+    Swift,
+    Dart,
+    CSharp,
+    JavaScript,
+    TypeScript,
+    Python,
+    Shell,
+    Ruby,
+    Perl,
+    Yaml,
+    Toml,
+    Dockerfile,
+    Html,
+    Xml,
+    Vue,
+    Svelte,
+    Css,
+    Scss,
+    Less,
+    Sql,
+}
+
+impl Language {
+    pub fn from_ext(ext: &str) -> Option<Self> {
+        let lang = match ext.to_ascii_lowercase().as_str() {
+            "rs" => Self::Rust,
+            "c" | "h" => Self::C,
+            "cpp" | "cc" | "cxx" | "hpp" | "hh" | "hxx" => Self::Cpp,
+            "go" => Self::Go,
+            "java" => Self::Java,
+            "kt" | "kts" => Self::Kotlin,
+            "swift" => Self::Swift,
+            "dart" => Self::Dart,
+            "cs" => Self::CSharp,
+            "js" | "jsx" | "mjs" | "cjs" => Self::JavaScript,
+            "ts" | "tsx" | "mts" | "cts" => Self::TypeScript,
+            "py" | "pyi" => Self::Python,
+            "sh" | "bash" | "zsh" => Self::Shell,
+            "rb" => Self::Ruby,
+            "pl" | "pm" => Self::Perl,
+            "yaml" | "yml" => Self::Yaml,
+            "toml" => Self::Toml,
+            "dockerfile" => Self::Dockerfile,
+            "html" | "htm" => Self::Html,
+            "xml" | "svg" => Self::Xml,
+            "vue" => Self::Vue,
+            "svelte" => Self::Svelte,
+            "css" => Self::Css,
+            "scss" => Self::Scss,
+            "less" => Self::Less,
+            "sql" => Self::Sql,
+            _ => return None,
+        };
+        Some(lang)
+    }
+
+    /// 按语言名识别，用于 Jupyter kernel 的 language 等场景，其余情况退回按扩展名识别
+    pub fn from_name(name: &str) -> Option<Self> {
+        let lang = match name.to_ascii_lowercase().as_str() {
+            "rust" => Self::Rust,
+            "c++" => Self::Cpp,
+            "c#" => Self::CSharp,
+            "javascript" => Self::JavaScript,
+            "typescript" => Self::TypeScript,
+            "python" | "python3" => Self::Python,
+            "bash" | "shell" => Self::Shell,
+            "ruby" => Self::Ruby,
+            "perl" => Self::Perl,
+            "java" => Self::Java,
+            "kotlin" => Self::Kotlin,
+            "swift" => Self::Swift,
+            "sql" => Self::Sql,
+            other => return Self::from_ext(other),
+        };
+        Some(lang)
+    }
+
+    /// 优先按扩展名识别，没有扩展名时再看文件名（如 `Dockerfile`）
+    pub fn from_path(path: &Path) -> Option<Self> {
+        if let Some(lang) = path.extension().and_then(|s| s.to_str()).and_then(Self::from_ext) {
+            return Some(lang);
+        }
+
+        let name = path.file_name()?.to_str()?.to_ascii_lowercase();
+        if name == "dockerfile" || name == "containerfile" || name.starts_with("dockerfile.") {
+            return Some(Self::Dockerfile);
+        }
+        None
+    }
+
+    /// Markdown 代码块使用的语言标记
+    pub fn fence_tag(&self) -> &'static str {
+        match self {
+            Self::Rust => "rust",
+            Self::C => "c",
+            Self::Cpp => "cpp",
+            Self::Go => "go",
+            Self::Java => "java",
+            Self::Kotlin => "kotlin",
+            Self::Swift => "swift",
+            Self::Dart => "dart",
+            Self::CSharp => "csharp",
+            Self::JavaScript => "javascript",
+            Self::TypeScript => "typescript",
+            Self::Python => "python",
+            Self::Shell => "bash",
+            Self::Ruby => "ruby",
+            Self::Perl => "perl",
+            Self::Yaml => "yaml",
+            Self::Toml => "toml",
+            Self::Dockerfile => "dockerfile",
+            Self::Html => "html",
+            Self::Xml => "xml",
+            Self::Vue => "vue",
+            Self::Svelte => "svelte",
+            Self::Css => "css",
+            Self::Scss => "scss",
+            Self::Less => "less",
+            Self::Sql => "sql",
+        }
+    }
+}
@@ -1,4 +1,15 @@
 pub mod tokenizer;
+pub mod language;
+mod comments;
 pub mod processing;
+mod skeleton;
+pub mod format;
 pub mod core;
+pub mod redact;
+pub mod stream;
+pub mod split;
+pub mod git_context;
+pub mod budget;
+pub mod walker;
+pub mod repo_map;
 pub mod commands;
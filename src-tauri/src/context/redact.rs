@@ -0,0 +1,145 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use rayon::prelude::*;
+use serde::Serialize;
+use super::core::FileBody;
+use crate::gitleaks::{self, IgnoreSet, RuleSet, SecretMatch};
+
+// ============================================================================
+// Inline Secret Redaction
+// ============================================================================
+//
+// 组装上下文时对每个文件运行 gitleaks 规则，把命中的密钥替换成
+// `[REDACTED:<rule>#<n>]`。同一个值在整份上下文里始终对应同一个占位符，
+// 编号按文件顺序分配，与并行处理的先后无关。
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Redaction {
+    pub placeholder: String,
+    pub rule_id: String,
+    pub line_number: usize,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RedactedFile {
+    pub path: String,
+    pub redactions: Vec<Redaction>,
+}
+
+/// 脱敏报告，只记录占位符和位置，不包含密钥本身
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct RedactionReport {
+    /// 替换的次数
+    pub total: usize,
+    /// 不同密钥值的个数（即占位符个数）
+    pub unique_secrets: usize,
+    pub files: Vec<RedactedFile>,
+}
+
+/// 跨批次保存编号状态，流式导出时逐批调用 `redact_batch`
+pub struct Redactor {
+    rules: Arc<RuleSet>,
+    /// 项目根目录，规则和白名单的路径条件按相对它的路径判断
+    root: Option<PathBuf>,
+    ignored: IgnoreSet,
+    placeholders: HashMap<String, String>,
+    counters: HashMap<String, usize>,
+    report: RedactionReport,
+}
+
+impl Redactor {
+    /// `ignored` 中放行的结果（`ignored_secrets` 表）保持原样
+    pub fn new(ignored: IgnoreSet) -> Self {
+        Self::with_rules(gitleaks::active_rules(), None, ignored)
+    }
+
+    /// 项目内的文件：在当前规则上叠加仓库的 `.gitleaks.toml`。`ignored` 应按同一个 `root` 编译
+    pub fn for_repo(root: &Path, ignored: IgnoreSet) -> Self {
+        let absolute = std::path::absolute(root).unwrap_or_else(|_| root.to_path_buf());
+        Self::with_rules(gitleaks::rules_for_repo(root), Some(absolute), ignored)
+    }
+
+    fn with_rules(rules: Arc<RuleSet>, root: Option<PathBuf>, ignored: IgnoreSet) -> Self {
+        Self {
+            rules,
+            root,
+            ignored,
+            placeholders: HashMap::new(),
+            counters: HashMap::new(),
+            report: RedactionReport::default(),
+        }
+    }
+
+    /// 按顺序脱敏一批文件。扫描并行进行，编号和替换按传入顺序依次完成
+    pub(super) fn redact_batch(&mut self, files: Vec<(&str, FileBody)>) -> Vec<FileBody> {
+        let scanned: Vec<(&str, FileBody, Vec<SecretMatch>)> = files.into_par_iter()
+            .map(|(path, body)| {
+                let matches = match &body {
+                    FileBody::Text(text) => {
+                        let relative = self.relative_path(path);
+                        gitleaks::scan_with(&self.rules, text, relative.as_deref()).into_iter()
+                            .filter(|m| !self.ignored.is_ignored(m, Some(relative.as_deref().unwrap_or(path)), None))
+                            .collect()
+                    }
+                    FileBody::Notice(..) => Vec::new(),
+                };
+                (path, body, matches)
+            })
+            .collect();
+
+        scanned.into_iter()
+            .map(|(path, body, matches)| match body {
+                FileBody::Text(text) if !matches.is_empty() => FileBody::Text(self.replace(path, &text, &matches)),
+                body => body,
+            })
+            .collect()
+    }
+
+    /// 文件相对项目根目录的路径，不在项目内时为 None
+    fn relative_path(&self, path: &str) -> Option<String> {
+        let absolute = std::path::absolute(path).ok()?;
+        let relative = absolute.strip_prefix(self.root.as_ref()?).ok()?;
+        Some(relative.to_string_lossy().replace('\\', "/"))
+    }
+
+    /// `matches` 已按位置排序且互不重叠（见 `gitleaks::scan_with`）
+    fn replace(&mut self, path: &str, text: &str, matches: &[SecretMatch]) -> String {
+        let mut out = String::with_capacity(text.len());
+        let mut redactions = Vec::with_capacity(matches.len());
+        let mut last = 0;
+
+        for m in matches {
+            let placeholder = match self.placeholders.get(&m.value) {
+                Some(p) => p.clone(),
+                None => {
+                    let n = self.counters.entry(m.kind.clone()).or_insert(0);
+                    *n += 1;
+                    let p = format!("[REDACTED:{}#{}]", m.kind, n);
+                    self.placeholders.insert(m.value.clone(), p.clone());
+                    p
+                }
+            };
+            out.push_str(&text[last..m.index]);
+            out.push_str(&placeholder);
+            last = m.index + m.matched_text().len();
+            redactions.push(Redaction { placeholder, rule_id: m.kind.clone(), line_number: m.line_number });
+        }
+        out.push_str(&text[last..]);
+
+        self.report.total += redactions.len();
+        self.report.files.push(RedactedFile { path: path.to_string(), redactions });
+        out
+    }
+
+    pub fn into_report(self) -> RedactionReport {
+        RedactionReport { unique_secrets: self.placeholders.len(), ..self.report }
+    }
+}
+
+/// `get_redacted_context` 的返回值
+#[derive(Debug, Serialize)]
+pub struct RedactedContext {
+    pub content: String,
+    pub report: RedactionReport,
+}
@@ -0,0 +1,564 @@
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::Path;
+use once_cell::sync::Lazy;
+use rayon::prelude::*;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use crate::charset;
+use crate::db::ProjectConfig;
+use super::language::Language;
+use super::processing;
+use super::skeleton;
+use super::tokenizer::TokenizerKind;
+use super::walker::{self, NodeKind, WalkNode};
+
+// ============================================================================
+// Repository Symbol Map
+// ============================================================================
+//
+// 为整个项目生成一份紧凑的符号地图：每个文件的顶层函数、类型、类、trait、导出常量，
+// 以及类 / impl / trait 中的方法。先去掉注释并用骨架提取去掉函数体，
+// 再逐行匹配声明；每个符号按它在全项目中被引用的次数排序，超出 token 预算的部分丢弃。
+
+/// 超过该大小的文件不参与（多为生成代码或数据）
+const MAX_FILE_SIZE: u64 = 512 * 1024;
+/// 签名超过该长度时截断
+const MAX_SIGNATURE_CHARS: usize = 120;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SymbolKind {
+    Function,
+    Method,
+    Class,
+    Struct,
+    Enum,
+    Trait,
+    Interface,
+    Type,
+    Module,
+    Impl,
+    Macro,
+    Const,
+}
+
+impl SymbolKind {
+    /// 成员挂在这些符号下面
+    fn is_container(&self) -> bool {
+        matches!(self, Self::Class | Self::Struct | Self::Trait | Self::Interface | Self::Impl | Self::Module)
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Symbol {
+    pub name: String,
+    pub kind: SymbolKind,
+    /// 声明所在行（去掉注释和函数体后的行号，只用于同分时保持源码顺序）
+    #[serde(skip)]
+    order: usize,
+    pub signature: String,
+    /// 在其他文件中出现的次数（同名符号有多个定义时平分）
+    pub references: usize,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub members: Vec<Symbol>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct FileSymbols {
+    /// 相对项目根目录的路径，统一使用 `/`
+    pub path: String,
+    pub symbols: Vec<Symbol>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct RepoMapOptions {
+    /// 渲染结果的 token 上限，不设则输出全部符号
+    #[serde(default)]
+    pub max_tokens: Option<usize>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RepoMap {
+    /// 渲染好的地图，可以放进 `ContextHeader::repo_map`
+    pub content: String,
+    pub tokens: usize,
+    /// 地图中包含的文件和符号，按排名排列
+    pub files: Vec<FileSymbols>,
+    /// 提取到的符号总数（含因预算被丢弃的）
+    pub total_symbols: usize,
+}
+
+/// 遍历项目（遵守与目录树相同的忽略规则）并生成符号地图
+pub fn generate_for_project(
+    root: &str,
+    config: &ProjectConfig,
+    options: &RepoMapOptions,
+    tokenizer: TokenizerKind,
+) -> Result<RepoMap, String> {
+    let mut paths = Vec::new();
+    collect_source_files(&walker::walk_project(root, config)?, &mut paths);
+    Ok(generate(Path::new(root), &paths, options, tokenizer))
+}
+
+fn collect_source_files(nodes: &[WalkNode], out: &mut Vec<String>) {
+    for node in nodes {
+        match &node.children {
+            Some(children) => collect_source_files(children, out),
+            None if node.kind == NodeKind::File && !node.is_binary && node.size <= MAX_FILE_SIZE => {
+                out.push(node.path.clone());
+            }
+            None => {}
+        }
+    }
+}
+
+/// 为给定的文件生成符号地图，路径按 `root` 显示为相对路径
+pub fn generate(root: &Path, paths: &[String], options: &RepoMapOptions, tokenizer: TokenizerKind) -> RepoMap {
+    let parsed: Vec<(String, Vec<Symbol>, String)> = paths.par_iter()
+        .filter_map(|path| {
+            let p = Path::new(path);
+            let lang = Language::from_path(p)?;
+            let bytes = fs::read(p).ok()?;
+            if charset::is_binary(&bytes) {
+                return None;
+            }
+            let code = processing::strip_comments_with(&charset::decode(&bytes).text, Some(lang), false);
+            let symbols = extract_symbols(&code, lang);
+            Some((relative_path(root, p), symbols, code))
+        })
+        .collect();
+
+    // 统计所有定义过的名字在全项目中出现的次数
+    let mut definitions: HashMap<&str, usize> = HashMap::new();
+    for (_, symbols, _) in &parsed {
+        for s in symbols.iter().flat_map(|s| std::iter::once(s).chain(&s.members)) {
+            *definitions.entry(s.name.as_str()).or_insert(0) += 1;
+        }
+    }
+    let names: HashSet<&str> = definitions.keys().copied().collect();
+    let per_file: Vec<HashMap<&str, usize>> = parsed.par_iter()
+        .map(|(_, _, code)| count_identifiers(code, &names))
+        .collect();
+    let mut totals: HashMap<&str, usize> = HashMap::new();
+    for counts in &per_file {
+        for (name, n) in counts {
+            *totals.entry(name).or_insert(0) += n;
+        }
+    }
+    // 只算其他文件中的出现次数：文件内部的辅助函数不会排到前面
+    let references = |name: &str, local: &HashMap<&str, usize>| {
+        let defs = definitions.get(name).copied().unwrap_or(1).max(1);
+        let total = totals.get(name).copied().unwrap_or(0);
+        total.saturating_sub(local.get(name).copied().unwrap_or(0)) / defs
+    };
+
+    let mut files: Vec<FileSymbols> = parsed.iter()
+        .zip(&per_file)
+        .filter(|((_, symbols, _), _)| !symbols.is_empty())
+        .map(|((path, symbols, _), local)| {
+            let mut symbols = symbols.clone();
+            for s in &mut symbols {
+                s.references = references(&s.name, local);
+                for m in &mut s.members {
+                    m.references = references(&m.name, local);
+                }
+                // impl 的名字是实现的类型，按类型被引用的次数排序
+                rank(&mut s.members);
+            }
+            rank(&mut symbols);
+            FileSymbols { path: path.clone(), symbols }
+        })
+        .collect();
+    files.sort_by(|a, b| file_score(b).cmp(&file_score(a)).then_with(|| a.path.cmp(&b.path)));
+
+    let total_symbols = files.iter().flat_map(|f| &f.symbols).map(|s| 1 + s.members.len()).sum();
+    if let Some(max_tokens) = options.max_tokens {
+        files = fit_budget(files, max_tokens, tokenizer);
+    }
+    let content = render(&files);
+    RepoMap { tokens: tokenizer.count(&content), content, files, total_symbols }
+}
+
+fn relative_path(root: &Path, path: &Path) -> String {
+    path.strip_prefix(root).unwrap_or(path).to_string_lossy().replace('\\', "/")
+}
+
+fn rank(symbols: &mut [Symbol]) {
+    symbols.sort_by(|a, b| b.references.cmp(&a.references).then_with(|| a.order.cmp(&b.order)));
+}
+
+fn file_score(file: &FileSymbols) -> usize {
+    file.symbols.iter().map(|s| s.references).sum()
+}
+
+fn count_identifiers<'a>(code: &str, names: &HashSet<&'a str>) -> HashMap<&'a str, usize> {
+    let mut counts = HashMap::new();
+    let is_ident = |c: char| c.is_alphanumeric() || c == '_' || c == '$';
+    for word in code.split(|c: char| !is_ident(c)) {
+        if let Some(&name) = names.get(word) {
+            *counts.entry(name).or_insert(0) += 1;
+        }
+    }
+    counts
+}
+
+// ----------------------------------------------------------------------------
+// Rendering & Budget
+// ----------------------------------------------------------------------------
+
+/// ```text
+/// src/context/core.rs:
+///   pub fn load_file(path: &str, options: &ProcessOptions) -> FileBody
+///   impl FileBody
+///     fn as_str(&self) -> &str
+/// ```
+fn render(files: &[FileSymbols]) -> String {
+    let mut out = String::new();
+    for file in files {
+        out.push_str(&file_line(file));
+        for s in &file.symbols {
+            out.push_str(&symbol_line(s, 1));
+            for m in &s.members {
+                out.push_str(&symbol_line(m, 2));
+            }
+        }
+    }
+    out
+}
+
+fn file_line(file: &FileSymbols) -> String {
+    format!("{}:\n", file.path)
+}
+
+fn symbol_line(symbol: &Symbol, depth: usize) -> String {
+    format!("{}{}\n", "  ".repeat(depth), symbol.signature)
+}
+
+/// 按引用次数从高到低依次放入符号，直到超出预算。逐行计算 token 数，与整体计算相差很小
+fn fit_budget(files: Vec<FileSymbols>, max_tokens: usize, tokenizer: TokenizerKind) -> Vec<FileSymbols> {
+    // (引用数, 文件下标, 符号下标, 成员下标)
+    let mut candidates: Vec<(usize, usize, usize, Option<usize>)> = Vec::new();
+    for (fi, file) in files.iter().enumerate() {
+        for (si, s) in file.symbols.iter().enumerate() {
+            candidates.push((s.references, fi, si, None));
+            for (mi, m) in s.members.iter().enumerate() {
+                candidates.push((m.references, fi, si, Some(mi)));
+            }
+        }
+    }
+    candidates.sort_by(|a, b| b.0.cmp(&a.0).then_with(|| (a.1, a.2, a.3).cmp(&(b.1, b.2, b.3))));
+
+    let mut used = 0;
+    let mut kept_files: HashSet<usize> = HashSet::new();
+    let mut kept_symbols: HashSet<(usize, usize)> = HashSet::new();
+    let mut kept_members: HashSet<(usize, usize, usize)> = HashSet::new();
+    for (_, fi, si, mi) in candidates {
+        let file = &files[fi];
+        let symbol = &file.symbols[si];
+        let mut cost = 0;
+        if !kept_files.contains(&fi) {
+            cost += tokenizer.count(&file_line(file));
+        }
+        if !kept_symbols.contains(&(fi, si)) {
+            cost += tokenizer.count(&symbol_line(symbol, 1));
+        }
+        if let Some(mi) = mi {
+            cost += tokenizer.count(&symbol_line(&symbol.members[mi], 2));
+        }
+        if used + cost > max_tokens {
+            continue;
+        }
+        used += cost;
+        kept_files.insert(fi);
+        kept_symbols.insert((fi, si));
+        if let Some(mi) = mi {
+            kept_members.insert((fi, si, mi));
+        }
+    }
+
+    files.into_iter()
+        .enumerate()
+        .filter(|(fi, _)| kept_files.contains(fi))
+        .map(|(fi, file)| {
+            let symbols = file.symbols.into_iter()
+                .enumerate()
+                .filter(|(si, _)| kept_symbols.contains(&(fi, *si)))
+                .map(|(si, mut s)| {
+                    s.members = std::mem::take(&mut s.members).into_iter()
+                        .enumerate()
+                        .filter(|(mi, _)| kept_members.contains(&(fi, si, *mi)))
+                        .map(|(_, m)| m)
+                        .collect();
+                    s
+                })
+                .collect();
+            FileSymbols { path: file.path, symbols }
+        })
+        .collect()
+}
+
+// ----------------------------------------------------------------------------
+// Symbol Extraction
+// ----------------------------------------------------------------------------
+
+/// 一条声明规则：`name` 分组是符号名；有 `kind` 分组时按关键字决定类型
+struct Pattern {
+    re: Regex,
+    kind: SymbolKind,
+}
+
+fn pattern(re: &str, kind: SymbolKind) -> Pattern {
+    Pattern { re: Regex::new(re).expect("invalid repo map pattern"), kind }
+}
+
+/// 某一类语言的顶层声明和容器内成员的规则
+struct Rules {
+    top: Vec<Pattern>,
+    members: Vec<Pattern>,
+}
+
+const RUST_VIS: &str = r"(?:pub(?:\([^)]*\))?\s+)?";
+const C_MODIFIERS: &str = r"(?:(?:public|private|protected|internal|static|final|abstract|sealed|partial|open|data|inline|export|virtual|override|async|extern|const|constexpr|unsafe|readonly|fileprivate|mutating|suspend|operator|infix|tailrec|external|actual|expect|lateinit|default)\s+)*";
+/// C 风格的函数 / 方法：返回类型和修饰符之后是 `name(...)`，函数体已被折叠成 `{ ... }`
+const C_FUNCTION: &str = r"^(?:[\w:<>,\*&\[\]~?]+\s+)+[\*&]*(?P<name>[\w:~]+)\s*\([^;]*\)[^;=]*\{";
+/// 只有声明没有函数体：头文件里的原型、接口和抽象方法
+const C_PROTOTYPE: &str = r"^(?:[\w:<>,\*&\[\]~?]+\s+)+[\*&]*(?P<name>[\w:~]+)\s*\([^;{=]*\)\s*(?:const\s*)?(?:throws\s+[\w., ]+)?;";
+
+static RUST: Lazy<Rules> = Lazy::new(|| Rules {
+    top: vec![
+        pattern(&format!(r#"^{}(?:(?:const|async|unsafe|extern\s+"[^"]*")\s+)*fn\s+(?P<name>\w+)"#, RUST_VIS), SymbolKind::Function),
+        pattern(&format!(r"^{}(?:struct|union)\s+(?P<name>\w+)", RUST_VIS), SymbolKind::Struct),
+        pattern(&format!(r"^{}enum\s+(?P<name>\w+)", RUST_VIS), SymbolKind::Enum),
+        pattern(&format!(r"^{}(?:unsafe\s+)?trait\s+(?P<name>\w+)", RUST_VIS), SymbolKind::Trait),
+        pattern(&format!(r"^{}type\s+(?P<name>\w+)", RUST_VIS), SymbolKind::Type),
+        // `mod foo;` 只是声明文件，不算符号
+        pattern(&format!(r"^{}mod\s+(?P<name>\w+)\s*\{{", RUST_VIS), SymbolKind::Module),
+        pattern(r"^pub(?:\([^)]*\))?\s+(?:const|static)\s+(?:mut\s+)?(?P<name>\w+)", SymbolKind::Const),
+        pattern(r"^(?:unsafe\s+)?impl\b(?:<.*?>)?\s+(?:.*?\s+for\s+)?(?P<name>\w+)", SymbolKind::Impl),
+        pattern(r"^macro_rules!\s*(?P<name>\w+)", SymbolKind::Macro),
+    ],
+    members: vec![
+        pattern(&format!(r#"^{}(?:(?:const|async|unsafe|extern\s+"[^"]*")\s+)*fn\s+(?P<name>\w+)"#, RUST_VIS), SymbolKind::Method),
+    ],
+});
+
+static GO: Lazy<Rules> = Lazy::new(|| Rules {
+    top: vec![
+        pattern(r"^func\s+\([^)]*\)\s*(?P<name>\w+)", SymbolKind::Method),
+        pattern(r"^func\s+(?P<name>\w+)", SymbolKind::Function),
+        pattern(r"^type\s+(?P<name>\w+)\s+struct\b", SymbolKind::Struct),
+        pattern(r"^type\s+(?P<name>\w+)\s+interface\b", SymbolKind::Interface),
+        pattern(r"^type\s+(?P<name>\w+)", SymbolKind::Type),
+        // Go 只有首字母大写的才是导出的
+        pattern(r"^(?:const|var)\s+(?P<name>[A-Z]\w*)", SymbolKind::Const),
+    ],
+    members: vec![],
+});
+
+static JAVASCRIPT: Lazy<Rules> = Lazy::new(|| Rules {
+    top: vec![
+        pattern(r"^(?:export\s+)?(?:default\s+)?(?:declare\s+)?(?:async\s+)?function\s*\*?\s*(?P<name>[\w$]+)", SymbolKind::Function),
+        pattern(r"^(?:export\s+)?(?:default\s+)?(?:declare\s+)?(?:abstract\s+)?class\s+(?P<name>[\w$]+)", SymbolKind::Class),
+        pattern(r"^(?:export\s+)?(?:declare\s+)?interface\s+(?P<name>[\w$]+)", SymbolKind::Interface),
+        pattern(r"^(?:export\s+)?(?:declare\s+)?type\s+(?P<name>[\w$]+)", SymbolKind::Type),
+        pattern(r"^(?:export\s+)?(?:declare\s+)?(?:const\s+)?enum\s+(?P<name>[\w$]+)", SymbolKind::Enum),
+        pattern(r"^(?:export\s+)?(?:declare\s+)?(?:namespace|module)\s+(?P<name>[\w$.]+)", SymbolKind::Module),
+        pattern(r"^export\s+(?:declare\s+)?(?:const|let|var)\s+(?P<name>[\w$]+)", SymbolKind::Const),
+    ],
+    members: vec![
+        pattern(r"^(?:(?:public|private|protected|static|readonly|async|abstract|override|declare|get|set)\s+)*\*?(?P<name>#?[\w$]+)\s*(?:<[^>]*>)?\s*\(", SymbolKind::Method),
+    ],
+});
+
+static PYTHON: Lazy<Rules> = Lazy::new(|| Rules {
+    top: vec![
+        pattern(r"^(?:async\s+)?def\s+(?P<name>\w+)", SymbolKind::Function),
+        pattern(r"^class\s+(?P<name>\w+)", SymbolKind::Class),
+        pattern(r"^(?P<name>[A-Z][A-Z0-9_]*)\s*(?::[^=]*)?=", SymbolKind::Const),
+    ],
+    members: vec![
+        pattern(r"^(?:async\s+)?def\s+(?P<name>\w+)", SymbolKind::Method),
+    ],
+});
+
+static RUBY: Lazy<Rules> = Lazy::new(|| Rules {
+    top: vec![
+        pattern(r"^def\s+(?:self\.)?(?P<name>\w+[?!=]?)", SymbolKind::Function),
+        pattern(r"^class\s+(?P<name>[\w:]+)", SymbolKind::Class),
+        pattern(r"^module\s+(?P<name>[\w:]+)", SymbolKind::Module),
+        pattern(r"^(?P<name>[A-Z][A-Z0-9_]*)\s*=", SymbolKind::Const),
+    ],
+    members: vec![
+        pattern(r"^def\s+(?:self\.)?(?P<name>\w+[?!=]?)", SymbolKind::Method),
+        pattern(r"^class\s+(?P<name>[\w:]+)", SymbolKind::Class),
+    ],
+});
+
+/// Java / C# / Kotlin / Swift / Dart / C / C++
+static C_FAMILY: Lazy<Rules> = Lazy::new(|| Rules {
+    top: vec![
+        pattern(
+            &format!(r"^(?:template\s*<.*>\s*)?{}(?:enum\s+)?(?P<kind>class|interface|enum|struct|record|object|protocol|trait|extension|namespace|union)\s+(?P<name>\w+)", C_MODIFIERS),
+            SymbolKind::Class,
+        ),
+        pattern(&format!(r"^{}(?:fun|func)\s+(?:<[^>]*>\s*)?(?:[\w.]+\.)?(?P<name>\w+)", C_MODIFIERS), SymbolKind::Function),
+        pattern(r"^#define\s+(?P<name>[A-Z]\w*)", SymbolKind::Const),
+        pattern(r"^(?:typedef\s+.*?\s+)(?P<name>\w+)\s*;", SymbolKind::Type),
+        pattern(C_FUNCTION, SymbolKind::Function),
+        pattern(C_PROTOTYPE, SymbolKind::Function),
+    ],
+    members: vec![
+        pattern(
+            &format!(r"^{}(?:enum\s+)?(?P<kind>class|interface|enum|struct|record|object|protocol)\s+(?P<name>\w+)", C_MODIFIERS),
+            SymbolKind::Class,
+        ),
+        pattern(&format!(r"^{}(?:fun|func)\s+(?:<[^>]*>\s*)?(?P<name>\w+)", C_MODIFIERS), SymbolKind::Method),
+        pattern(r"\bstatic\s+final\s+[\w<>\[\], ]+\s+(?P<name>[A-Z][A-Z0-9_]*)\s*=", SymbolKind::Const),
+        pattern(r"\bconst\s+[\w<>\[\], ]+\s+(?P<name>[A-Z]\w*)\s*=", SymbolKind::Const),
+        pattern(C_FUNCTION, SymbolKind::Method),
+        pattern(C_PROTOTYPE, SymbolKind::Method),
+        // 构造函数没有返回类型
+        pattern(&format!(r"^{}(?P<name>[A-Z]\w*)\s*\([^;]*\)[^;=]*\{{", C_MODIFIERS), SymbolKind::Method),
+    ],
+});
+
+static SHELL: Lazy<Rules> = Lazy::new(|| Rules {
+    top: vec![
+        pattern(r"^function\s+(?P<name>[\w:-]+)", SymbolKind::Function),
+        pattern(r"^(?P<name>[\w:-]+)\s*\(\)", SymbolKind::Function),
+    ],
+    members: vec![],
+});
+
+static PERL: Lazy<Rules> = Lazy::new(|| Rules {
+    top: vec![
+        pattern(r"^sub\s+(?P<name>\w+)", SymbolKind::Function),
+        pattern(r"^package\s+(?P<name>[\w:]+)", SymbolKind::Module),
+    ],
+    members: vec![
+        pattern(r"^sub\s+(?P<name>\w+)", SymbolKind::Method),
+    ],
+});
+
+fn rules_for(lang: Language) -> Option<&'static Rules> {
+    let rules: &Rules = match lang {
+        Language::Rust => &RUST,
+        Language::Go => &GO,
+        Language::JavaScript | Language::TypeScript => &JAVASCRIPT,
+        Language::Python => &PYTHON,
+        Language::Ruby => &RUBY,
+        Language::C | Language::Cpp | Language::Java | Language::Kotlin
+        | Language::Swift | Language::Dart | Language::CSharp => &C_FAMILY,
+        Language::Shell => &SHELL,
+        Language::Perl => &PERL,
+        _ => return None,
+    };
+    Some(rules)
+}
+
+/// 匹配 if / for 这类语句而不是声明的名字
+const NOT_SYMBOLS: &[&str] = &[
+    "if", "else", "for", "foreach", "while", "switch", "catch", "return", "new", "sizeof", "do",
+    "try", "using", "lock", "synchronized", "when", "guard", "defer", "throw", "await", "super", "this",
+];
+
+fn match_line(patterns: &[Pattern], line: &str) -> Option<(String, SymbolKind)> {
+    patterns.iter().find_map(|p| {
+        let caps = p.re.captures(line)?;
+        let name = caps.name("name")?.as_str();
+        if NOT_SYMBOLS.contains(&name) {
+            return None;
+        }
+        let kind = match caps.name("kind").map(|k| k.as_str()) {
+            Some("interface" | "protocol") => SymbolKind::Interface,
+            Some("enum") => SymbolKind::Enum,
+            Some("struct" | "union" | "record") => SymbolKind::Struct,
+            Some("trait") => SymbolKind::Trait,
+            Some("namespace") => SymbolKind::Module,
+            Some(_) | None => p.kind,
+        };
+        Some((name.to_string(), kind))
+    })
+}
+
+/// 去掉折叠后的函数体和行尾的 `{` / `:`，过长时截断
+fn signature(line: &str) -> String {
+    let mut sig = line.trim();
+    if let Some(p) = sig.find("{ ...") {
+        sig = &sig[..p];
+    }
+    let sig = sig.trim_end().trim_end_matches(['{', ':', ';']).trim_end();
+    match sig.char_indices().nth(MAX_SIGNATURE_CHARS) {
+        Some((cut, _)) => format!("{}…", &sig[..cut]),
+        None => sig.to_string(),
+    }
+}
+
+/// 常量只在初始值很短时保留，`= create<State>()(` 这类多行表达式去掉等号后的部分
+fn const_signature(line: &str) -> String {
+    let sig = signature(line);
+    let multiline = line.trim_end().ends_with(['(', '[', '{', ',', '>', '=']);
+    match sig.find('=') {
+        Some(p) if multiline || sig.len() > 80 => sig[..p].trim_end().to_string(),
+        _ => sig,
+    }
+}
+
+fn indent_width(line: &str) -> usize {
+    line.bytes()
+        .take_while(|&b| b == b' ' || b == b'\t')
+        .map(|b| if b == b'\t' { 4 } else { 1 })
+        .sum()
+}
+
+/// 提取一个文件的顶层符号，以及顶层容器（类、impl、trait 等）中的直接成员。
+/// 嵌套层级按缩进判断；骨架提取失败时只看顶格的声明
+fn extract_symbols(code: &str, lang: Language) -> Vec<Symbol> {
+    let Some(rules) = rules_for(lang) else { return Vec::new() };
+    let (outline, members_allowed) = match skeleton::extract(code, lang) {
+        Some(outline) => (outline, true),
+        None => (code.to_string(), false),
+    };
+
+    let mut symbols: Vec<Symbol> = Vec::new();
+    // 当前所在的顶层容器，以及它的成员缩进（第一个成员决定）
+    let mut container: Option<(usize, Option<usize>)> = None;
+
+    for (order, line) in outline.lines().enumerate() {
+        let text = line.trim();
+        if text.is_empty() {
+            continue;
+        }
+        let indent = indent_width(line);
+
+        if indent == 0 {
+            container = None;
+            let Some((name, kind)) = match_line(&rules.top, text) else { continue };
+            if kind.is_container() && members_allowed {
+                container = Some((symbols.len(), None));
+            }
+            let signature = if kind == SymbolKind::Const { const_signature(text) } else { signature(text) };
+            symbols.push(Symbol { name, kind, order, signature, references: 0, members: Vec::new() });
+            continue;
+        }
+
+        let Some((parent, member_indent)) = container.as_mut() else { continue };
+        if member_indent.is_some_and(|m| m != indent) {
+            continue;
+        }
+        if let Some((name, kind)) = match_line(&rules.members, text) {
+            *member_indent = Some(indent);
+            symbols[*parent].members.push(Symbol {
+                name,
+                kind,
+                order,
+                signature: signature(text),
+                references: 0,
+                members: Vec::new(),
+            });
+        }
+    }
+
+    symbols
+}
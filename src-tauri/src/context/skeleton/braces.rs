@@ -0,0 +1,413 @@
+use super::ELIDED;
+
+/// 反引号字符串的写法
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Backtick {
+    None,
+    /// JS 模板字符串，支持转义
+    Escaped,
+    /// Go 原始字符串
+    Raw,
+}
+
+/// 花括号语言的词法差异，以及哪些代码块需要展开
+pub(super) struct Syntax {
+    block_comments: bool,
+    nested_comments: bool,
+    line_comment: Option<&'static [u8]>,
+    /// 带转义的引号
+    quotes: &'static [u8],
+    /// 字符串可以跨行（Rust、Shell、Perl）
+    multiline_strings: bool,
+    /// 单引号串内没有转义（Shell）
+    raw_single_quotes: bool,
+    triple_quotes: bool,
+    /// C# 的 `@"..."`
+    verbatim_strings: bool,
+    backtick: Backtick,
+    /// Rust 的原始字符串、字符字面量与生命周期
+    rust: bool,
+    /// 出现这些关键字的代码块是函数体，整体省略
+    functions: &'static [&'static str],
+    /// 这些代码块展开处理内部成员（类、模块、impl 等）
+    containers: &'static [&'static str],
+    /// 这些代码块原样保留（枚举、多行 import）
+    verbatim: &'static [&'static str],
+}
+
+const FUNCTIONS: &[&str] = &["fn", "func", "function", "fun", "def"];
+const CONTAINERS: &[&str] = &[
+    "class", "struct", "trait", "impl", "interface", "namespace", "mod", "module", "object",
+    "extension", "protocol", "union", "record", "mixin", "type", "extern",
+];
+const VERBATIM: &[&str] = &["enum", "use", "import"];
+
+impl Syntax {
+    pub(super) const RUST: Syntax = Syntax {
+        block_comments: true,
+        nested_comments: true,
+        line_comment: Some(b"//"),
+        quotes: b"\"",
+        multiline_strings: true,
+        raw_single_quotes: false,
+        triple_quotes: false,
+        verbatim_strings: false,
+        backtick: Backtick::None,
+        rust: true,
+        functions: FUNCTIONS,
+        containers: CONTAINERS,
+        verbatim: VERBATIM,
+    };
+
+    /// C / C++ / Java / C# / Kotlin / Swift / Dart
+    pub(super) const C: Syntax = Syntax {
+        block_comments: true,
+        nested_comments: false,
+        line_comment: Some(b"//"),
+        quotes: b"\"'",
+        multiline_strings: false,
+        raw_single_quotes: false,
+        triple_quotes: true,
+        verbatim_strings: true,
+        backtick: Backtick::None,
+        rust: false,
+        functions: FUNCTIONS,
+        containers: CONTAINERS,
+        verbatim: VERBATIM,
+    };
+
+    pub(super) const GO: Syntax = Syntax {
+        backtick: Backtick::Raw,
+        triple_quotes: false,
+        verbatim_strings: false,
+        ..Self::C
+    };
+
+    pub(super) const JAVASCRIPT: Syntax = Syntax {
+        backtick: Backtick::Escaped,
+        triple_quotes: false,
+        verbatim_strings: false,
+        ..Self::C
+    };
+
+    /// CSS 只展开 `@media` 这类分组规则，普通规则只留选择器
+    pub(super) const CSS: Syntax = Syntax {
+        line_comment: None,
+        triple_quotes: false,
+        verbatim_strings: false,
+        functions: &[],
+        containers: &["@media", "@supports", "@layer", "@container", "@document"],
+        verbatim: &[],
+        ..Self::C
+    };
+
+    pub(super) const SCSS: Syntax = Syntax {
+        line_comment: Some(b"//"),
+        ..Self::CSS
+    };
+
+    pub(super) const SHELL: Syntax = Syntax {
+        block_comments: false,
+        nested_comments: false,
+        line_comment: Some(b"#"),
+        quotes: b"\"'",
+        multiline_strings: true,
+        raw_single_quotes: true,
+        triple_quotes: false,
+        verbatim_strings: false,
+        backtick: Backtick::None,
+        rust: false,
+        functions: &["function"],
+        containers: &[],
+        verbatim: &[],
+    };
+
+    pub(super) const PERL: Syntax = Syntax {
+        raw_single_quotes: false,
+        functions: &["sub"],
+        containers: &["package"],
+        ..Self::SHELL
+    };
+
+    /// 如果 `i` 处是字符串或注释，返回它的结束位置
+    fn literal_end(&self, s: &[u8], i: usize) -> Option<usize> {
+        let c = s[i];
+        let rest = &s[i..];
+        let prev = i.checked_sub(1).map(|p| s[p]);
+
+        if self.block_comments && rest.starts_with(b"/*") {
+            return Some(block_comment_end(s, i, self.nested_comments));
+        }
+        if let Some(lc) = self.line_comment {
+            // `#` 只有出现在词首时才是注释（排除 `$#`、`${#x}` 等）
+            let hash_ok = lc != b"#" || matches!(prev, None | Some(b' ' | b'\t' | b'\n' | b';' | b'(' | b'{' | b'}' | b'|' | b'&'));
+            if rest.starts_with(lc) && hash_ok {
+                return Some(find(s, b"\n", i).unwrap_or(s.len()));
+            }
+        }
+        if self.triple_quotes && (rest.starts_with(b"\"\"\"") || rest.starts_with(b"'''")) {
+            return Some(find(s, &rest[..3], i + 3).map(|e| e + 3).unwrap_or(s.len()));
+        }
+        if self.verbatim_strings && rest.starts_with(b"@\"") {
+            let mut j = i + 2;
+            while j < s.len() {
+                if s[j] == b'"' {
+                    if s.get(j + 1) == Some(&b'"') {
+                        j += 2;
+                        continue;
+                    }
+                    return Some(j + 1);
+                }
+                j += 1;
+            }
+            return Some(s.len());
+        }
+        if self.rust {
+            let ident_before = prev.is_some_and(|p| p.is_ascii_alphanumeric() || p == b'_');
+            if (c == b'r' || c == b'b') && !ident_before {
+                if let Some(end) = rust_raw_string_end(s, i) {
+                    return Some(end);
+                }
+            }
+            if c == b'\'' {
+                return rust_char_end(s, i);
+            }
+        }
+        if self.quotes.contains(&c) {
+            let escapes = !(self.raw_single_quotes && c == b'\'');
+            return Some(quoted_end(s, i, c, escapes, self.multiline_strings));
+        }
+        match (c, self.backtick) {
+            (b'`', Backtick::Escaped) => Some(quoted_end(s, i, b'`', true, true)),
+            (b'`', Backtick::Raw) => Some(quoted_end(s, i, b'`', false, true)),
+            _ => None,
+        }
+    }
+
+    fn classify(&self, header: &[u8]) -> Block {
+        let all = words(header);
+        if all.is_empty() || find(header, b"=>", 0).is_some() {
+            return Block::Body;
+        }
+        if all.iter().any(|w| self.functions.contains(w)) {
+            return Block::Body;
+        }
+
+        // 多行 import 只看 `{` 所在的那一行，避免上一条语句的关键字串进来
+        let last: Vec<&str> = header.split(|&b| b == b'\n')
+            .map(words)
+            .rfind(|w| !w.is_empty())
+            .unwrap_or_default();
+        if last == ["export"] || last.iter().any(|w| self.verbatim.contains(w)) {
+            return Block::Verbatim;
+        }
+        if all.iter().any(|w| self.containers.contains(w)) {
+            return Block::Container;
+        }
+        Block::Body
+    }
+}
+
+enum Block {
+    /// 保留花括号，继续处理内部成员
+    Container,
+    Verbatim,
+    Body,
+}
+
+/// 扫描整个文件：多行的函数体折叠成 `{ ... }`，类型 / 模块展开，同一行内的花括号原样保留。
+/// 括号不配对时返回 None
+pub(super) fn extract(src: &str, syntax: &Syntax) -> Option<String> {
+    let s = src.as_bytes();
+    let mut out = Vec::with_capacity(s.len() / 2);
+    // 当前语句里已经扫描过的代码（字符串、注释只留一个空格），用来判断下一个 `{` 的类型
+    let mut header: Vec<u8> = Vec::new();
+    let mut open_containers = 0usize;
+    let mut i = 0;
+
+    while i < s.len() {
+        if let Some(end) = syntax.literal_end(s, i) {
+            out.extend_from_slice(&s[i..end]);
+            header.push(b' ');
+            i = end;
+            continue;
+        }
+
+        match s[i] {
+            b'{' => {
+                let close = matching_brace(s, i, syntax)?;
+                if !s[i..close].contains(&b'\n') {
+                    out.extend_from_slice(&s[i..=close]);
+                    header.extend_from_slice(b"{}");
+                    i = close + 1;
+                    continue;
+                }
+                match syntax.classify(&header) {
+                    Block::Container => {
+                        out.push(b'{');
+                        header.clear();
+                        open_containers += 1;
+                        i += 1;
+                        continue;
+                    }
+                    Block::Verbatim => out.extend_from_slice(&s[i..=close]),
+                    Block::Body => {
+                        out.extend_from_slice(b"{ ");
+                        out.extend_from_slice(ELIDED.as_bytes());
+                        out.extend_from_slice(b" }");
+                    }
+                }
+                i = close + 1;
+                header.extend_from_slice(b"{}");
+                if rest_of_line_blank(s, i) {
+                    header.clear();
+                }
+            }
+            b'}' => {
+                open_containers = open_containers.checked_sub(1)?;
+                out.push(b'}');
+                header.clear();
+                i += 1;
+            }
+            b';' => {
+                out.push(b';');
+                header.clear();
+                i += 1;
+            }
+            b'\n' => {
+                // 空行分隔开的是另一条语句
+                let line_start = header.iter().rposition(|&b| b == b'\n').map(|p| p + 1).unwrap_or(0);
+                if header[line_start..].iter().all(|b| b.is_ascii_whitespace()) {
+                    header.clear();
+                } else {
+                    header.push(b'\n');
+                }
+                out.push(b'\n');
+                i += 1;
+            }
+            c => {
+                out.push(c);
+                header.push(c);
+                i += 1;
+            }
+        }
+    }
+
+    if open_containers != 0 {
+        return None;
+    }
+    // 只在 ASCII 字节处切分，结果一定是合法的 UTF-8
+    String::from_utf8(out).ok()
+}
+
+fn matching_brace(s: &[u8], open: usize, syntax: &Syntax) -> Option<usize> {
+    let mut depth = 0usize;
+    let mut i = open;
+    while i < s.len() {
+        if let Some(end) = syntax.literal_end(s, i) {
+            i = end;
+            continue;
+        }
+        match s[i] {
+            b'{' => depth += 1,
+            b'}' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(i);
+                }
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+    None
+}
+
+fn rest_of_line_blank(s: &[u8], from: usize) -> bool {
+    s[from..].iter()
+        .take_while(|&&b| b != b'\n')
+        .all(|b| b.is_ascii_whitespace())
+}
+
+fn words(text: &[u8]) -> Vec<&str> {
+    text.split(|&b| !(b.is_ascii_alphanumeric() || b == b'_' || b == b'$' || b == b'@'))
+        .filter(|w| !w.is_empty())
+        .filter_map(|w| std::str::from_utf8(w).ok())
+        .collect()
+}
+
+fn find(s: &[u8], pat: &[u8], from: usize) -> Option<usize> {
+    if from >= s.len() {
+        return None;
+    }
+    s[from..].windows(pat.len()).position(|w| w == pat).map(|p| from + p)
+}
+
+fn block_comment_end(s: &[u8], start: usize, nested: bool) -> usize {
+    let mut depth = 0;
+    let mut i = start;
+    while i + 1 < s.len() {
+        if s[i] == b'/' && s[i + 1] == b'*' && (nested || depth == 0) {
+            depth += 1;
+            i += 2;
+        } else if s[i] == b'*' && s[i + 1] == b'/' {
+            depth -= 1;
+            i += 2;
+            if depth == 0 {
+                return i;
+            }
+        } else {
+            i += 1;
+        }
+    }
+    s.len()
+}
+
+fn quoted_end(s: &[u8], start: usize, quote: u8, escapes: bool, multiline: bool) -> usize {
+    let mut i = start + 1;
+    while i < s.len() {
+        match s[i] {
+            b'\\' if escapes => i += 2,
+            c if c == quote => return i + 1,
+            b'\n' if !multiline => return i,
+            _ => i += 1,
+        }
+    }
+    s.len()
+}
+
+/// `r"..."`、`r#"..."#`、`br"..."`
+fn rust_raw_string_end(s: &[u8], start: usize) -> Option<usize> {
+    let mut j = start;
+    if s.get(j) == Some(&b'b') {
+        j += 1;
+    }
+    if s.get(j) != Some(&b'r') {
+        return None;
+    }
+    j += 1;
+    let hashes = s[j..].iter().take_while(|&&b| b == b'#').count();
+    j += hashes;
+    if s.get(j) != Some(&b'"') {
+        return None;
+    }
+    let mut closing = vec![b'"'];
+    closing.extend(std::iter::repeat_n(b'#', hashes));
+    Some(find(s, &closing, j + 1).map(|e| e + closing.len()).unwrap_or(s.len()))
+}
+
+/// 字符字面量返回结束位置；生命周期（`'a`）返回 None，按普通代码处理
+fn rust_char_end(s: &[u8], start: usize) -> Option<usize> {
+    let next = *s.get(start + 1)?;
+    if next == b'\\' {
+        let close = s[start + 2..].iter().take(12).position(|&b| b == b'\'')?;
+        return Some(start + 2 + close + 1);
+    }
+    let width = match next {
+        0x00..=0x7f => 1,
+        0xc0..=0xdf => 2,
+        0xe0..=0xef => 3,
+        _ => 4,
+    };
+    (s.get(start + 1 + width) == Some(&b'\'')).then_some(start + 2 + width)
+}
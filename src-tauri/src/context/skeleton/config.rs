@@ -0,0 +1,133 @@
+use super::{indent_width, ELIDED};
+
+/// YAML 保留到第几层（0 为顶层键）
+const YAML_MAX_DEPTH: usize = 1;
+
+fn push_elided(out: &mut String, indent: usize) {
+    out.push_str(&" ".repeat(indent));
+    out.push_str(ELIDED);
+    out.push('\n');
+}
+
+/// 只保留顶层键和它们的直接子项，更深的内容每段折叠成一行 `...`
+pub(super) fn extract_yaml(src: &str) -> String {
+    let mut out = String::with_capacity(src.len() / 2);
+    let mut ancestors: Vec<usize> = Vec::new();
+    let mut eliding = false;
+
+    for line in src.lines() {
+        let t = line.trim();
+        if t.is_empty() || t.starts_with('#') {
+            if !eliding {
+                out.push_str(line);
+                out.push('\n');
+            }
+            continue;
+        }
+
+        let indent = indent_width(line);
+        while ancestors.last().is_some_and(|&a| a >= indent) {
+            ancestors.pop();
+        }
+        let depth = ancestors.len();
+        ancestors.push(indent);
+
+        if depth <= YAML_MAX_DEPTH {
+            out.push_str(line);
+            out.push('\n');
+            eliding = false;
+        } else if !eliding {
+            push_elided(&mut out, indent);
+            eliding = true;
+        }
+    }
+
+    out
+}
+
+/// 保留表头和键，多行数组 / 多行字符串只留第一行
+pub(super) fn extract_toml(src: &str) -> String {
+    let mut out = String::with_capacity(src.len() / 2);
+    let mut open_brackets = 0i32;
+    let mut open_triple: Option<&str> = None;
+
+    for line in src.lines() {
+        if let Some(q) = open_triple {
+            if line.contains(q) {
+                open_triple = None;
+            }
+            continue;
+        }
+        if open_brackets > 0 {
+            open_brackets += bracket_balance(line);
+            continue;
+        }
+
+        out.push_str(line);
+        out.push('\n');
+
+        let t = line.trim_start();
+        if t.starts_with('[') || t.starts_with('#') {
+            continue;
+        }
+        let Some((_, value)) = t.split_once('=') else { continue };
+        let value = value.trim();
+        for q in ["\"\"\"", "'''"] {
+            if value.starts_with(q) && value.matches(q).count() == 1 {
+                open_triple = Some(q);
+            }
+        }
+        if open_triple.is_none() {
+            open_brackets = bracket_balance(value).max(0);
+        }
+        if open_triple.is_some() || open_brackets > 0 {
+            push_elided(&mut out, indent_width(line) + 4);
+        }
+    }
+
+    out
+}
+
+/// 单行内 `[`、`{` 比 `]`、`}` 多几个（引号内的不算）
+fn bracket_balance(line: &str) -> i32 {
+    let mut balance = 0;
+    let mut quote: Option<char> = None;
+    let mut chars = line.chars();
+    while let Some(c) = chars.next() {
+        match (quote, c) {
+            (Some('"'), '\\') => {
+                chars.next();
+            }
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), _) => {}
+            (None, '"' | '\'') => quote = Some(c),
+            (None, '#') => break,
+            (None, '[' | '{') => balance += 1,
+            (None, ']' | '}') => balance -= 1,
+            _ => {}
+        }
+    }
+    balance
+}
+
+/// 每条指令只留第一行，`\` 续行折叠成 `...`
+pub(super) fn extract_dockerfile(src: &str) -> String {
+    let mut out = String::with_capacity(src.len() / 2);
+    let mut continuing = false;
+
+    for line in src.lines() {
+        let continues = line.trim_end().ends_with('\\');
+        if continuing {
+            continuing = continues;
+            continue;
+        }
+        out.push_str(line);
+        out.push('\n');
+        if continues && !line.trim_start().starts_with('#') {
+            push_elided(&mut out, 4);
+            continuing = true;
+        }
+    }
+
+    out
+}
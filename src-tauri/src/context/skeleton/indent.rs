@@ -0,0 +1,223 @@
+use super::{indent_width, ELIDED};
+
+/// Python 的逻辑行：括号内换行、反斜杠续行、三引号字符串都算同一行
+struct LogicalLine<'a> {
+    lines: Vec<&'a str>,
+    indent: usize,
+    /// 去掉注释后以 `:` 结尾，即复合语句的头部
+    opens_block: bool,
+}
+
+impl LogicalLine<'_> {
+    fn first(&self) -> &str {
+        self.lines[0].trim_start()
+    }
+
+    fn is_blank(&self) -> bool {
+        self.lines.len() == 1 && self.lines[0].trim().is_empty()
+    }
+
+    fn is_docstring(&self) -> bool {
+        let t = self.first().trim_start_matches(['r', 'R', 'u', 'U', 'b', 'B', 'f', 'F']);
+        t.starts_with('"') || t.starts_with('\'')
+    }
+}
+
+fn logical_lines(src: &str) -> Vec<LogicalLine<'_>> {
+    let mut result = Vec::new();
+    let mut current: Vec<&str> = Vec::new();
+    let mut depth = 0usize;
+    let mut triple: Option<&[u8]> = None;
+    let mut last_code = 0u8;
+
+    for line in src.lines() {
+        current.push(line);
+        let b = line.as_bytes();
+        let mut i = 0;
+        while i < b.len() {
+            if let Some(q) = triple {
+                match b[i..].windows(3).position(|w| w == q) {
+                    Some(p) => {
+                        i += p + 3;
+                        triple = None;
+                        last_code = q[0];
+                    }
+                    None => i = b.len(),
+                }
+                continue;
+            }
+            match b[i] {
+                b'#' => break,
+                q @ (b'"' | b'\'') => {
+                    if b[i..].starts_with(&[q, q, q]) {
+                        triple = Some(if q == b'"' { b"\"\"\"" } else { b"'''" });
+                        i += 3;
+                        continue;
+                    }
+                    i += 1;
+                    while i < b.len() && b[i] != q {
+                        i += if b[i] == b'\\' { 2 } else { 1 };
+                    }
+                    i += 1;
+                    last_code = q;
+                    continue;
+                }
+                b'(' | b'[' | b'{' => depth += 1,
+                b')' | b']' | b'}' => depth = depth.saturating_sub(1),
+                _ => {}
+            }
+            if !b[i].is_ascii_whitespace() {
+                last_code = b[i];
+            }
+            i += 1;
+        }
+
+        let continued = triple.is_some() || depth > 0 || line.trim_end().ends_with('\\');
+        if !continued {
+            result.push(LogicalLine {
+                indent: indent_width(current[0]),
+                lines: std::mem::take(&mut current),
+                opens_block: last_code == b':',
+            });
+            last_code = 0;
+        }
+    }
+    if !current.is_empty() {
+        result.push(LogicalLine { indent: indent_width(current[0]), lines: current, opens_block: false });
+    }
+    result
+}
+
+fn push_lines(out: &mut String, lines: &[&str]) {
+    for line in lines {
+        out.push_str(line);
+        out.push('\n');
+    }
+}
+
+fn push_elided(out: &mut String, indent: usize) {
+    out.push_str(&" ".repeat(indent));
+    out.push_str(ELIDED);
+    out.push('\n');
+}
+
+/// 保留 import、模块级语句、类定义和函数签名（含 docstring），函数体和其他代码块换成 `...`
+pub(super) fn extract_python(src: &str) -> String {
+    let lines = logical_lines(src);
+    let mut out = String::with_capacity(src.len() / 2);
+    let mut skip_deeper_than: Option<usize> = None;
+    // 被跳过的代码块后面是否有空行，结束跳过时补回一个
+    let mut skipped_blank = false;
+    let mut i = 0;
+
+    while i < lines.len() {
+        let line = &lines[i];
+        i += 1;
+
+        if line.is_blank() {
+            if skip_deeper_than.is_none() {
+                out.push('\n');
+            } else {
+                skipped_blank = true;
+            }
+            continue;
+        }
+        if let Some(d) = skip_deeper_than {
+            // 顶格写的注释不代表代码块结束
+            if line.indent > d || line.first().starts_with('#') {
+                skipped_blank = false;
+                continue;
+            }
+            skip_deeper_than = None;
+            if std::mem::take(&mut skipped_blank) {
+                out.push('\n');
+            }
+        }
+
+        let first = line.first();
+        if !line.opens_block || first.starts_with("class ") {
+            // 普通语句；太长的（大字典、长列表）只留第一行
+            if line.lines.len() > 3 {
+                push_lines(&mut out, &line.lines[..1]);
+                push_elided(&mut out, line.indent + 4);
+            } else {
+                push_lines(&mut out, &line.lines);
+            }
+            continue;
+        }
+
+        push_lines(&mut out, &line.lines);
+        let body_idx = (i..lines.len()).find(|&k| !lines[k].is_blank());
+        let body = body_idx.map(|k| &lines[k]).filter(|l| l.indent > line.indent);
+        let body_indent = body.map(|l| l.indent).unwrap_or(line.indent + 4);
+
+        let is_def = first.starts_with("def ") || first.starts_with("async def ");
+        if let (true, Some(k), Some(doc)) = (is_def, body_idx, body) {
+            if doc.is_docstring() {
+                push_lines(&mut out, &doc.lines);
+                i = k + 1;
+            }
+        }
+        push_elided(&mut out, body_indent);
+        skip_deeper_than = Some(line.indent);
+    }
+
+    out
+}
+
+/// 与方法 / 代码块同一缩进、但仍属于它的子句
+const RUBY_CLAUSES: &[&str] = &["rescue", "ensure", "else", "elsif", "when", "in "];
+const RUBY_BLOCKS: &[&str] = &["if ", "unless ", "while ", "until ", "case ", "for ", "begin"];
+
+/// 保留 class / module、常量、mixin 等声明和方法签名，方法体和 `do` 块换成 `...`。
+/// 依赖常规的缩进风格来找 `end`
+pub(super) fn extract_ruby(src: &str) -> String {
+    let mut out = String::with_capacity(src.len() / 2);
+    let mut skip_deeper_than: Option<usize> = None;
+
+    for line in src.lines() {
+        let t = line.trim();
+        if t.is_empty() {
+            if skip_deeper_than.is_none() {
+                out.push('\n');
+            }
+            continue;
+        }
+        let indent = indent_width(line);
+        if let Some(d) = skip_deeper_than {
+            let clause = indent == d && RUBY_CLAUSES.iter().any(|c| t.starts_with(c));
+            if indent > d || clause || t.starts_with('#') {
+                continue;
+            }
+            skip_deeper_than = None;
+        }
+
+        out.push_str(line);
+        out.push('\n');
+
+        let is_def = t.starts_with("def ") && !ruby_one_liner(t);
+        if is_def || opens_ruby_block(t) {
+            push_elided(&mut out, indent + 2);
+            skip_deeper_than = Some(indent);
+        }
+    }
+
+    out
+}
+
+fn ruby_one_liner(t: &str) -> bool {
+    t.ends_with(" end") || t.ends_with(";end") || t.contains(") =") || t.split_whitespace().nth(2) == Some("=")
+}
+
+fn opens_ruby_block(t: &str) -> bool {
+    if RUBY_BLOCKS.iter().any(|k| t.starts_with(k)) && !t.ends_with(" end") {
+        return true;
+    }
+    match t.rfind(" do") {
+        Some(p) => {
+            let rest = t[p + 3..].trim();
+            rest.is_empty() || (rest.starts_with('|') && rest.ends_with('|'))
+        }
+        None => false,
+    }
+}
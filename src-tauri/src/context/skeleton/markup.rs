@@ -0,0 +1,40 @@
+use super::braces::{self, Syntax};
+
+/// HTML / XML / Vue / Svelte：标记本身原样保留，`<script>` 和 `<style>` 的内容按对应语言提取骨架
+pub(super) fn extract(src: &str) -> String {
+    let lower = src.to_ascii_lowercase();
+    let mut out = String::with_capacity(src.len());
+    let mut pos = 0;
+
+    while let Some((open, tag)) = next_embedded(&lower, pos) {
+        let Some(body_start) = lower[open..].find('>').map(|p| open + p + 1) else { break };
+        let attrs = &lower[open..body_start];
+        let syntax = match tag {
+            "script" => &Syntax::JAVASCRIPT,
+            _ if ["scss", "sass", "less"].iter().any(|l| attrs.contains(l)) => &Syntax::SCSS,
+            _ => &Syntax::CSS,
+        };
+        let closing = format!("</{}", tag);
+        let body_end = lower[body_start..].find(&closing).map(|p| body_start + p).unwrap_or(src.len());
+
+        out.push_str(&src[pos..body_start]);
+        let body = &src[body_start..body_end];
+        match braces::extract(body, syntax) {
+            Some(skeleton) => out.push_str(&skeleton),
+            None => out.push_str(body),
+        }
+        pos = body_end;
+    }
+
+    out.push_str(&src[pos..]);
+    out
+}
+
+fn next_embedded(lower: &str, from: usize) -> Option<(usize, &'static str)> {
+    let script = lower[from..].find("<script").map(|p| (from + p, "script"));
+    let style = lower[from..].find("<style").map(|p| (from + p, "style"));
+    match (script, style) {
+        (Some(a), Some(b)) => Some(if a.0 < b.0 { a } else { b }),
+        (a, b) => a.or(b),
+    }
+}
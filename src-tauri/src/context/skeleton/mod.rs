@@ -0,0 +1,73 @@
+// ============================================================================
+// Skeleton / Outline Extraction
+// ============================================================================
+//
+// 只保留代码的“形状”：import、类型声明、函数签名，函数体用 `...` 代替。
+// 与注释剥离一样按语言分别处理，但只需要识别到足以找准代码块边界的程度：
+// 花括号语言数括号，Python / Ruby / YAML 看缩进，SQL 按语句切分。
+// 无法可靠解析时（例如括号不配对）返回 None，由调用方退回完整内容。
+
+mod braces;
+mod config;
+mod indent;
+mod markup;
+mod sql;
+
+use super::language::Language;
+use braces::Syntax;
+
+/// 被省略部分的占位符
+const ELIDED: &str = "...";
+
+pub fn extract(content: &str, lang: Language) -> Option<String> {
+    let skeleton = match lang {
+        Language::Rust => braces::extract(content, &Syntax::RUST),
+        Language::C | Language::Cpp | Language::Java | Language::CSharp
+        | Language::Kotlin | Language::Swift | Language::Dart => braces::extract(content, &Syntax::C),
+        Language::Go => braces::extract(content, &Syntax::GO),
+        Language::JavaScript | Language::TypeScript => braces::extract(content, &Syntax::JAVASCRIPT),
+        Language::Css => braces::extract(content, &Syntax::CSS),
+        Language::Scss | Language::Less => braces::extract(content, &Syntax::SCSS),
+        Language::Shell => braces::extract(content, &Syntax::SHELL),
+        Language::Perl => braces::extract(content, &Syntax::PERL),
+        Language::Python => Some(indent::extract_python(content)),
+        Language::Ruby => Some(indent::extract_ruby(content)),
+        Language::Yaml => Some(config::extract_yaml(content)),
+        Language::Toml => Some(config::extract_toml(content)),
+        Language::Dockerfile => Some(config::extract_dockerfile(content)),
+        Language::Html | Language::Xml | Language::Vue | Language::Svelte => Some(markup::extract(content)),
+        Language::Sql => Some(sql::extract(content)),
+    }?;
+    Some(tidy(&skeleton))
+}
+
+/// 去掉行尾空白，连续空行合并为一行
+fn tidy(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut blank_run = 0;
+    for line in text.lines() {
+        let line = line.trim_end();
+        if line.is_empty() {
+            blank_run += 1;
+            if blank_run > 1 || out.is_empty() {
+                continue;
+            }
+        } else {
+            blank_run = 0;
+        }
+        out.push_str(line);
+        out.push('\n');
+    }
+    while out.ends_with("\n\n") {
+        out.pop();
+    }
+    out
+}
+
+/// 行首缩进宽度（tab 按 4 列计）
+fn indent_width(line: &str) -> usize {
+    line.bytes()
+        .take_while(|&b| b == b' ' || b == b'\t')
+        .map(|b| if b == b'\t' { 4 } else { 1 })
+        .sum()
+}
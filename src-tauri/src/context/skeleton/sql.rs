@@ -0,0 +1,107 @@
+/// 保留建表、视图、索引等 DDL；函数 / 存储过程 / 触发器只留到函数体之前；
+/// 其余语句（INSERT、UPDATE、SELECT……）只统计数量
+pub(super) fn extract(src: &str) -> String {
+    let mut out = String::with_capacity(src.len() / 2);
+    let mut omitted = 0;
+
+    for stmt in statements(src) {
+        let text = skip_leading_comments(stmt);
+        if text.is_empty() {
+            continue;
+        }
+        let upper = text.to_ascii_uppercase();
+        let words: Vec<&str> = upper.split_whitespace().take(6).collect();
+        if !matches!(words.first(), Some(&("CREATE" | "ALTER"))) {
+            omitted += 1;
+            continue;
+        }
+
+        let routine = words.iter().any(|w| matches!(*w, "FUNCTION" | "PROCEDURE" | "TRIGGER"));
+        match body_start(&upper).filter(|_| routine) {
+            Some(p) => {
+                out.push_str(text[..p].trim_end());
+                out.push_str(" ...;\n\n");
+            }
+            None => {
+                out.push_str(text);
+                out.push_str(";\n\n");
+            }
+        }
+    }
+
+    if omitted > 0 {
+        out.push_str(&format!("-- ... {} other statements omitted\n", omitted));
+    }
+    out
+}
+
+fn skip_leading_comments(stmt: &str) -> &str {
+    let mut text = stmt.trim();
+    loop {
+        if text.starts_with("--") {
+            text = text.find('\n').map(|p| text[p..].trim_start()).unwrap_or("");
+        } else if text.starts_with("/*") {
+            text = text.find("*/").map(|p| text[p + 2..].trim_start()).unwrap_or("");
+        } else {
+            return text;
+        }
+    }
+}
+
+/// 按分号切分语句；单引号、双引号和 `$tag$` 内的分号不算
+fn statements(src: &str) -> Vec<&str> {
+    let b = src.as_bytes();
+    let mut result = Vec::new();
+    let mut start = 0;
+    let mut i = 0;
+
+    while i < b.len() {
+        match b[i] {
+            q @ (b'\'' | b'"') => {
+                i += 1;
+                while i < b.len() {
+                    if b[i] == q {
+                        // `''` 是转义的引号
+                        if b.get(i + 1) == Some(&q) {
+                            i += 2;
+                            continue;
+                        }
+                        break;
+                    }
+                    i += 1;
+                }
+            }
+            b'$' => {
+                let tag_len = b[i + 1..].iter().take_while(|c| c.is_ascii_alphanumeric() || **c == b'_').count();
+                if b.get(i + 1 + tag_len) == Some(&b'$') {
+                    let tag = &src[i..i + tag_len + 2];
+                    i = src[i + tag.len()..].find(tag).map(|p| i + tag.len() + p + tag.len() - 1).unwrap_or(b.len());
+                }
+            }
+            b'-' if b.get(i + 1) == Some(&b'-') => {
+                i = src[i..].find('\n').map(|p| i + p).unwrap_or(b.len());
+            }
+            b';' => {
+                result.push(&src[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+    result.push(&src[start.min(src.len())..]);
+    result
+}
+
+/// 函数体开始的位置：第一个独立的 `AS` 或 `BEGIN`
+fn body_start(upper: &str) -> Option<usize> {
+    let b = upper.as_bytes();
+    let is_word_at = |p: usize, len: usize| {
+        let before = p == 0 || (!b[p - 1].is_ascii_alphanumeric() && b[p - 1] != b'_');
+        let after = b.get(p + len).is_none_or(|c| !c.is_ascii_alphanumeric() && *c != b'_');
+        before && after
+    };
+    ["AS", "BEGIN"].iter()
+        .filter_map(|kw| upper.match_indices(kw).map(|(p, _)| p).find(|&p| is_word_at(p, kw.len())))
+        .min()
+}
@@ -0,0 +1,233 @@
+use rayon::prelude::*;
+use serde::Serialize;
+use super::core::{self, FileBody, ProcessOptions};
+use super::format::{ContextHeader, OutputFormat};
+use super::redact::Redactor;
+use super::tokenizer::TokenizerKind;
+
+// ============================================================================
+// Multi-part Context
+// ============================================================================
+//
+// 按 token 上限把上下文拆成多个独立的文档：尽量在文件之间断开，
+// 单个文件放不下时再按行拆开。每部分开头注明“第 i 部分，共 N 部分”，
+// 并提示模型在收到最后一部分之前不要作答。
+
+#[derive(Debug, Serialize)]
+pub struct ContextPart {
+    /// 从 1 开始
+    pub index: usize,
+    pub total: usize,
+    pub content: String,
+    pub tokens: usize,
+    /// 本部分包含的文件，按行拆开的文件会出现在多个部分里
+    pub files: Vec<String>,
+}
+
+/// 放入某一部分的文件块，可能只是一个大文件的若干行
+struct Piece {
+    path: String,
+    block: String,
+    tokens: usize,
+}
+
+/// 实际 token 数超出上限时（分词在拼接处不完全可加），收紧预算重新分配的次数
+const MAX_REPACK_ATTEMPTS: usize = 3;
+
+fn part_note(index: usize, total: usize) -> String {
+    if index < total {
+        format!(
+            "[Part {} of {}] This project context is split into {} parts. \
+             Reply only with \"Received part {} of {}\" and wait for the next part before answering.",
+            index, total, total, index, total
+        )
+    } else {
+        format!(
+            "[Part {} of {}] This is the final part of the project context. \
+             All parts have now been provided; use the complete context to answer.",
+            index, total
+        )
+    }
+}
+
+/// 第 `index` 部分的 header：说明行放在 summary 最前面，原有的说明、目录树和符号地图只出现在第一部分
+fn part_header(header: &ContextHeader, index: usize, total: usize) -> ContextHeader {
+    if total == 1 {
+        return header.clone();
+    }
+    let note = part_note(index, total);
+    if index > 1 {
+        return ContextHeader { summary: note, ..ContextHeader::default() };
+    }
+    let summary = if header.summary.trim().is_empty() { note } else { format!("{}\n\n{}", note, header.summary) };
+    ContextHeader { summary, ..header.clone() }
+}
+
+/// 把上下文拆成若干部分，每部分不超过 `max_tokens`。
+/// 上限连说明行和第一部分的目录树都放不下时返回错误。开启脱敏时先替换密钥再拆分
+pub fn split_context(
+    paths: Vec<String>,
+    header: &ContextHeader,
+    options: ProcessOptions,
+    max_tokens: usize,
+    tokenizer: TokenizerKind,
+    redactor: Option<&mut Redactor>,
+) -> Result<Vec<ContextPart>, String> {
+    let format = options.format;
+    let bodies = core::load_bodies(&paths, &options, redactor);
+    let files: Vec<(String, FileBody, String, usize)> = paths.par_iter()
+        .zip(bodies.into_par_iter())
+        .map(|(path, body)| {
+            let block = format.render_file(path, body.as_str());
+            let tokens = tokenizer.count(&block);
+            (path.clone(), body, block, tokens)
+        })
+        .collect();
+
+    // 外层标签 + 最长的说明行；页码按四位数估算，实际只会更短
+    let frame = [part_note(9998, 9999), part_note(9999, 9999)].iter()
+        .map(|note| tokenizer.count(&format.render_document(&ContextHeader { summary: note.clone(), ..ContextHeader::default() }, &[])))
+        .max()
+        .unwrap_or(0);
+    let first_frame = tokenizer.count(&format.render_document(&part_header(header, 1, 9999), &[]));
+
+    let too_small = || format!("Token limit {} is too small to split this context", max_tokens);
+    if frame.max(first_frame) >= max_tokens {
+        return Err(too_small());
+    }
+
+    let mut cap = max_tokens - frame;
+    let mut first_cap = max_tokens - first_frame;
+    for _ in 0..MAX_REPACK_ATTEMPTS {
+        let pieces: Vec<Piece> = files.par_iter()
+            .flat_map_iter(|(path, body, block, tokens)| match body {
+                FileBody::Text(text) if *tokens > cap => split_lines(path, text, cap, tokenizer, format),
+                _ => vec![Piece { path: path.clone(), block: block.clone(), tokens: *tokens }],
+            })
+            .collect();
+
+        let groups = pack(pieces, first_cap, cap);
+        let total = groups.len();
+        let parts: Vec<ContextPart> = groups.into_par_iter()
+            .enumerate()
+            .map(|(i, group)| {
+                let mut files: Vec<String> = Vec::with_capacity(group.len());
+                let mut blocks = Vec::with_capacity(group.len());
+                for piece in group {
+                    if files.last() != Some(&piece.path) {
+                        files.push(piece.path);
+                    }
+                    blocks.push(piece.block);
+                }
+                let content = format.render_document(&part_header(header, i + 1, total), &blocks);
+                let tokens = tokenizer.count(&content);
+                ContextPart { index: i + 1, total, content, tokens, files }
+            })
+            .collect();
+
+        let overflow = parts.iter().map(|p| p.tokens.saturating_sub(max_tokens)).max().unwrap_or(0);
+        if overflow == 0 {
+            return Ok(parts);
+        }
+        let shrink = overflow.max(cap / 50);
+        if shrink >= cap {
+            break;
+        }
+        cap -= shrink;
+        first_cap = first_cap.saturating_sub(shrink);
+    }
+
+    Err(too_small())
+}
+
+/// 按顺序装入各部分：放得下就放进当前部分，否则另起一部分
+fn pack(pieces: Vec<Piece>, first_cap: usize, cap: usize) -> Vec<Vec<Piece>> {
+    let mut groups: Vec<Vec<Piece>> = Vec::new();
+    let mut current: Vec<Piece> = Vec::new();
+    let mut used = 0;
+
+    for piece in pieces {
+        let limit = if groups.is_empty() { first_cap } else { cap };
+        if used + piece.tokens > limit && (!current.is_empty() || groups.is_empty()) {
+            // 第一部分放不下时，让它只包含说明和目录树
+            groups.push(std::mem::take(&mut current));
+            used = 0;
+        }
+        used += piece.tokens;
+        current.push(piece);
+    }
+    if !current.is_empty() || groups.is_empty() {
+        groups.push(current);
+    }
+    groups
+}
+
+/// 把放不下一个部分的文件按行拆成多个块，每块开头注明行号范围
+fn split_lines(path: &str, text: &str, cap: usize, tokenizer: TokenizerKind, format: OutputFormat) -> Vec<Piece> {
+    let lines: Vec<&str> = text.lines().collect();
+    let label = |start: usize, end: usize| format!("[Lines {}-{} of {}]\n", start, end, lines.len());
+    let overhead = tokenizer.count(&format.render_file(path, &label(lines.len(), lines.len())));
+    let budget = cap.saturating_sub(overhead).max(1);
+
+    let mut pieces = Vec::new();
+    let mut chunk = String::new();
+    let mut chunk_tokens = 0;
+    let mut start = 1;
+    let mut flush = |chunk: &mut String, start: usize, end: usize| {
+        let block = format.render_file(path, &(label(start, end) + chunk.as_str()));
+        let tokens = tokenizer.count(&block);
+        pieces.push(Piece { path: path.to_string(), block, tokens });
+        chunk.clear();
+    };
+
+    for (i, line) in lines.iter().enumerate() {
+        let line_no = i + 1;
+        let line_tokens = tokenizer.count(line) + 1;
+        if chunk_tokens + line_tokens > budget && !chunk.is_empty() {
+            flush(&mut chunk, start, line_no - 1);
+            chunk_tokens = 0;
+        }
+        if chunk.is_empty() {
+            start = line_no;
+        }
+
+        if line_tokens > budget {
+            // 单行就超出（压缩过的代码等）：按字符切开
+            for segment in split_long_line(line, budget, tokenizer) {
+                chunk.push_str(segment);
+                flush(&mut chunk, line_no, line_no);
+            }
+            chunk_tokens = 0;
+            continue;
+        }
+        chunk.push_str(line);
+        chunk.push('\n');
+        chunk_tokens += line_tokens;
+    }
+    if !chunk.is_empty() {
+        flush(&mut chunk, start, lines.len());
+    }
+    pieces
+}
+
+fn split_long_line(line: &str, budget: usize, tokenizer: TokenizerKind) -> Vec<&str> {
+    let mut segments = Vec::new();
+    let mut rest = line;
+    while !rest.is_empty() {
+        // 先按每 token 约 3 个字节估计，超出就减半
+        let mut len = (budget * 3).min(rest.len());
+        loop {
+            while !rest.is_char_boundary(len) {
+                len -= 1;
+            }
+            if len <= 1 || tokenizer.count(&rest[..len]) <= budget {
+                break;
+            }
+            len /= 2;
+        }
+        let len = len.max(rest.chars().next().map_or(1, char::len_utf8));
+        segments.push(&rest[..len]);
+        rest = &rest[len..];
+    }
+    segments
+}
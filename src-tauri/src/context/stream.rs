@@ -0,0 +1,81 @@
+use std::io::Write;
+use std::sync::atomic::{AtomicBool, Ordering};
+use rayon::prelude::*;
+use serde::Serialize;
+use super::core::{self, ProcessOptions};
+use super::format::ContextHeader;
+use super::redact::Redactor;
+
+// ============================================================================
+// Streaming Context Writer
+// ============================================================================
+//
+// 文件分批并行处理，每批按原顺序写出后立即释放，内存占用只和批大小有关，
+// 与总输出大小无关。每批结束时汇报进度并检查取消标记。
+
+/// 前端监听的进度事件名
+pub const PROGRESS_EVENT: &str = "context-export-progress";
+pub const CANCELLED: &str = "Export cancelled";
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ExportProgress {
+    pub job_id: String,
+    pub done: usize,
+    pub total: usize,
+    /// 已写出的字节数
+    pub bytes: u64,
+}
+
+/// 每批并行处理的文件数
+fn batch_size() -> usize {
+    rayon::current_num_threads().max(1) * 4
+}
+
+/// 把上下文按顺序写入 `out`，输出与 `assemble_context_parallel` 完全一致。
+/// 传入 `redactor` 时先对每批文件做密钥脱敏。
+/// `on_progress(done, bytes)` 在每批写完后调用；`cancel` 被置位时返回 `CANCELLED` 错误
+pub fn write_context<W: Write>(
+    out: &mut W,
+    paths: &[String],
+    header: &ContextHeader,
+    options: &ProcessOptions,
+    cancel: &AtomicBool,
+    mut redactor: Option<&mut Redactor>,
+    mut on_progress: impl FnMut(usize, u64),
+) -> Result<u64, String> {
+    let format = options.format;
+    let mut written = 0u64;
+
+    emit(out, &format.document_head(header), &mut written)?;
+    on_progress(0, 0);
+
+    let mut done = 0;
+    for batch in paths.chunks(batch_size()) {
+        if cancel.load(Ordering::Relaxed) {
+            return Err(CANCELLED.to_string());
+        }
+        let blocks: Vec<String> = match redactor.as_deref_mut() {
+            Some(redactor) => core::load_redacted(batch, options, redactor),
+            None => batch.par_iter()
+                .map(|path| core::read_and_process_file(path, options))
+                .collect(),
+        };
+        for block in blocks {
+            emit(out, format.block_prefix(done), &mut written)?;
+            emit(out, &block, &mut written)?;
+            emit(out, format.block_suffix(), &mut written)?;
+            done += 1;
+        }
+        on_progress(done, written);
+    }
+
+    emit(out, format.document_tail(paths.len()), &mut written)?;
+    out.flush().map_err(|e| format!("Failed to write context: {}", e))?;
+    Ok(written)
+}
+
+fn emit<W: Write>(out: &mut W, text: &str, written: &mut u64) -> Result<(), String> {
+    out.write_all(text.as_bytes()).map_err(|e| format!("Failed to write context: {}", e))?;
+    *written += text.len() as u64;
+    Ok(())
+}
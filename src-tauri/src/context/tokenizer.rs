@@ -1,12 +1,64 @@
 use tiktoken_rs::CoreBPE;
 use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
 
-static BPE: Lazy<CoreBPE> = Lazy::new(|| {
+static CL100K: Lazy<CoreBPE> = Lazy::new(|| {
     tiktoken_rs::cl100k_base().expect("Failed to load cl100k_base tokenizer")
 });
 
-pub fn count_tokens(text: &str) -> usize {
-    BPE.encode_ordinary(text).len()
+static O200K: Lazy<CoreBPE> = Lazy::new(|| {
+    tiktoken_rs::o200k_base().expect("Failed to load o200k_base tokenizer")
+});
+
+// Claude / Gemini 没有可离线使用的分词器（只能调用各自的 count_tokens / countTokens 接口），
+// 这里用 cl100k 的结果乘以一个粗略系数估算，结果只是估计值，`is_exact` 为 false，界面上标为「≈」。
+// 系数不是从官方文档得来的：Claude 3 及以后的分词器在英文和源码上切出的 token 普遍比 cl100k 多一成多，
+// Gemini 的 SentencePiece 词表（约 256k）与 cl100k 接近；两者都取偏高的值，宁可高估也不要撑爆窗口。
+// 中文、压缩代码等文本上误差可能超过 ±20%，需要精确值时以接口返回为准，并据此调整系数。
+const CLAUDE_RATIO: f64 = 1.16;
+const GEMINI_RATIO: f64 = 1.04;
+
+/// 按模型家族区分的分词器
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TokenizerKind {
+    /// GPT-4 / GPT-3.5，也是 DeepSeek、Qwen 等未知模型的默认值
+    #[default]
+    Cl100k,
+    /// GPT-4o、o 系列、GPT-5
+    O200k,
+    Claude,
+    Gemini,
+}
+
+impl TokenizerKind {
+    pub fn count(&self, text: &str) -> usize {
+        match self {
+            Self::Cl100k => CL100K.encode_ordinary(text).len(),
+            Self::O200k => O200K.encode_ordinary(text).len(),
+            Self::Claude => scale(CL100K.encode_ordinary(text).len(), CLAUDE_RATIO),
+            Self::Gemini => scale(CL100K.encode_ordinary(text).len(), GEMINI_RATIO),
+        }
+    }
+
+    /// 与 serde 序列化一致的名称，用作缓存键
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Cl100k => "cl100k",
+            Self::O200k => "o200k",
+            Self::Claude => "claude",
+            Self::Gemini => "gemini",
+        }
+    }
+
+    /// 是否是精确计数（而不是估算）
+    pub fn is_exact(&self) -> bool {
+        matches!(self, Self::Cl100k | Self::O200k)
+    }
+}
+
+fn scale(tokens: usize, ratio: f64) -> usize {
+    (tokens as f64 * ratio).ceil() as usize
 }
 
 #[allow(dead_code)]
@@ -0,0 +1,134 @@
+use std::cmp::Reverse;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use ignore::WalkBuilder;
+use rayon::prelude::*;
+use serde::Serialize;
+use crate::db::ProjectConfig;
+use super::core;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum NodeKind {
+    File,
+    Dir,
+}
+
+/// 与前端 `FileNode` 对应的目录树节点（选中/展开等 UI 状态由前端补充）
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WalkNode {
+    pub name: String,
+    pub path: String,
+    pub kind: NodeKind,
+    pub size: u64,
+    pub is_binary: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub children: Option<Vec<WalkNode>>,
+}
+
+struct WalkEntry {
+    path: PathBuf,
+    depth: usize,
+    is_dir: bool,
+    size: u64,
+}
+
+/// 遍历项目目录，返回排好序的目录树（目录在前，同类按名称排序）。
+///
+/// - `config` 中的 `dirs` / `files` / `extensions` 都是忽略列表，按名称匹配
+/// - 遵守 `.gitignore`、`.ignore`、`.git/info/exclude` 和全局 gitignore（不要求必须是 git 仓库）
+/// - 会跟随符号链接，但跳过链接成环的目录
+pub fn walk_project(root: &str, config: &ProjectConfig) -> Result<Vec<WalkNode>, String> {
+    let root = PathBuf::from(root);
+    if !root.is_dir() {
+        return Err(format!("Not a directory: {}", root.display()));
+    }
+
+    let walker = walk_builder(&root, config).build();
+
+    let mut entries = Vec::new();
+    // 权限不足、符号链接成环等错误直接跳过该条目
+    for result in walker.flatten() {
+        if result.depth() == 0 {
+            continue;
+        }
+        let is_dir = result.file_type().is_some_and(|t| t.is_dir());
+        let size = if is_dir { 0 } else { result.metadata().map(|m| m.len()).unwrap_or(0) };
+        entries.push(WalkEntry {
+            depth: result.depth(),
+            path: result.into_path(),
+            is_dir,
+            size,
+        });
+    }
+
+    let binary_flags: Vec<bool> = entries.par_iter()
+        .map(|e| !e.is_dir && core::sniff_binary(&e.path))
+        .collect();
+
+    // 先处理最深的条目，保证构建目录节点时子节点已经就绪
+    let mut order: Vec<usize> = (0..entries.len()).collect();
+    order.sort_by_key(|&i| Reverse(entries[i].depth));
+
+    let mut children: HashMap<PathBuf, Vec<WalkNode>> = HashMap::new();
+    for i in order {
+        let entry = &entries[i];
+        let node = WalkNode {
+            name: entry.path.file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_default(),
+            path: entry.path.to_string_lossy().into_owned(),
+            kind: if entry.is_dir { NodeKind::Dir } else { NodeKind::File },
+            size: entry.size,
+            is_binary: binary_flags[i],
+            children: entry.is_dir.then(|| sorted(children.remove(&entry.path).unwrap_or_default())),
+        };
+        if let Some(parent) = entry.path.parent() {
+            children.entry(parent.to_path_buf()).or_default().push(node);
+        }
+    }
+
+    Ok(sorted(children.remove(&root).unwrap_or_default()))
+}
+
+/// 与 `walk_project` 相同的遍历规则（gitignore、忽略列表、跳过 `.git`），供其他需要遍历项目的地方复用
+pub fn walk_builder(root: &Path, config: &ProjectConfig) -> WalkBuilder {
+    let ignore_dirs = config.dirs.clone();
+    let ignore_files = config.files.clone();
+    let ignore_exts: Vec<String> = config.extensions.iter()
+        .map(|e| e.trim_start_matches('.').to_lowercase())
+        .collect();
+
+    let mut builder = WalkBuilder::new(root);
+    builder
+        .hidden(false)
+        .follow_links(true)
+        .parents(true)
+        .ignore(true)
+        .git_ignore(true)
+        .git_global(true)
+        .git_exclude(true)
+        .require_git(false)
+        .filter_entry(move |entry| {
+            let name = entry.file_name().to_string_lossy();
+            let is_dir = entry.file_type().is_some_and(|t| t.is_dir());
+            if is_dir {
+                return name != ".git" && !ignore_dirs.iter().any(|d| *d == name);
+            }
+            if ignore_files.iter().any(|f| *f == name) {
+                return false;
+            }
+            match Path::new(name.as_ref()).extension() {
+                Some(ext) => !ignore_exts.contains(&ext.to_string_lossy().to_lowercase()),
+                None => true,
+            }
+        });
+    builder
+}
+
+fn sorted(mut nodes: Vec<WalkNode>) -> Vec<WalkNode> {
+    nodes.sort_by(|a, b| {
+        let dir_first = (a.kind != NodeKind::Dir).cmp(&(b.kind != NodeKind::Dir));
+        dir_first.then_with(|| a.name.to_lowercase().cmp(&b.name.to_lowercase()))
+    });
+    nodes
+}
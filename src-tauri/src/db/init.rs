@@ -8,6 +8,9 @@ use refinery::embed_migrations;
 // 编译时嵌入 migrations 文件夹中的 SQL 文件
 embed_migrations!("./migrations");
 
+/// 数据库文件名，位于应用的 local data 目录下
+pub const DB_FILE_NAME: &str = "prompts.db";
+
 pub struct DbState {
     pub conn: Mutex<Connection>,
 }
@@ -107,7 +110,7 @@ pub fn init_db(app_handle: &AppHandle) -> Result<Connection, Box<dyn std::error:
     if !app_dir.exists() {
         std::fs::create_dir_all(&app_dir).unwrap();
     }
-    let db_path = app_dir.join("prompts.db");
+    let db_path = app_dir.join(DB_FILE_NAME);
 
     let mut conn = Connection::open(db_path)?;
 
@@ -135,5 +138,12 @@ pub fn init_db(app_handle: &AppHandle) -> Result<Connection, Box<dyn std::error:
         Err(e) => return Err(Box::new(e)),
     }
 
+    // token 缓存只是加速用的，清理失败不影响启动
+    match super::token_cache::prune(&conn) {
+        Ok(0) => {}
+        Ok(n) => println!("[Database] Pruned {} stale token cache entries.", n),
+        Err(e) => eprintln!("[Database] Failed to prune token cache: {}", e),
+    }
+
     Ok(conn)
 }
@@ -11,6 +11,7 @@ pub mod project_config;
 pub mod secrets;
 pub mod apps;
 pub mod shell_history;
+pub mod token_cache;
 
 // Re-export public types
 pub use models::*;
@@ -42,11 +42,34 @@ pub struct ProjectConfig {
     pub extensions: Vec<String>,
 }
 
+/// 白名单条目。非空的条件全部满足时放行，至少要有一个条件
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct IgnoredSecret {
     pub id: String,
-    pub value: String,
+    /// 密钥的精确值
+    #[serde(default)]
+    pub value: Option<String>,
+    /// 匹配密钥值的正则
+    #[serde(default)]
+    pub value_regex: Option<String>,
+    #[serde(default)]
     pub rule_id: Option<String>,
+    /// 相对项目根目录的路径 glob；不含 `/` 时匹配任意目录下的文件名
+    #[serde(default)]
+    pub path_glob: Option<String>,
+    /// 只在该项目中生效
+    #[serde(default)]
+    pub project_root: Option<String>,
+    /// gitleaks 指纹 `file:rule:line`，历史扫描结果为 `commit:file:rule:line`
+    #[serde(default)]
+    pub fingerprint: Option<String>,
+    /// 过期时间（毫秒时间戳），None 表示永久有效
+    #[serde(default)]
+    pub expires_at: Option<i64>,
+    /// 加入白名单时报告该密钥的规则，只用于显示，不限定范围
+    #[serde(default)]
+    pub detected_by: Option<String>,
+    #[serde(default)]
     pub created_at: i64,
 }
 
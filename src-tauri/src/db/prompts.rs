@@ -1,4 +1,4 @@
-use rusqlite::params;
+use rusqlite::{params, Connection, OptionalExtension};
 use tauri::State;
 use uuid::Uuid;
 
@@ -190,6 +190,34 @@ pub fn search_prompts(
     Ok(prompts)
 }
 
+/// 按标题查找（不区分大小写），同名时优先收藏、再取最近更新的一条。供命令行使用
+pub fn find_prompt_by_title(conn: &Connection, title: &str) -> rusqlite::Result<Option<Prompt>> {
+    conn.query_row(
+        "SELECT * FROM prompts WHERE title = ?1 COLLATE NOCASE ORDER BY is_favorite DESC, updated_at DESC LIMIT 1",
+        params![title],
+        |row| {
+            Ok(Prompt {
+                id: row.get("id")?,
+                title: row.get("title")?,
+                content: row.get("content")?,
+                group_name: row.get("group_name")?,
+                description: row.get("description")?,
+                tags: row.get::<_, Option<String>>("tags")?.map(|s| serde_json::from_str(&s).unwrap_or_default()),
+                is_favorite: row.get("is_favorite")?,
+                created_at: row.get("created_at")?,
+                updated_at: row.get("updated_at")?,
+                source: row.get("source")?,
+                pack_id: row.get("pack_id")?,
+                original_id: row.get("original_id")?,
+                type_: row.get("type")?,
+                is_executable: row.get("is_executable").unwrap_or(Some(false)),
+                shell_type: row.get("shell_type").unwrap_or(None),
+                use_as_chat_template: row.get("use_as_chat_template").unwrap_or(Some(false)),
+            })
+        },
+    ).optional()
+}
+
 #[tauri::command]
 pub fn save_prompt(
     state: State<DbState>,
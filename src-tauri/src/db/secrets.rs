@@ -1,33 +1,83 @@
-use rusqlite::{params, Connection};
+use rusqlite::{params, Connection, Row};
 use tauri::State;
 use uuid::Uuid;
 
 use super::init::DbState;
 use super::models::IgnoredSecret;
+use crate::gitleaks;
 
 // ============================================================================
 // Ignored Secrets Commands
 // ============================================================================
 
+const SELECT_COLUMNS: &str =
+    "SELECT id, value, value_regex, rule_id, path_glob, project_root, fingerprint, expires_at, detected_by, created_at FROM ignored_secrets";
+
+fn row_to_secret(row: &Row) -> rusqlite::Result<IgnoredSecret> {
+    Ok(IgnoredSecret {
+        id: row.get(0)?,
+        value: row.get(1)?,
+        value_regex: row.get(2)?,
+        rule_id: row.get(3)?,
+        path_glob: row.get(4)?,
+        project_root: row.get(5)?,
+        fingerprint: row.get(6)?,
+        expires_at: row.get(7)?,
+        detected_by: row.get(8)?,
+        created_at: row.get::<_, Option<i64>>(9)?.unwrap_or(0),
+    })
+}
+
+/// 空字符串按未设置处理
+fn non_empty(field: Option<String>) -> Option<String> {
+    field.map(|s| s.trim().to_string()).filter(|s| !s.is_empty())
+}
+
 #[tauri::command]
 pub fn add_ignored_secrets(
     state: State<DbState>,
     secrets: Vec<IgnoredSecret>,
 ) -> Result<usize, String> {
+    for s in &secrets {
+        gitleaks::ignore::validate(s)?;
+    }
+
     let mut conn = state.conn.lock().map_err(|e| e.to_string())?;
     let tx = conn.transaction().map_err(|e| e.to_string())?;
     let mut count = 0;
 
     {
+        // 相同范围的条目已存在时只更新过期时间（唯一索引不包含 expires_at）；id 冲突时忽略
         let mut stmt = tx.prepare(
-            "INSERT OR IGNORE INTO ignored_secrets (id, value, rule_id, created_at) VALUES (?, ?, ?, ?)"
+            "INSERT INTO ignored_secrets (id, value, value_regex, rule_id, path_glob, project_root, fingerprint, expires_at, detected_by, created_at)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+             ON CONFLICT (
+                 COALESCE(value, ''), COALESCE(value_regex, ''), COALESCE(rule_id, ''),
+                 COALESCE(path_glob, ''), COALESCE(project_root, ''), COALESCE(fingerprint, '')
+             ) DO UPDATE SET expires_at = excluded.expires_at WHERE expires_at IS NOT excluded.expires_at
+             ON CONFLICT DO NOTHING"
         ).map_err(|e| e.to_string())?;
 
         for s in secrets {
             let id = if s.id.is_empty() { Uuid::new_v4().to_string() } else { s.id };
             let now = chrono::Utc::now().timestamp_millis();
-            stmt.execute(params![id, s.value, s.rule_id, now]).map_err(|e| e.to_string())?;
-            count += 1;
+            // 值需要原样保存（首尾空白也是密钥的一部分）
+            let value = s.value.filter(|v| !v.is_empty());
+            let changed = stmt.execute(params![
+                id,
+                value,
+                non_empty(s.value_regex),
+                non_empty(s.rule_id),
+                non_empty(s.path_glob),
+                non_empty(s.project_root),
+                non_empty(s.fingerprint),
+                s.expires_at,
+                non_empty(s.detected_by),
+                now
+            ]).map_err(|e| e.to_string())?;
+            if changed > 0 {
+                count += 1;
+            }
         }
     }
 
@@ -38,16 +88,9 @@ pub fn add_ignored_secrets(
 #[tauri::command]
 pub fn get_ignored_secrets(state: State<DbState>) -> Result<Vec<IgnoredSecret>, String> {
     let conn = state.conn.lock().map_err(|e| e.to_string())?;
-    let mut stmt = conn.prepare("SELECT id, value, rule_id, created_at FROM ignored_secrets ORDER BY created_at DESC").map_err(|e| e.to_string())?;
+    let mut stmt = conn.prepare(&format!("{} ORDER BY created_at DESC", SELECT_COLUMNS)).map_err(|e| e.to_string())?;
 
-    let rows = stmt.query_map([], |row| {
-        Ok(IgnoredSecret {
-            id: row.get(0)?,
-            value: row.get(1)?,
-            rule_id: row.get(2)?,
-            created_at: row.get(3)?,
-        })
-    }).map_err(|e| e.to_string())?;
+    let rows = stmt.query_map([], row_to_secret).map_err(|e| e.to_string())?;
 
     let mut results = Vec::new();
     for r in rows {
@@ -63,16 +106,10 @@ pub fn delete_ignored_secret(state: State<DbState>, id: String) -> Result<(), St
     Ok(())
 }
 
-// 内部帮助函数：获取所有白名单值（用于扫描过滤）
-pub fn get_all_ignored_values_internal(conn: &Connection) -> Result<std::collections::HashSet<String>, rusqlite::Error> {
-    let mut stmt = conn.prepare("SELECT value FROM ignored_secrets")?;
-    let rows = stmt.query_map([], |row| row.get::<_, String>(0))?;
-
-    let mut set = std::collections::HashSet::new();
-    for r in rows {
-        if let Ok(val) = r {
-            set.insert(val);
-        }
-    }
-    Ok(set)
+// 内部帮助函数：获取所有未过期的白名单条目（用于扫描过滤，见 gitleaks::ignore::IgnoreSet）
+pub fn get_active_ignored_internal(conn: &Connection) -> Result<Vec<IgnoredSecret>, rusqlite::Error> {
+    let now = chrono::Utc::now().timestamp_millis();
+    let mut stmt = conn.prepare(&format!("{} WHERE expires_at IS NULL OR expires_at > ?", SELECT_COLUMNS))?;
+    let rows = stmt.query_map(params![now], row_to_secret)?;
+    rows.collect()
 }
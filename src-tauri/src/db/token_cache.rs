@@ -0,0 +1,140 @@
+use std::collections::HashMap;
+use rusqlite::{params, Connection};
+
+// ============================================================================
+// Token Count Cache
+// ============================================================================
+
+/// 超过 30 天没用到的缓存会被清理
+const MAX_IDLE_MS: i64 = 30 * 24 * 60 * 60 * 1000;
+/// 缓存条目上限，超出时按最近使用时间淘汰
+const MAX_ENTRIES: i64 = 200_000;
+
+/// 单个文件的 token 统计。`mtime` / `size` 用于快速判断，`hash` 用于 mtime 变了但内容没变的情况
+#[derive(Debug, Clone)]
+pub struct TokenCacheEntry {
+    pub path: String,
+    pub mtime: i64,
+    pub size: i64,
+    pub hash: String,
+    pub tokens: usize,
+    pub bytes: usize,
+    pub lines: usize,
+    /// `FileStatus` 的字符串形式
+    pub status: String,
+    pub encoding: Option<String>,
+}
+
+/// 缓存键中除路径以外的部分：同一个文件在不同分词器、输出格式、注释处理方式下结果不同
+#[derive(Debug, Clone, Copy)]
+pub struct TokenCacheScope<'a> {
+    pub tokenizer: &'a str,
+    pub format: &'a str,
+    pub remove_comments: bool,
+    pub keep_doc_comments: bool,
+}
+
+impl TokenCacheScope<'_> {
+    /// 不删注释时 keep_doc_comments 没有意义，统一存 false 以免重复缓存
+    fn flags(&self) -> (bool, bool) {
+        (self.remove_comments, self.remove_comments && self.keep_doc_comments)
+    }
+}
+
+pub fn load_entries(
+    conn: &Connection,
+    scope: TokenCacheScope,
+    paths: &[String],
+) -> rusqlite::Result<HashMap<String, TokenCacheEntry>> {
+    let (remove_comments, keep_doc_comments) = scope.flags();
+    let mut stmt = conn.prepare_cached(
+        "SELECT mtime, size, hash, tokens, bytes, lines, status, encoding FROM token_cache
+         WHERE path = ?1 AND tokenizer = ?2 AND format = ?3 AND remove_comments = ?4 AND keep_doc_comments = ?5"
+    )?;
+
+    let mut entries = HashMap::new();
+    for path in paths {
+        let mut rows = stmt.query(params![path, scope.tokenizer, scope.format, remove_comments, keep_doc_comments])?;
+        if let Some(row) = rows.next()? {
+            entries.insert(path.clone(), TokenCacheEntry {
+                path: path.clone(),
+                mtime: row.get(0)?,
+                size: row.get(1)?,
+                hash: row.get(2)?,
+                tokens: row.get::<_, i64>(3)? as usize,
+                bytes: row.get::<_, i64>(4)? as usize,
+                lines: row.get::<_, i64>(5)? as usize,
+                status: row.get(6)?,
+                encoding: row.get(7)?,
+            });
+        }
+    }
+    Ok(entries)
+}
+
+/// 写回一次统计的结果：
+/// - `updated`：新算出的（或 mtime 变了但 hash 相同的）条目
+/// - `hits`：直接命中的路径，只刷新最近使用时间
+/// - `missing`：已经不存在的文件，删掉它在所有作用域下的缓存
+pub fn store_entries(
+    conn: &mut Connection,
+    scope: TokenCacheScope,
+    updated: &[TokenCacheEntry],
+    hits: &[String],
+    missing: &[String],
+) -> rusqlite::Result<()> {
+    let (remove_comments, keep_doc_comments) = scope.flags();
+    let now = chrono::Utc::now().timestamp_millis();
+    let tx = conn.transaction()?;
+
+    {
+        let mut upsert = tx.prepare(
+            "INSERT INTO token_cache (path, tokenizer, format, remove_comments, keep_doc_comments, mtime, size, hash, tokens, bytes, lines, status, encoding, last_used)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14)
+             ON CONFLICT(path, tokenizer, format, remove_comments, keep_doc_comments) DO UPDATE SET
+               mtime = excluded.mtime,
+               size = excluded.size,
+               hash = excluded.hash,
+               tokens = excluded.tokens,
+               bytes = excluded.bytes,
+               lines = excluded.lines,
+               status = excluded.status,
+               encoding = excluded.encoding,
+               last_used = excluded.last_used"
+        )?;
+        for e in updated {
+            upsert.execute(params![
+                e.path, scope.tokenizer, scope.format, remove_comments, keep_doc_comments,
+                e.mtime, e.size, e.hash, e.tokens as i64, e.bytes as i64, e.lines as i64, e.status, e.encoding, now
+            ])?;
+        }
+
+        let mut touch = tx.prepare(
+            "UPDATE token_cache SET last_used = ?1
+             WHERE path = ?2 AND tokenizer = ?3 AND format = ?4 AND remove_comments = ?5 AND keep_doc_comments = ?6"
+        )?;
+        for path in hits {
+            touch.execute(params![now, path, scope.tokenizer, scope.format, remove_comments, keep_doc_comments])?;
+        }
+
+        let mut delete = tx.prepare("DELETE FROM token_cache WHERE path = ?1")?;
+        for path in missing {
+            delete.execute(params![path])?;
+        }
+    }
+
+    tx.commit()
+}
+
+/// 清理长期未使用的条目，并把总数控制在上限以内。返回删除的条目数
+pub fn prune(conn: &Connection) -> rusqlite::Result<usize> {
+    let cutoff = chrono::Utc::now().timestamp_millis() - MAX_IDLE_MS;
+    let mut removed = conn.execute("DELETE FROM token_cache WHERE last_used < ?1", params![cutoff])?;
+    removed += conn.execute(
+        "DELETE FROM token_cache WHERE rowid IN (
+            SELECT rowid FROM token_cache ORDER BY last_used DESC LIMIT -1 OFFSET ?1
+         )",
+        params![MAX_ENTRIES],
+    )?;
+    Ok(removed)
+}
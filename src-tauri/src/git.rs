@@ -1,8 +1,10 @@
 use chrono::{DateTime, Local};
-use git2::{Delta, DiffFormat, DiffOptions, Oid, Repository};
+use git2::{Delta, Diff, DiffFindOptions, DiffFormat, DiffOptions, Oid, Patch, Repository};
 use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
 use std::path::Path;
+use crate::charset;
+use crate::notebook::{self, Notebook, NotebookOptions};
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct GitCommit {
@@ -21,8 +23,35 @@ pub struct GitDiffFile {
     pub modified_content: String,
     pub is_binary: bool,
     pub is_large: bool,
+    /// 两侧文本检测到的编码（如 "UTF-8"、"GBK"），内容已统一转成 UTF-8；不存在、二进制或过大时为 None
+    pub original_encoding: Option<String>,
+    pub modified_encoding: Option<String>,
 }
 
+/// 相对基准提交发生变化的文件，包括未提交的修改和未跟踪的新文件
+#[derive(Serialize, Clone, Debug)]
+pub struct ChangedFile {
+    /// 相对仓库根目录的路径
+    pub path: String,
+    pub status: String,
+    pub old_path: Option<String>,
+    /// unified diff，只在需要时生成；二进制文件为 None
+    #[serde(skip_serializing)]
+    pub patch: Option<String>,
+}
+
+/// diff 中一段连续的新增行（不含上下文），用于密钥扫描
+#[derive(Debug, Clone)]
+pub struct AddedHunk {
+    pub path: String,
+    /// 第一行在新文件中的行号（从 1 开始）
+    pub start_line: usize,
+    pub text: String,
+}
+
+/// 未指定基准时依次尝试的默认分支
+const DEFAULT_BASE_BRANCHES: &[&str] = &["main", "master", "origin/main", "origin/master"];
+
 struct DiffItem {
     path: String,
     status: String,
@@ -74,59 +103,78 @@ pub fn get_git_commits(project_path: String) -> Result<Vec<GitCommit>, String> {
     Ok(commits)
 }
 
-fn read_blob_content(repo: &Repository, id: git2::Oid, max_size: usize) -> (String, bool, bool) {
+/// 返回 (内容, 是否二进制, 是否过大, 检测到的编码)
+type SideContent = (String, bool, bool, Option<String>);
+
+fn read_blob_content(repo: &Repository, id: git2::Oid, max_size: usize) -> SideContent {
     if id.is_zero() {
-        return (String::new(), false, false);
+        return (String::new(), false, false, None);
     }
 
     match repo.find_blob(id) {
         Ok(blob) => {
-            let is_binary = blob.is_binary();
+            // git 自带的判断会把 UTF-16 文本当成二进制
+            let is_binary = charset::is_binary(blob.content());
             let is_large = blob.size() > max_size;
 
-            let content = if is_binary {
-                "[Binary File Omitted]".to_string()
+            if is_binary {
+                ("[Binary File Omitted]".to_string(), true, is_large, None)
             } else if is_large {
-                format!("[File Too Large: {} bytes]", blob.size())
+                (format!("[File Too Large: {} bytes]", blob.size()), false, true, None)
             } else {
-                String::from_utf8_lossy(blob.content()).to_string()
-            };
-
-            (content, is_binary, is_large)
+                let decoded = charset::decode(blob.content());
+                let encoding = decoded.encoding_name().to_string();
+                (decoded.text, false, false, Some(encoding))
+            }
         }
-        Err(_) => (String::new(), false, false),
+        Err(_) => (String::new(), false, false, None),
     }
 }
 
-fn read_file_content(full_path: &Path, max_size: usize) -> (String, bool, bool) {
+fn read_file_content(full_path: &Path, max_size: usize) -> SideContent {
     if let Ok(meta) = std::fs::metadata(full_path) {
         if meta.len() > max_size as u64 {
             return (
                 format!("[File Too Large: {} bytes]", meta.len()),
                 false,
                 true,
+                None,
             );
         }
     }
 
     match std::fs::read(full_path) {
         Ok(bytes) => {
-            let is_binary = bytes.iter().take(8000).any(|&b| b == 0);
-            if is_binary {
-                return ("[Binary File in Workdir]".to_string(), true, false);
+            if charset::is_binary(&bytes) {
+                return ("[Binary File in Workdir]".to_string(), true, false, None);
             }
 
-            let content_cow = String::from_utf8_lossy(&bytes);
-            let content = if content_cow.contains('\r') {
-                content_cow.replace("\r\n", "\n")
+            let decoded = charset::decode(&bytes);
+            let encoding = decoded.encoding_name().to_string();
+            let content = if decoded.text.contains('\r') {
+                decoded.text.replace("\r\n", "\n")
             } else {
-                content_cow.into_owned()
+                decoded.text
             };
 
-            (content, false, false)
+            (content, false, false, Some(encoding))
         }
-        Err(_) => ("Error reading file from disk".to_string(), false, false),
+        Err(_) => ("Error reading file from disk".to_string(), false, false, None),
+    }
+}
+
+/// 转成逐 cell 的文本；不是合法 notebook（空内容、二进制 / 过大的说明等）时保持原样。
+/// 原始文件按 notebook 的上限读取，大小限制作用在提取出的文本上
+fn notebook_view(side: SideContent, max_size: usize) -> SideContent {
+    let (content, binary, large, encoding) = side;
+    let text = match Notebook::parse(&content) {
+        Ok(nb) => nb.render(&NotebookOptions::default(), str::to_string),
+        Err(_) => content,
+    };
+    if !binary && !large && text.len() > max_size {
+        return (format!("[File Too Large: {} bytes]", text.len()), false, true, None);
     }
+    (text, binary, large, encoding)
 }
 
 #[tauri::command]
@@ -193,28 +241,39 @@ pub fn get_git_diff(
         .into_par_iter()
         .map(|item| {
             let local_repo = Repository::open(&project_path).ok();
+            let is_notebook = notebook::is_notebook(Path::new(&item.path));
+            let read_limit = if is_notebook { notebook::MAX_NOTEBOOK_SOURCE_SIZE as usize } else { MAX_SIZE };
 
-            let (original_content, old_binary, old_large) = if let Some(r) = &local_repo {
-                read_blob_content(r, item.old_oid, MAX_SIZE)
+            let original = if let Some(r) = &local_repo {
+                read_blob_content(r, item.old_oid, read_limit)
             } else {
-                (String::new(), false, false)
+                (String::new(), false, false, None)
             };
 
-            let (modified_content, new_binary, new_large) = if is_workdir_mode {
+            let modified = if is_workdir_mode {
                 if item.delta_status == Delta::Deleted {
-                    (String::new(), false, false)
+                    (String::new(), false, false, None)
                 } else {
                     let full_path = Path::new(&project_path).join(&item.path);
-                    read_file_content(&full_path, MAX_SIZE)
+                    read_file_content(&full_path, read_limit)
                 }
             } else {
                 if let Some(r) = &local_repo {
-                    read_blob_content(r, item.new_oid, MAX_SIZE)
+                    read_blob_content(r, item.new_oid, read_limit)
                 } else {
-                    (String::new(), false, false)
+                    (String::new(), false, false, None)
                 }
             };
 
+            // Notebook 按 cell 比较，忽略输出和 JSON 格式上的变化
+            let (original, modified) = if is_notebook {
+                (notebook_view(original, MAX_SIZE), notebook_view(modified, MAX_SIZE))
+            } else {
+                (original, modified)
+            };
+            let (original_content, old_binary, old_large, original_encoding) = original;
+            let (modified_content, new_binary, new_large, modified_encoding) = modified;
+
             GitDiffFile {
                 path: item.path,
                 status: item.status,
@@ -223,6 +282,8 @@ pub fn get_git_diff(
                 modified_content,
                 is_binary: old_binary || new_binary,
                 is_large: old_large || new_large,
+                original_encoding,
+                modified_encoding,
             }
         })
         .collect();
@@ -272,3 +333,137 @@ pub fn get_git_diff_text(
 
     Ok(String::from_utf8_lossy(&diff_buf).to_string())
 }
+
+/// 解析基准提交。`base_ref` 可以是提交哈希、分支、标签等任意 revspec，未指定时使用默认分支；
+/// `merge_base` 为 true 时取它与 HEAD 的分叉点，即“这个分支上改了什么”
+pub fn resolve_base_commit(repo: &Repository, base_ref: Option<&str>, merge_base: bool) -> Result<Oid, String> {
+    let base = match base_ref.map(str::trim).filter(|r| !r.is_empty()) {
+        Some(spec) => repo
+            .revparse_single(spec)
+            .and_then(|obj| obj.peel_to_commit())
+            .map_err(|e| format!("Invalid base ref '{}': {}", spec, e))?
+            .id(),
+        None => DEFAULT_BASE_BRANCHES
+            .iter()
+            .find_map(|b| repo.revparse_single(b).and_then(|obj| obj.peel_to_commit()).ok())
+            .ok_or_else(|| "No base ref given and no main/master branch found".to_string())?
+            .id(),
+    };
+
+    if !merge_base {
+        return Ok(base);
+    }
+    let head = repo
+        .head()
+        .and_then(|h| h.peel_to_commit())
+        .map_err(|e| format!("Failed to resolve HEAD: {}", e))?;
+    repo.merge_base(head.id(), base)
+        .map_err(|e| format!("No merge base between HEAD and {}: {}", base, e))
+}
+
+/// 基准提交到工作区（含暂存区和未跟踪文件）之间变化的文件，识别重命名。
+/// `include_patch` 为 true 时附带每个文件的 unified diff
+pub fn changed_files_since(repo: &Repository, base: Oid, include_patch: bool) -> Result<Vec<ChangedFile>, String> {
+    let base_tree = repo
+        .find_commit(base)
+        .and_then(|c| c.tree())
+        .map_err(|e| e.to_string())?;
+
+    let mut diff_opts = DiffOptions::new();
+    diff_opts.include_untracked(true).recurse_untracked_dirs(true);
+    let mut diff = repo
+        .diff_tree_to_workdir_with_index(Some(&base_tree), Some(&mut diff_opts))
+        .map_err(|e| format!("Workdir diff failed: {}", e))?;
+
+    let mut find_opts = DiffFindOptions::new();
+    find_opts.renames(true).for_untracked(true);
+    diff.find_similar(Some(&mut find_opts))
+        .map_err(|e| format!("Rename detection failed: {}", e))?;
+
+    let mut files = Vec::new();
+    for (idx, delta) in diff.deltas().enumerate() {
+        let status = match delta.status() {
+            Delta::Added | Delta::Untracked | Delta::Copied => "Added",
+            Delta::Deleted => "Deleted",
+            Delta::Renamed => "Renamed",
+            Delta::Modified | Delta::Typechange => "Modified",
+            _ => continue,
+        };
+        let Some(path) = delta.new_file().path().or(delta.old_file().path()) else { continue };
+
+        let patch = if include_patch && !delta.flags().is_binary() {
+            Patch::from_diff(&diff, idx)
+                .ok()
+                .flatten()
+                .and_then(|mut p| p.to_buf().ok())
+                .map(|buf| charset::decode(&buf).text)
+        } else {
+            None
+        };
+
+        files.push(ChangedFile {
+            path: path.to_string_lossy().to_string(),
+            status: status.to_string(),
+            old_path: if delta.status() == Delta::Renamed {
+                delta.old_file().path().map(|p| p.to_string_lossy().to_string())
+            } else {
+                None
+            },
+            patch,
+        });
+    }
+
+    Ok(files)
+}
+
+/// 以 0 行上下文生成的 diff 中，每个 hunk 的新增行在新文件里是连续的，
+/// 按行号从新版本的解码结果中截取，编码检测与读取文件时一致（GBK、UTF-16 等不会被当成 UTF-8）。
+/// 跳过二进制文件和新版本超过 `max_size` 字节的文件；diff 需要以 `force_text` 生成，否则 UTF-16 文件没有 hunk
+pub fn added_hunks(repo: &Repository, diff: &Diff, max_size: u64) -> Result<Vec<AddedHunk>, String> {
+    let mut hunks = Vec::new();
+    for idx in 0..diff.deltas().len() {
+        let Some(delta) = diff.get_delta(idx) else { continue };
+        if delta.new_file().size() > max_size {
+            continue;
+        }
+        let Some(path) = delta.new_file().path().map(|p| p.to_string_lossy().into_owned()) else { continue };
+        let Ok(blob) = repo.find_blob(delta.new_file().id()) else { continue };
+        if charset::is_binary(blob.content()) {
+            continue;
+        }
+        let Ok(Some(patch)) = Patch::from_diff(diff, idx) else { continue };
+
+        let decoded = charset::decode(blob.content());
+        let new_lines: Vec<&str> = decoded.text.split_inclusive('\n').collect();
+        for hunk_idx in 0..patch.num_hunks() {
+            let (hunk, _) = patch.hunk(hunk_idx).map_err(|e| e.to_string())?;
+            let start_line = hunk.new_start() as usize;
+            if hunk.new_lines() == 0 || start_line == 0 {
+                continue;
+            }
+            let end = (start_line - 1 + hunk.new_lines() as usize).min(new_lines.len());
+            let Some(lines) = new_lines.get(start_line - 1..end) else { continue };
+            hunks.push(AddedHunk { path: path.clone(), start_line, text: lines.concat() });
+        }
+    }
+    Ok(hunks)
+}
+
+/// 暂存区相对 HEAD 的改动（0 行上下文，识别重命名）；还没有提交时与空树比较
+pub fn staged_diff(repo: &Repository) -> Result<Diff<'_>, String> {
+    let head_tree = match repo.head() {
+        Ok(head) => Some(head.peel_to_tree().map_err(|e| e.to_string())?),
+        Err(_) => None,
+    };
+    let index = repo.index().map_err(|e| e.to_string())?;
+    let mut diff_opts = DiffOptions::new();
+    diff_opts.context_lines(0).force_text(true);
+    let mut diff = repo
+        .diff_tree_to_index(head_tree.as_ref(), Some(&index), Some(&mut diff_opts))
+        .map_err(|e| format!("Index diff failed: {}", e))?;
+    let mut find_opts = DiffFindOptions::new();
+    find_opts.renames(true);
+    diff.find_similar(Some(&mut find_opts))
+        .map_err(|e| format!("Rename detection failed: {}", e))?;
+    Ok(diff)
+}
@@ -0,0 +1,127 @@
+use std::cmp::Ordering;
+use std::str::FromStr;
+use entropy::shannon_entropy;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use super::ruleset::CompiledRule;
+use super::SecretMatch;
+
+// ============================================================================
+// Severity & Confidence
+// ============================================================================
+//
+// 严重程度由规则决定（泄露后的影响），置信度则是规则的基础值再根据上下文调整：
+//   - 同一行出现规则关键字或服务名（如 aws、datadog）时提高，纯字符集规则没有任何上下文时降低
+//   - 熵值远高于规则阈值时提高，刚好越过阈值时降低
+//   - 位于测试 / fixture / 示例目录中的文件降低
+// 得分范围 0-100。
+
+const CONTEXT_BONUS: i32 = 15;
+const NO_CONTEXT_PENALTY: i32 = 10;
+const ENTROPY_BONUS: i32 = 10;
+const ENTROPY_PENALTY: i32 = 10;
+const TEST_PATH_PENALTY: i32 = 25;
+
+/// 规则 id 的第一段如果是这些词，不当作服务名
+const GENERIC_ID_PREFIXES: &[&str] = &["generic", "common", "private", "jwt", "rsa", "ec", "openssh", "age"];
+
+static TEST_PATH_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?i)(^|/)(tests?|__tests__|spec|specs|fixtures?|testdata|test_data|mocks?|__mocks__|examples?|samples?)(/|$)|[._-](test|spec|mock|example|sample)s?\.[a-z0-9]+$|\.(example|sample)$")
+        .unwrap()
+});
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum Severity {
+    Low,
+    Medium,
+    High,
+    Critical,
+}
+
+impl FromStr for Severity {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "low" | "info" => Ok(Self::Low),
+            "medium" => Ok(Self::Medium),
+            "high" => Ok(Self::High),
+            "critical" => Ok(Self::Critical),
+            _ => Err(format!("Unknown severity: {}", s)),
+        }
+    }
+}
+
+/// 规则本身的可信程度：前缀独特的规则为 High，只靠字符集和长度匹配的为 Low
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum Confidence {
+    Low,
+    Medium,
+    High,
+}
+
+impl Confidence {
+    fn base_score(self) -> i32 {
+        match self {
+            Self::Low => 30,
+            Self::Medium => 55,
+            Self::High => 80,
+        }
+    }
+}
+
+impl FromStr for Confidence {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "low" => Ok(Self::Low),
+            "medium" => Ok(Self::Medium),
+            "high" => Ok(Self::High),
+            _ => Err(format!("Unknown confidence: {}", s)),
+        }
+    }
+}
+
+/// 风险从高到低：先比严重程度，再比置信度
+pub fn by_risk(a: &SecretMatch, b: &SecretMatch) -> Ordering {
+    b.risk_level.cmp(&a.risk_level).then_with(|| b.confidence.cmp(&a.confidence))
+}
+
+pub fn is_test_path(path: &str) -> bool {
+    TEST_PATH_REGEX.is_match(&path.replace('\\', "/"))
+}
+
+/// `line` 为密钥所在的整行，`secret_range` 为密钥在 `line` 中的字节范围
+pub fn score(rule: &CompiledRule, secret: &str, line: &str, secret_range: (usize, usize), path: Option<&str>) -> u8 {
+    let mut score = rule.confidence.base_score();
+
+    // 密钥本身不算上下文：前缀型规则的关键字总在密钥里
+    let context = format!("{} {}", &line[..secret_range.0], &line[secret_range.1..]).to_lowercase();
+    let provider = rule.id.split('-').next().filter(|p| p.len() > 2 && !GENERIC_ID_PREFIXES.contains(p));
+    let has_context = rule.keywords.iter()
+        .map(|k| k.to_lowercase())
+        .chain(provider.map(str::to_string))
+        .any(|term| !term.is_empty() && context.contains(&term));
+    if has_context {
+        score += CONTEXT_BONUS;
+    } else if rule.keywords.is_empty() {
+        score -= NO_CONTEXT_PENALTY;
+    }
+
+    if let Some(min_entropy) = rule.entropy {
+        let margin = shannon_entropy(secret) as f64 - min_entropy;
+        if margin >= 1.0 {
+            score += ENTROPY_BONUS;
+        } else if margin < 0.25 {
+            score -= ENTROPY_PENALTY;
+        }
+    }
+
+    if path.is_some_and(is_test_path) {
+        score -= TEST_PATH_PENALTY;
+    }
+
+    score.clamp(0, 100) as u8
+}
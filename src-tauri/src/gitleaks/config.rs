@@ -0,0 +1,289 @@
+use std::fs;
+use std::path::Path;
+use regex::Regex;
+use serde::Deserialize;
+use super::ruleset::{Allowlist, CompiledRule, RegexTarget, RuleError, RuleSet};
+use super::{Confidence, Severity};
+
+// ============================================================================
+// gitleaks TOML Config
+// ============================================================================
+//
+// 读取与上游 gitleaks 兼容的配置文件：
+//
+//   [extend]
+//   useDefault = true          # 在下层规则的基础上扩展，否则整体替换下层规则（仓库配置除外，见下）
+//   path = "base.toml"         # 或者扩展另一个配置文件（相对于当前文件）
+//   disabledRules = ["..."]
+//
+//   [[rules]]
+//   id = "acme-token"
+//   regex = '''acme_[a-z0-9]{32}'''
+//   secretGroup = 1
+//   entropy = 3.5
+//   keywords = ["acme_"]
+//   path = '''\.env$'''
+//   severity = "critical"      # CtxRun 扩展：low / medium / high / critical，默认 high
+//   confidence = "high"        # CtxRun 扩展：low / medium / high，默认 medium
+//   [[rules.allowlists]]       # 旧版写法 [rules.allowlist] 同样支持
+//   regexes = ['''test''']
+//
+//   [[allowlists]]             # 全局 allowlist，旧版写法为 [allowlist]
+//   paths = ['''fixtures/''']
+//
+// 同 id 的规则会整条覆盖下层规则。无法编译的规则跳过并记录到 `RuleSet::errors`。
+//
+// 仓库里的 .gitleaks.toml 来自克隆下来的代码，不可信：它总是在下层规则上扩展，
+// 不写 [extend] 或写 `useDefault = false` 也不会替换内置规则和用户规则，否则一个只有一条规则的配置
+// 就能让脱敏和提交前检查失效。`disabledRules` 仍然生效，被移除的下层规则记录在 `RuleSet::dropped_rules`。
+
+/// 应用数据目录下的用户规则文件
+pub const USER_CONFIG_FILE: &str = "gitleaks.toml";
+/// 仓库根目录下的规则文件
+pub const REPO_CONFIG_FILE: &str = ".gitleaks.toml";
+
+/// `[extend] path` 的最大嵌套层数
+const MAX_EXTEND_DEPTH: usize = 4;
+
+#[derive(Debug, Default, Deserialize)]
+struct ConfigFile {
+    #[serde(default)]
+    extend: Option<ExtendConfig>,
+    #[serde(default)]
+    rules: Vec<RuleConfig>,
+    #[serde(default)]
+    allowlist: Option<AllowlistConfig>,
+    #[serde(default)]
+    allowlists: Vec<AllowlistConfig>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ExtendConfig {
+    #[serde(default)]
+    use_default: Option<bool>,
+    #[serde(default)]
+    path: Option<String>,
+    #[serde(default)]
+    disabled_rules: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RuleConfig {
+    id: String,
+    #[serde(default)]
+    description: Option<String>,
+    #[serde(default)]
+    regex: Option<String>,
+    #[serde(default)]
+    secret_group: Option<usize>,
+    #[serde(default)]
+    entropy: Option<f64>,
+    #[serde(default)]
+    keywords: Vec<String>,
+    #[serde(default)]
+    path: Option<String>,
+    #[serde(default)]
+    severity: Option<String>,
+    #[serde(default)]
+    confidence: Option<String>,
+    #[serde(default)]
+    allowlist: Option<AllowlistConfig>,
+    #[serde(default)]
+    allowlists: Vec<AllowlistConfig>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct AllowlistConfig {
+    #[serde(default)]
+    description: Option<String>,
+    #[serde(default)]
+    condition: Option<String>,
+    #[serde(default)]
+    regex_target: Option<String>,
+    #[serde(default)]
+    regexes: Vec<String>,
+    #[serde(default)]
+    stopwords: Vec<String>,
+    #[serde(default)]
+    paths: Vec<String>,
+    #[serde(default)]
+    target_rules: Vec<String>,
+}
+
+/// 配置文件所在的层
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Layer {
+    /// 用户自己的配置（应用数据目录、命令行 --config），与 gitleaks 语义一致
+    User,
+    /// 仓库中的配置，不能替换下层规则
+    Repository,
+}
+
+/// 在 `base` 上叠加一个配置文件，文件不存在时原样返回 `base`。
+/// 文件无法读取或解析时同样返回 `base`，并在 errors 中记录原因
+pub fn load_layer(base: &RuleSet, path: &Path) -> RuleSet {
+    load_with(base, path, Layer::User)
+}
+
+/// 与 `load_layer` 相同，但配置总是在 `base` 上扩展，见文件开头的说明
+pub fn load_repo_layer(base: &RuleSet, path: &Path) -> RuleSet {
+    load_with(base, path, Layer::Repository)
+}
+
+fn load_with(base: &RuleSet, path: &Path, layer: Layer) -> RuleSet {
+    if !path.is_file() {
+        return base.clone();
+    }
+    load_file(base, path, 0, layer).unwrap_or_else(|error| {
+        let mut rules = base.clone();
+        rules.errors.push(error);
+        rules
+    })
+}
+
+fn load_file(base: &RuleSet, path: &Path, depth: usize, layer: Layer) -> Result<RuleSet, RuleError> {
+    let source = path.to_string_lossy().into_owned();
+    let file_error = |message: String| RuleError { source: source.clone(), rule_id: None, message };
+
+    let text = fs::read_to_string(path).map_err(|e| file_error(e.to_string()))?;
+    let config: ConfigFile = toml::from_str(&text).map_err(|e| file_error(e.to_string()))?;
+    let extend = config.extend.unwrap_or_default();
+
+    let mut rules = match &extend.path {
+        Some(parent) if depth >= MAX_EXTEND_DEPTH => {
+            return Err(file_error(format!("[extend] nested too deeply at {}", parent)));
+        }
+        Some(parent) => {
+            let parent = path.parent().unwrap_or(Path::new("")).join(parent);
+            load_file(base, &parent, depth + 1, layer)?
+        }
+        None if extend.use_default == Some(true) => base.clone(),
+        None if layer == Layer::Repository => {
+            let mut rules = base.clone();
+            if extend.use_default == Some(false) {
+                rules.errors.push(file_error(
+                    "useDefault = false is ignored in repository configs; built-in and user rules stay active".to_string(),
+                ));
+            }
+            rules
+        }
+        // 与 gitleaks 一致：没有 [extend] 的配置完全替换下层规则
+        None => RuleSet {
+            errors: base.errors.clone(),
+            sources: base.sources.clone(),
+            dropped_rules: base.rules.iter().map(|r| r.id.clone()).chain(base.dropped_rules.iter().cloned()).collect(),
+            ..RuleSet::default()
+        },
+    };
+
+    let disabled: Vec<String> = rules.rules.iter()
+        .filter(|r| extend.disabled_rules.contains(&r.id))
+        .map(|r| r.id.clone())
+        .collect();
+    if !disabled.is_empty() {
+        rules.rules.retain(|r| !extend.disabled_rules.contains(&r.id));
+        rules.dropped_rules.extend(disabled);
+    }
+
+    for rule in config.rules {
+        let id = rule.id.clone();
+        match compile_rule(rule, &source, &mut rules.errors) {
+            Ok(compiled) => rules.upsert(compiled),
+            Err(message) => rules.errors.push(RuleError { source: source.clone(), rule_id: Some(id), message }),
+        }
+    }
+    // 被移除后又由本文件重新定义的规则不算移除
+    let RuleSet { rules: active, dropped_rules, .. } = &mut rules;
+    dropped_rules.retain(|id| !active.iter().any(|r| &r.id == id));
+
+    for allowlist in config.allowlist.into_iter().chain(config.allowlists) {
+        match compile_allowlist(allowlist) {
+            Ok(compiled) => rules.allowlists.push(compiled),
+            Err(message) => rules.errors.push(RuleError { source: source.clone(), rule_id: None, message }),
+        }
+    }
+
+    rules.sources.push(source);
+    Ok(rules)
+}
+
+/// 规则本身编译失败返回 Err；规则内 allowlist 编译失败只丢弃该 allowlist 并记录
+fn compile_rule(rule: RuleConfig, source: &str, errors: &mut Vec<RuleError>) -> Result<CompiledRule, String> {
+    if rule.id.trim().is_empty() {
+        return Err("Rule id is empty".to_string());
+    }
+    let regex = match &rule.regex {
+        Some(pattern) => Regex::new(pattern).map_err(|e| format!("Invalid regex: {}", e))?,
+        None => return Err("Rules without a regex (path-only rules) are not supported".to_string()),
+    };
+    let path = rule.path.as_deref()
+        .map(|p| Regex::new(p).map_err(|e| format!("Invalid path regex: {}", e)))
+        .transpose()?;
+    let severity = rule.severity.as_deref().map(str::parse).transpose()?.unwrap_or(Severity::High);
+    let confidence = rule.confidence.as_deref().map(str::parse).transpose()?.unwrap_or(Confidence::Medium);
+
+    // gitleaks：未指定 secretGroup 且正则只有一个捕获组时，取该捕获组
+    let secret_group = match rule.secret_group {
+        Some(group) if group >= regex.captures_len() => {
+            return Err(format!("secretGroup {} exceeds the number of capture groups", group));
+        }
+        Some(0) => None,
+        Some(group) => Some(group),
+        None if regex.capture_names().flatten().any(|n| n == "secret") => None,
+        None if regex.captures_len() == 2 => Some(1),
+        None => None,
+    };
+
+    let mut allowlists = Vec::new();
+    for allowlist in rule.allowlist.into_iter().chain(rule.allowlists) {
+        match compile_allowlist(allowlist) {
+            Ok(compiled) => allowlists.push(compiled),
+            Err(message) => errors.push(RuleError { source: source.to_string(), rule_id: Some(rule.id.clone()), message }),
+        }
+    }
+
+    Ok(CompiledRule {
+        description: rule.description.unwrap_or_else(|| rule.id.clone()),
+        id: rule.id,
+        regex,
+        secret_group,
+        entropy: rule.entropy.filter(|e| *e > 0.0),
+        keywords: rule.keywords,
+        path,
+        allowlists,
+        severity,
+        confidence,
+    })
+}
+
+fn compile_allowlist(config: AllowlistConfig) -> Result<Allowlist, String> {
+    let match_all = match config.condition.as_deref().map(str::to_ascii_uppercase).as_deref() {
+        None | Some("OR") => false,
+        Some("AND") => true,
+        Some(other) => return Err(format!("Unknown allowlist condition: {}", other)),
+    };
+    let regex_target = match config.regex_target.as_deref() {
+        None | Some("") | Some("secret") => RegexTarget::Secret,
+        Some("match") => RegexTarget::Match,
+        Some("line") => RegexTarget::Line,
+        Some(other) => return Err(format!("Unknown allowlist regexTarget: {}", other)),
+    };
+    let compile_all = |patterns: &[String]| -> Result<Vec<Regex>, String> {
+        patterns.iter()
+            .map(|p| Regex::new(p).map_err(|e| format!("Invalid allowlist regex: {}", e)))
+            .collect()
+    };
+
+    Ok(Allowlist {
+        description: config.description.unwrap_or_default(),
+        match_all,
+        regex_target,
+        regexes: compile_all(&config.regexes)?,
+        stopwords: config.stopwords.iter().map(|w| w.to_lowercase()).collect(),
+        paths: compile_all(&config.paths)?,
+        target_rules: config.target_rules,
+    })
+}
@@ -0,0 +1,142 @@
+use base64::engine::general_purpose::{STANDARD_NO_PAD, URL_SAFE_NO_PAD};
+use base64::Engine;
+use once_cell::sync::Lazy;
+use percent_encoding::percent_decode_str;
+use regex::{Match, Matches, Regex};
+use std::iter::Peekable;
+use serde::{Deserialize, Serialize};
+use super::{confidence, SecretMatch};
+
+// ============================================================================
+// Encoded Secrets
+// ============================================================================
+//
+// Kubernetes Secret、.dockerconfigjson、URL 查询串里的密钥经过 base64 / hex / URL 编码，
+// 原文中看不到。这里按位置逐个取出候选片段，能解码成文本的用同一套规则重新扫描，
+// 解码结果中的编码片段继续解码，最多 MAX_DECODE_DEPTH 层。候选片段不预先收集，
+// 解码次数和重新扫描的片段数都有上限（各层共用），大文件上的开销有界。
+// 解码后发现的密钥映射回原文中最外层编码片段的位置：`index` 指向片段起始，
+// `encoded.text` 为片段原文，高亮和脱敏时替换整个片段。
+
+/// 最多嵌套解码的层数（例如 .dockerconfigjson 的 base64 里，auth 字段又是一层 base64）
+pub const MAX_DECODE_DEPTH: usize = 3;
+/// 一次扫描最多重新扫描的解码片段数，避免大量 URL 或长标识符的文件拖慢扫描
+pub const MAX_DECODED_SEGMENTS: usize = 4096;
+/// 一次扫描最多尝试解码的候选片段数。压缩代码里的长标识符大多解码失败，不计入上面的上限，但同样要限制
+pub const MAX_DECODE_ATTEMPTS: usize = 64 * 1024;
+
+/// 至少 16 个字符（解码后 12 字节），允许 URL 安全字母表和末尾的 `=`
+static BASE64_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"[A-Za-z0-9+/_-]{16,}={0,2}").unwrap());
+static HEX_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"[0-9A-Fa-f]{32,}").unwrap());
+/// 含有 `%XX` 的一段连续非空白字符（通常是 URL 或查询串）
+static PERCENT_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r#"[^\s"'`<>]*%[0-9A-Fa-f]{2}[^\s"'`<>]*"#).unwrap());
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Encoding {
+    Base64,
+    Hex,
+    Percent,
+}
+
+/// 在编码内容中发现的密钥对应的原文片段
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct EncodedSegment {
+    /// 从外到内依次解码的编码
+    pub encodings: Vec<Encoding>,
+    /// 原文中的编码片段
+    pub text: String,
+}
+
+/// 一次扫描剩余的解码次数和重新扫描的片段数，嵌套解码共用
+pub(super) struct DecodeBudget {
+    attempts: usize,
+    segments: usize,
+}
+
+impl Default for DecodeBudget {
+    fn default() -> Self {
+        Self { attempts: MAX_DECODE_ATTEMPTS, segments: MAX_DECODED_SEGMENTS }
+    }
+}
+
+/// 解码结果必须是 UTF-8 文本（除换行和制表符外没有控制字符），二进制内容不重新扫描
+fn as_text(bytes: Vec<u8>) -> Option<String> {
+    String::from_utf8(bytes).ok().filter(|s| {
+        !s.trim().is_empty() && s.chars().all(|c| !c.is_control() || matches!(c, '\n' | '\r' | '\t'))
+    })
+}
+
+fn decode(encoding: Encoding, segment: &str) -> Option<String> {
+    match encoding {
+        Encoding::Base64 => {
+            let body = segment.trim_end_matches('=');
+            let url_safe = body.contains(['-', '_']);
+            if url_safe && body.contains(['+', '/']) {
+                return None;
+            }
+            let engine = if url_safe { &URL_SAFE_NO_PAD } else { &STANDARD_NO_PAD };
+            as_text(engine.decode(body).ok()?)
+        }
+        Encoding::Hex => as_text(hex::decode(segment).ok()?),
+        Encoding::Percent => percent_decode_str(segment).decode_utf8().ok()
+            .and_then(|s| as_text(s.into_owned().into_bytes())),
+    }
+}
+
+/// 三种编码的候选片段按位置合并后逐个产出（位置相同时依次为 base64、hex、URL 编码）
+fn candidates(text: &str) -> impl Iterator<Item = (Encoding, Match<'_>)> {
+    let mut streams: [(Encoding, Peekable<Matches<'static, '_>>); 3] = [
+        (Encoding::Base64, BASE64_REGEX.find_iter(text).peekable()),
+        (Encoding::Hex, HEX_REGEX.find_iter(text).peekable()),
+        (Encoding::Percent, PERCENT_REGEX.find_iter(text).peekable()),
+    ];
+    std::iter::from_fn(move || {
+        let (_, next) = streams.iter_mut()
+            .enumerate()
+            .filter_map(|(i, (_, matches))| matches.peek().map(|m| (m.start(), i)))
+            .min()?;
+        let (encoding, matches) = &mut streams[next];
+        Some((*encoding, matches.next()?))
+    })
+}
+
+/// 解码 `text` 中的编码片段并用 `scan` 扫描解码结果，返回的结果位置均为 `text` 中编码片段的位置
+pub(super) fn scan_decoded(
+    text: &str,
+    depth: usize,
+    budget: &mut DecodeBudget,
+    scan: &dyn Fn(&str) -> Vec<SecretMatch>,
+) -> Vec<SecretMatch> {
+    if depth == 0 {
+        return Vec::new();
+    }
+
+    let mut found = Vec::new();
+    for (encoding, segment) in candidates(text) {
+        if budget.attempts == 0 || budget.segments == 0 {
+            break;
+        }
+        budget.attempts -= 1;
+        let Some(decoded) = decode(encoding, segment.as_str()) else { continue };
+        budget.segments -= 1;
+
+        let mut inner = scan(&decoded);
+        inner.extend(scan_decoded(&decoded, depth - 1, budget, scan));
+        // 原文中就能看到的值（如 URL 里未编码的部分）已经由普通扫描报告
+        inner.retain(|m| !segment.as_str().contains(m.value.as_str()));
+        // 同一片段中的结果在原文中位置相同，只报告风险最高的一个；脱敏时整个片段都会被替换
+        let Some(best) = inner.into_iter().min_by(confidence::by_risk) else { continue };
+
+        let mut encodings = vec![encoding];
+        if let Some(inner) = &best.encoded {
+            encodings.extend_from_slice(&inner.encodings);
+        }
+        found.push(SecretMatch {
+            index: segment.start(),
+            encoded: Some(EncodedSegment { encodings, text: segment.as_str().to_string() }),
+            ..best
+        });
+    }
+    found
+}
@@ -0,0 +1,184 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Mutex;
+use ignore::WalkState;
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, State};
+use super::history::{ScanProgress, CANCELLED, PROGRESS_EVENT};
+use super::{IgnoreSet, RuleSet, SecretMatch};
+use crate::charset;
+use crate::context::walker;
+use crate::db::{self, DbState, ProjectConfig};
+use crate::jobs::JobRegistry;
+
+// ============================================================================
+// Working Tree Secret Scan
+// ============================================================================
+//
+// 按上下文面板相同的规则遍历项目（gitignore + 过滤列表），跳过二进制和过大的文件，
+// 对其余文件运行 gitleaks 规则。非 UTF-8 文件先按检测到的编码转码。
+
+const DEFAULT_MAX_FILE_SIZE: u64 = 1024 * 1024;
+/// 每处理这么多个文件汇报一次进度
+const PROGRESS_INTERVAL: usize = 200;
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct DirectoryScanOptions {
+    /// 超过该字节数的文件跳过
+    #[serde(default = "default_max_file_size")]
+    pub max_file_size: u64,
+}
+
+fn default_max_file_size() -> u64 {
+    DEFAULT_MAX_FILE_SIZE
+}
+
+impl Default for DirectoryScanOptions {
+    fn default() -> Self {
+        Self { max_file_size: DEFAULT_MAX_FILE_SIZE }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct FileSecretMatch {
+    #[serde(flatten)]
+    pub secret: SecretMatch,
+    /// 相对扫描根目录的路径，分隔符统一为 `/`
+    pub path: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct DirectoryScan {
+    /// 按路径、行号排序
+    pub findings: Vec<FileSecretMatch>,
+    pub files_scanned: usize,
+    pub skipped_binary: usize,
+    pub skipped_large: usize,
+    /// 读取失败（权限不足、扫描期间被删除）的文件，不计入 `files_scanned`
+    pub skipped_unreadable: usize,
+}
+
+/// 并行遍历 `root` 并收集要扫描的文件及其大小
+fn collect_files(root: &Path, config: &ProjectConfig) -> Vec<(PathBuf, u64)> {
+    let files = Mutex::new(Vec::new());
+    walker::walk_builder(root, config).build_parallel().run(|| {
+        Box::new(|result| {
+            // 权限不足、符号链接成环等错误直接跳过该条目
+            let Ok(entry) = result else { return WalkState::Continue };
+            if entry.file_type().is_some_and(|t| t.is_file()) {
+                let size = entry.metadata().map(|m| m.len()).unwrap_or(0);
+                if let Ok(mut files) = files.lock() {
+                    files.push((entry.into_path(), size));
+                }
+            }
+            WalkState::Continue
+        })
+    });
+    files.into_inner().unwrap_or_default()
+}
+
+pub fn scan_tree(
+    root: &Path,
+    config: &ProjectConfig,
+    options: &DirectoryScanOptions,
+    rules: &RuleSet,
+    ignored: &IgnoreSet,
+    cancel: &AtomicBool,
+    on_progress: impl Fn(usize, usize) + Sync,
+) -> Result<DirectoryScan, String> {
+    if !root.is_dir() {
+        return Err(format!("Not a directory: {}", root.display()));
+    }
+    let files = collect_files(root, config);
+    let total = files.len();
+    let done = AtomicUsize::new(0);
+    let skipped_binary = AtomicUsize::new(0);
+    let skipped_large = AtomicUsize::new(0);
+    let skipped_unreadable = AtomicUsize::new(0);
+
+    let per_file: Vec<Vec<FileSecretMatch>> = files
+        .par_iter()
+        .map(|(path, size)| {
+            if cancel.load(Ordering::Relaxed) {
+                return Err(CANCELLED.to_string());
+            }
+            let n = done.fetch_add(1, Ordering::Relaxed) + 1;
+            if n.is_multiple_of(PROGRESS_INTERVAL) || n == total {
+                on_progress(n, total);
+            }
+
+            if *size > options.max_file_size {
+                skipped_large.fetch_add(1, Ordering::Relaxed);
+                return Ok(Vec::new());
+            }
+            let Ok(bytes) = fs::read(path) else {
+                skipped_unreadable.fetch_add(1, Ordering::Relaxed);
+                return Ok(Vec::new());
+            };
+            if charset::is_binary(&bytes) {
+                skipped_binary.fetch_add(1, Ordering::Relaxed);
+                return Ok(Vec::new());
+            }
+
+            let text = charset::decode(&bytes).text;
+            let relative = path.strip_prefix(root).unwrap_or(path).to_string_lossy().replace('\\', "/");
+            Ok(super::scan_with(rules, &text, Some(&relative)).into_iter()
+                .filter(|m| !ignored.is_ignored(m, Some(&relative), None))
+                .map(|secret| FileSecretMatch { secret, path: relative.clone() })
+                .collect())
+        })
+        .collect::<Result<_, String>>()?;
+
+    let mut findings: Vec<FileSecretMatch> = per_file.into_iter().flatten().collect();
+    findings.sort_by(|a, b| a.path.cmp(&b.path).then(a.secret.index.cmp(&b.secret.index)));
+
+    let skipped_binary = skipped_binary.into_inner();
+    let skipped_large = skipped_large.into_inner();
+    let skipped_unreadable = skipped_unreadable.into_inner();
+    Ok(DirectoryScan {
+        findings,
+        files_scanned: total - skipped_binary - skipped_large - skipped_unreadable,
+        skipped_binary,
+        skipped_large,
+        skipped_unreadable,
+    })
+}
+
+/// 扫描项目目录（工作区）。使用项目的 .gitleaks.toml、.gitleaksignore 和白名单，进度通过 `secret-scan-progress` 事件汇报
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub async fn scan_directory(
+    app: AppHandle,
+    jobs: State<'_, JobRegistry>,
+    state: State<'_, DbState>,
+    root: String,
+    config: Option<ProjectConfig>,
+    options: Option<DirectoryScanOptions>,
+    job_id: Option<String>
+) -> Result<DirectoryScan, String> {
+    let entries = {
+        let conn = state.conn.lock().map_err(|e| e.to_string())?;
+        db::secrets::get_active_ignored_internal(&conn).map_err(|e| e.to_string())?
+    };
+    let job_id = job_id.unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+    let cancel = jobs.register(&job_id);
+
+    let id = job_id.clone();
+    let result = tauri::async_runtime::spawn_blocking(move || {
+        let root = Path::new(&root);
+        let config = config.unwrap_or(ProjectConfig { dirs: Vec::new(), files: Vec::new(), extensions: Vec::new() });
+        let rules = super::rules_for_repo(root);
+        let ignored = IgnoreSet::compile(&entries, Some(root));
+        scan_tree(root, &config, &options.unwrap_or_default(), &rules, &ignored, &cancel, |done, total| {
+            let progress = ScanProgress { job_id: id.clone(), done, total };
+            if let Err(e) = app.emit(PROGRESS_EVENT, progress) {
+                eprintln!("[Gitleaks] Failed to emit scan progress: {}", e);
+            }
+        })
+    }).await.map_err(|e| e.to_string());
+
+    jobs.finish(&job_id);
+    result?
+}
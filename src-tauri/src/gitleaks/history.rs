@@ -0,0 +1,223 @@
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use chrono::{DateTime, Local};
+use git2::{Commit, DiffFindOptions, DiffOptions, Oid, Repository, Sort};
+use rayon::prelude::*;
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, State};
+use super::{IgnoreSet, RuleSet, SecretMatch};
+use crate::db::{self, DbState};
+use crate::git;
+use crate::jobs::JobRegistry;
+
+// ============================================================================
+// Git History Secret Scan
+// ============================================================================
+//
+// 与 `git log -p --all -M` 的范围一致：逐个提交与第一个父提交比较（根提交与空树比较），
+// 只扫描新增的行。合并提交没有自己的改动，跳过。
+// 同一个密钥在同一文件中反复出现时只保留最早引入它的那次提交。
+
+/// 前端监听的进度事件名
+pub const PROGRESS_EVENT: &str = "secret-scan-progress";
+pub const CANCELLED: &str = "Scan cancelled";
+
+/// 超过该大小的文件改动不扫描
+const MAX_BLOB_SIZE: u64 = 1024 * 1024;
+/// 每处理这么多个提交汇报一次进度
+const PROGRESS_INTERVAL: usize = 50;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct HistoryFinding {
+    /// `line_number` / `snippet_start_line` 为该提交中文件的行号
+    #[serde(flatten)]
+    pub secret: SecretMatch,
+    pub path: String,
+    pub commit: String,
+    pub author: String,
+    pub email: String,
+    pub date: String,
+    pub message: String,
+    /// 同一密钥在该文件中出现的提交数（包括引入它的提交）
+    pub occurrences: usize,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ScanProgress {
+    pub job_id: String,
+    pub done: usize,
+    pub total: usize,
+}
+
+/// 扫描范围：`a..b`、单个引用（包含其全部祖先），为 None 时扫描所有引用
+pub fn collect_commits(repo: &Repository, range: Option<&str>) -> Result<Vec<Oid>, String> {
+    let mut revwalk = repo.revwalk().map_err(|e| e.to_string())?;
+    revwalk.set_sorting(Sort::TOPOLOGICAL | Sort::TIME | Sort::REVERSE).map_err(|e| e.to_string())?;
+
+    match range.map(str::trim).filter(|r| !r.is_empty()) {
+        Some(range) if range.contains("..") => {
+            revwalk.push_range(range).map_err(|e| format!("Invalid range {}: {}", range, e))?;
+        }
+        Some(rev) => {
+            let commit = repo.revparse_single(rev)
+                .and_then(|obj| obj.peel_to_commit())
+                .map_err(|e| format!("Cannot resolve {}: {}", rev, e))?;
+            revwalk.push(commit.id()).map_err(|e| e.to_string())?;
+        }
+        None => {
+            revwalk.push_glob("*").map_err(|e| e.to_string())?;
+            // 分离 HEAD 不在任何引用下；空仓库没有 HEAD
+            let _ = revwalk.push_head();
+        }
+    }
+
+    revwalk.collect::<Result<Vec<_>, _>>().map_err(|e| e.to_string())
+}
+
+/// 按提交从旧到新返回结果，`on_progress(done, total)` 在并行线程中调用
+pub fn scan_history(
+    repo_path: &Path,
+    range: Option<&str>,
+    rules: &RuleSet,
+    ignored: &IgnoreSet,
+    cancel: &AtomicBool,
+    on_progress: impl Fn(usize, usize) + Sync,
+) -> Result<Vec<HistoryFinding>, String> {
+    let repo = Repository::open(repo_path).map_err(|e| format!("Cannot open repository: {}", e))?;
+    let commits = collect_commits(&repo, range)?;
+    let total = commits.len();
+    let done = AtomicUsize::new(0);
+
+    // Repository 不能跨线程共享，每个工作线程各自打开一份
+    let per_commit: Vec<Vec<HistoryFinding>> = commits
+        .par_iter()
+        .map_init(
+            || Repository::open(repo_path).map_err(|e| e.to_string()),
+            |repo, &oid| {
+                if cancel.load(Ordering::Relaxed) {
+                    return Err(CANCELLED.to_string());
+                }
+                let repo = repo.as_ref().map_err(|e| e.clone())?;
+                let findings = scan_commit(repo, oid, rules, ignored)?;
+
+                let n = done.fetch_add(1, Ordering::Relaxed) + 1;
+                if n.is_multiple_of(PROGRESS_INTERVAL) || n == total {
+                    on_progress(n, total);
+                }
+                Ok(findings)
+            },
+        )
+        .collect::<Result<_, String>>()?;
+
+    // commits 已按时间从旧到新排列，保留每个密钥第一次出现的位置
+    let mut findings: Vec<HistoryFinding> = Vec::new();
+    let mut seen: HashMap<(String, String, String), usize> = HashMap::new();
+    for finding in per_commit.into_iter().flatten() {
+        let key = (finding.secret.kind.clone(), finding.path.clone(), finding.secret.value.clone());
+        match seen.get(&key) {
+            Some(&idx) => findings[idx].occurrences += 1,
+            None => {
+                seen.insert(key, findings.len());
+                findings.push(finding);
+            }
+        }
+    }
+    Ok(findings)
+}
+
+fn scan_commit(repo: &Repository, oid: Oid, rules: &RuleSet, ignored: &IgnoreSet) -> Result<Vec<HistoryFinding>, String> {
+    let commit = repo.find_commit(oid).map_err(|e| e.to_string())?;
+    if commit.parent_count() > 1 {
+        return Ok(Vec::new());
+    }
+
+    let tree = commit.tree().map_err(|e| e.to_string())?;
+    let parent_tree = match commit.parent(0) {
+        Ok(parent) => Some(parent.tree().map_err(|e| e.to_string())?),
+        Err(_) => None,
+    };
+
+    let mut diff_opts = DiffOptions::new();
+    // 二进制文件由 added_hunks 按内容跳过，UTF-16 文本也需要生成 hunk
+    diff_opts.context_lines(0).force_text(true);
+    let mut diff = repo
+        .diff_tree_to_tree(parent_tree.as_ref(), Some(&tree), Some(&mut diff_opts))
+        .map_err(|e| e.to_string())?;
+    let mut find_opts = DiffFindOptions::new();
+    find_opts.renames(true);
+    diff.find_similar(Some(&mut find_opts)).map_err(|e| e.to_string())?;
+
+    let commit_id = commit.id().to_string();
+    let mut findings = Vec::new();
+    for hunk in git::added_hunks(repo, &diff, MAX_BLOB_SIZE)? {
+        for mut secret in super::scan_with(rules, &hunk.text, Some(&hunk.path)) {
+            secret.line_number += hunk.start_line - 1;
+            secret.snippet_start_line += hunk.start_line - 1;
+            if ignored.is_ignored(&secret, Some(&hunk.path), Some(&commit_id)) {
+                continue;
+            }
+            findings.push(finding(&commit, &hunk.path, secret));
+        }
+    }
+    Ok(findings)
+}
+
+fn finding(commit: &Commit, path: &str, secret: SecretMatch) -> HistoryFinding {
+    let author = commit.author();
+    let date = DateTime::from_timestamp(commit.time().seconds(), 0)
+        .unwrap_or_default()
+        .with_timezone(&Local)
+        .format("%Y-%m-%d %H:%M")
+        .to_string();
+    HistoryFinding {
+        secret,
+        path: path.to_string(),
+        commit: commit.id().to_string(),
+        author: author.name().unwrap_or("Unknown").to_string(),
+        email: author.email().unwrap_or("").to_string(),
+        date,
+        message: commit.summary().unwrap_or("").to_string(),
+        occurrences: 1,
+    }
+}
+
+/// 扫描仓库历史。使用仓库的 .gitleaks.toml、.gitleaksignore 和白名单，进度通过 `secret-scan-progress` 事件汇报
+#[tauri::command]
+pub async fn scan_git_history(
+    app: AppHandle,
+    jobs: State<'_, JobRegistry>,
+    state: State<'_, DbState>,
+    project_path: String,
+    range: Option<String>,
+    job_id: Option<String>
+) -> Result<Vec<HistoryFinding>, String> {
+    let entries = {
+        let conn = state.conn.lock().map_err(|e| e.to_string())?;
+        db::secrets::get_active_ignored_internal(&conn).map_err(|e| e.to_string())?
+    };
+    let job_id = job_id.unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+    let cancel = jobs.register(&job_id);
+
+    let id = job_id.clone();
+    let result = tauri::async_runtime::spawn_blocking(move || {
+        let root = Path::new(&project_path);
+        let rules = super::rules_for_repo(root);
+        let ignored = IgnoreSet::compile(&entries, Some(root));
+        scan_history(root, range.as_deref(), &rules, &ignored, &cancel, |done, total| {
+            let progress = ScanProgress { job_id: id.clone(), done, total };
+            if let Err(e) = app.emit(PROGRESS_EVENT, progress) {
+                eprintln!("[Gitleaks] Failed to emit scan progress: {}", e);
+            }
+        })
+    }).await.map_err(|e| e.to_string());
+
+    jobs.finish(&job_id);
+    result?
+}
+
+/// 取消正在进行的历史扫描，返回是否找到了该任务
+#[tauri::command]
+pub fn cancel_secret_scan(job_id: String, jobs: State<'_, JobRegistry>) -> bool {
+    jobs.cancel(&job_id)
+}
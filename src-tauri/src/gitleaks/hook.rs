@@ -0,0 +1,182 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use git2::Repository;
+use serde::Serialize;
+use super::directory::FileSecretMatch;
+use super::{IgnoreSet, RuleSet};
+use crate::git;
+
+// ============================================================================
+// Pre-commit Secret Guard
+// ============================================================================
+//
+// 在仓库中安装一个 `pre-commit` 钩子脚本，提交前调用 `ctxrun-cli scan --staged`，
+// 只扫描暂存区相对 HEAD 新增的行（而不是工作区文件）。发现未加入白名单的密钥时
+// CLI 以非零状态退出，git 随之中止提交。
+// 脚本带有 HOOK_MARKER 标记，卸载和覆盖时只处理带标记的脚本，不会动用户自己的钩子。
+// 找不到 CLI 或扫描出错时同样阻止提交；确实需要在没有 CLI 的环境里提交时，
+// 设置 `git config ctxrun.hookFailOpen true` 让钩子在找不到 CLI 时放行。
+
+const HOOK_NAME: &str = "pre-commit";
+const HOOK_MARKER: &str = "ctxrun-secret-guard";
+/// 超过该大小的暂存文件不扫描
+const MAX_BLOB_SIZE: u64 = 1024 * 1024;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct HookStatus {
+    /// CtxRun 的钩子已安装
+    pub installed: bool,
+    /// 已存在不是由 CtxRun 安装的 pre-commit 钩子，此时不会覆盖
+    pub foreign_hook: bool,
+    pub hook_path: String,
+    /// 钩子脚本调用的 CLI 路径
+    pub cli_path: Option<String>,
+}
+
+/// 扫描暂存区中新增的行，行号为暂存版本文件中的行号
+pub fn scan_staged(repo_path: &Path, rules: &RuleSet, ignored: &IgnoreSet) -> Result<Vec<FileSecretMatch>, String> {
+    let repo = Repository::discover(repo_path).map_err(|e| format!("Cannot open repository: {}", e))?;
+    let diff = git::staged_diff(&repo)?;
+
+    let mut findings = Vec::new();
+    for hunk in git::added_hunks(&repo, &diff, MAX_BLOB_SIZE)? {
+        for mut secret in super::scan_with(rules, &hunk.text, Some(&hunk.path)) {
+            secret.line_number += hunk.start_line - 1;
+            secret.snippet_start_line += hunk.start_line - 1;
+            if ignored.is_ignored(&secret, Some(&hunk.path), None) {
+                continue;
+            }
+            findings.push(FileSecretMatch { secret, path: hunk.path.clone() });
+        }
+    }
+    Ok(findings)
+}
+
+/// 遵循 `core.hooksPath`（相对路径相对于工作区根目录），否则为 `.git/hooks`
+fn hooks_dir(repo: &Repository) -> PathBuf {
+    let configured = repo.config().ok().and_then(|c| c.get_path("core.hooksPath").ok());
+    match configured {
+        Some(dir) if dir.is_absolute() => dir,
+        Some(dir) => repo.workdir().unwrap_or_else(|| repo.path()).join(dir),
+        None => common_dir(repo).join("hooks"),
+    }
+}
+
+/// 链接工作树的 gitdir 里有一个 `commondir` 文件指向主仓库的 .git，hooks 目录与主仓库共用
+fn common_dir(repo: &Repository) -> PathBuf {
+    if repo.is_worktree() {
+        if let Ok(common) = fs::read_to_string(repo.path().join("commondir")) {
+            return repo.path().join(common.trim());
+        }
+    }
+    repo.path().to_path_buf()
+}
+
+fn hook_path(repo_path: &Path) -> Result<PathBuf, String> {
+    let repo = Repository::discover(repo_path).map_err(|e| format!("Cannot open repository: {}", e))?;
+    Ok(hooks_dir(&repo).join(HOOK_NAME))
+}
+
+/// 与当前程序放在一起的 ctxrun-cli，找不到时返回 None（脚本退回到 PATH 中查找）
+fn bundled_cli() -> Option<PathBuf> {
+    let exe = std::env::current_exe().ok()?;
+    let cli = exe.parent()?.join(format!("ctxrun-cli{}", std::env::consts::EXE_SUFFIX));
+    cli.is_file().then_some(cli)
+}
+
+/// 从已安装的脚本中读出 CTXRUN_CLI 的值
+fn script_cli_path(script: &str) -> Option<String> {
+    let value = script.lines().find_map(|l| l.strip_prefix("CTXRUN_CLI="))?;
+    let value = value.trim().trim_matches('\'').replace("'\\''", "'");
+    (!value.is_empty()).then_some(value)
+}
+
+fn hook_script(cli: Option<&Path>) -> String {
+    // Windows 上 git 通过自带的 sh 执行钩子，使用正斜杠路径
+    let cli = cli.map(|p| p.to_string_lossy().replace('\\', "/")).unwrap_or_default();
+    format!(
+        r#"#!/bin/sh
+# {marker}: installed by CtxRun. Uninstall it from CtxRun or delete this file.
+# Scans the staged changes for secrets and blocks the commit when any are found.
+CTXRUN_CLI='{cli}'
+if [ ! -x "$CTXRUN_CLI" ]; then
+  CTXRUN_CLI=$(command -v ctxrun-cli) || {{
+    if [ "$(git config --bool ctxrun.hookFailOpen)" = "true" ]; then
+      echo "ctxrun: ctxrun-cli not found, skipping secret scan (ctxrun.hookFailOpen is set)" >&2
+      exit 0
+    fi
+    echo "ctxrun: ctxrun-cli not found, commit blocked. Reinstall the hook from CtxRun, put ctxrun-cli on PATH," >&2
+    echo "ctxrun: or run 'git config ctxrun.hookFailOpen true' to commit without a secret scan." >&2
+    exit 1
+  }}
+fi
+"$CTXRUN_CLI" scan --staged .
+status=$?
+if [ $status -eq 1 ]; then
+  echo "ctxrun: commit blocked. Remove the secrets above, add them to the allowlist, or commit with --no-verify." >&2
+elif [ $status -ne 0 ]; then
+  echo "ctxrun: secret scan failed, commit blocked. Fix the error above or commit with --no-verify." >&2
+fi
+exit $status
+"#,
+        marker = HOOK_MARKER,
+        cli = cli.replace('\'', "'\\''"),
+    )
+}
+
+pub fn hook_status(repo_path: &Path) -> Result<HookStatus, String> {
+    let path = hook_path(repo_path)?;
+    let script = fs::read_to_string(&path).ok();
+    let installed = script.as_deref().is_some_and(|s| s.contains(HOOK_MARKER));
+    Ok(HookStatus {
+        installed,
+        foreign_hook: path.exists() && !installed,
+        hook_path: path.to_string_lossy().into_owned(),
+        cli_path: script.as_deref().filter(|_| installed).and_then(script_cli_path),
+    })
+}
+
+/// 写入钩子脚本；已安装时更新 CLI 路径，存在其他钩子时报错
+pub fn install_hook(repo_path: &Path, cli: Option<&Path>) -> Result<HookStatus, String> {
+    let status = hook_status(repo_path)?;
+    if status.foreign_hook {
+        return Err(format!("A pre-commit hook that was not installed by CtxRun already exists: {}", status.hook_path));
+    }
+
+    let path = Path::new(&status.hook_path);
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    }
+    fs::write(path, hook_script(cli)).map_err(|e| format!("Failed to write hook: {}", e))?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(path, fs::Permissions::from_mode(0o755)).map_err(|e| e.to_string())?;
+    }
+    hook_status(repo_path)
+}
+
+/// 只删除 CtxRun 安装的钩子
+pub fn uninstall_hook(repo_path: &Path) -> Result<HookStatus, String> {
+    let status = hook_status(repo_path)?;
+    if status.installed {
+        fs::remove_file(&status.hook_path).map_err(|e| format!("Failed to remove hook: {}", e))?;
+        return hook_status(repo_path);
+    }
+    Ok(status)
+}
+
+#[tauri::command]
+pub async fn get_secret_hook_status(project_path: String) -> Result<HookStatus, String> {
+    hook_status(Path::new(&project_path))
+}
+
+#[tauri::command]
+pub async fn install_secret_hook(project_path: String) -> Result<HookStatus, String> {
+    install_hook(Path::new(&project_path), bundled_cli().as_deref())
+}
+
+#[tauri::command]
+pub async fn uninstall_secret_hook(project_path: String) -> Result<HookStatus, String> {
+    uninstall_hook(Path::new(&project_path))
+}
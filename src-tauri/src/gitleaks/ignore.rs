@@ -0,0 +1,205 @@
+use std::collections::HashSet;
+use std::fs;
+use std::path::Path;
+use globset::{GlobBuilder, GlobMatcher};
+use regex::Regex;
+use super::report::fingerprint;
+use super::SecretMatch;
+use crate::db::models::IgnoredSecret;
+
+// ============================================================================
+// Secret Allowlist
+// ============================================================================
+//
+// 把数据库中的白名单条目（`ignored_secrets`）和仓库根目录的 `.gitleaksignore` 编译成 `IgnoreSet`，
+// 各个扫描入口用它过滤结果。条目可以限定值、值正则、规则、路径 glob、项目和指纹，
+// 所有非空条件都满足时放行。只有值（旧版白名单）或只有指纹的条目走哈希表快速路径。
+
+/// 仓库根目录下的指纹忽略文件，每行一个 gitleaks 指纹，`#` 开头为注释
+pub const GITLEAKS_IGNORE_FILE: &str = ".gitleaksignore";
+
+#[derive(Debug, Clone)]
+struct PathGlob {
+    matcher: GlobMatcher,
+    /// 模式中没有 `/` 时只匹配文件名
+    basename: bool,
+}
+
+impl PathGlob {
+    fn is_match(&self, path: &str) -> bool {
+        if self.basename {
+            self.matcher.is_match(path.rsplit('/').next().unwrap_or(path))
+        } else {
+            self.matcher.is_match(path)
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct IgnoreEntry {
+    value: Option<String>,
+    value_regex: Option<Regex>,
+    rule_id: Option<String>,
+    path_glob: Option<PathGlob>,
+    /// 扫描时不知道项目根目录的情况下，要求文件位于该目录中（路径为绝对路径）
+    project: Option<String>,
+    fingerprint: Option<String>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct IgnoreSet {
+    /// 扫描的项目根目录，结果路径相对于它
+    root: Option<String>,
+    values: HashSet<String>,
+    fingerprints: HashSet<String>,
+    entries: Vec<IgnoreEntry>,
+}
+
+fn normalize(path: &str) -> String {
+    path.replace('\\', "/").trim_end_matches('/').to_string()
+}
+
+fn non_empty(field: &Option<String>) -> Option<&str> {
+    field.as_deref().map(str::trim).filter(|s| !s.is_empty())
+}
+
+fn fingerprint_matches(fingerprints: impl Fn(&str) -> bool, path: &str, secret: &SecretMatch, commit: Option<&str>) -> bool {
+    fingerprints(&fingerprint(path, &secret.kind, secret.line_number, None))
+        || commit.is_some_and(|c| fingerprints(&fingerprint(path, &secret.kind, secret.line_number, Some(c))))
+}
+
+/// 条目至少要有一个条件，正则和 glob 必须能编译
+pub fn validate(entry: &IgnoredSecret) -> Result<(), String> {
+    compile_entry(entry, non_empty(&entry.project_root).map(normalize)).map(|_| ())
+}
+
+/// `project` 为需要按绝对路径判断的项目目录
+fn compile_entry(entry: &IgnoredSecret, project: Option<String>) -> Result<IgnoreEntry, String> {
+    let value_regex = non_empty(&entry.value_regex)
+        .map(|r| Regex::new(r).map_err(|e| format!("Invalid value regex: {}", e)))
+        .transpose()?;
+    let path_glob = non_empty(&entry.path_glob)
+        .map(|g| {
+            let glob = g.replace('\\', "/");
+            GlobBuilder::new(glob.trim_start_matches("./"))
+                .literal_separator(true)
+                .build()
+                .map(|g| PathGlob { matcher: g.compile_matcher(), basename: !glob.contains('/') })
+                .map_err(|e| format!("Invalid path glob: {}", e))
+        })
+        .transpose()?;
+
+    let compiled = IgnoreEntry {
+        value: entry.value.clone().filter(|v| !v.is_empty()),
+        value_regex,
+        rule_id: non_empty(&entry.rule_id).map(str::to_string),
+        path_glob,
+        project,
+        fingerprint: non_empty(&entry.fingerprint).map(str::to_string),
+    };
+    if compiled.value.is_none() && compiled.value_regex.is_none() && compiled.rule_id.is_none()
+        && compiled.path_glob.is_none() && compiled.fingerprint.is_none() {
+        return Err("An allowlist entry needs a value, regex, rule, path or fingerprint".to_string());
+    }
+    Ok(compiled)
+}
+
+impl IgnoreEntry {
+    /// `path` 已经是相对于扫描根目录的路径（根目录未知时为原始路径）
+    fn matches(&self, secret: &SecretMatch, path: Option<&str>, commit: Option<&str>) -> bool {
+        if self.value.as_ref().is_some_and(|v| *v != secret.value)
+            || self.value_regex.as_ref().is_some_and(|r| !r.is_match(&secret.value))
+            || self.rule_id.as_ref().is_some_and(|r| *r != secret.kind) {
+            return false;
+        }
+        if self.project.is_none() && self.path_glob.is_none() && self.fingerprint.is_none() {
+            return true;
+        }
+
+        let Some(path) = path else { return false };
+        let path = match &self.project {
+            Some(project) => match path.strip_prefix(project.as_str()).and_then(|p| p.strip_prefix('/')) {
+                Some(relative) => relative,
+                None => return false,
+            },
+            None => path,
+        };
+        if self.path_glob.as_ref().is_some_and(|g| !g.is_match(path)) {
+            return false;
+        }
+        match &self.fingerprint {
+            Some(fp) => fingerprint_matches(|f| f == fp, path, secret, commit),
+            None => true,
+        }
+    }
+}
+
+impl IgnoreSet {
+    /// `root` 为扫描的项目根目录：其他项目的条目被丢弃，并读取根目录下的 `.gitleaksignore`。
+    /// 为 None 时（例如组装上下文）保留所有项目的条目，按文件的绝对路径判断是否位于该项目中
+    pub fn compile(entries: &[IgnoredSecret], root: Option<&Path>) -> Self {
+        let root_str = root.map(|r| normalize(&r.to_string_lossy()));
+        let mut set = IgnoreSet { root: root_str.clone(), ..Self::default() };
+
+        for entry in entries {
+            let project = match (non_empty(&entry.project_root).map(normalize), &root_str) {
+                (Some(project), Some(root)) if project != *root => continue,
+                // 与扫描根目录相同：结果路径本来就是相对路径，不需要再判断
+                (Some(_), Some(_)) => None,
+                (project, None) => project,
+                (None, Some(_)) => None,
+            };
+            let compiled = match compile_entry(entry, project) {
+                Ok(compiled) => compiled,
+                Err(e) => {
+                    eprintln!("[Secrets] Skipping allowlist entry {}: {}", entry.id, e);
+                    continue;
+                }
+            };
+            let only_value = compiled.value_regex.is_none() && compiled.rule_id.is_none()
+                && compiled.path_glob.is_none() && compiled.project.is_none();
+            match (&compiled.value, &compiled.fingerprint) {
+                (Some(value), None) if only_value => { set.values.insert(value.clone()); }
+                (None, Some(fp)) if only_value => { set.fingerprints.insert(fp.clone()); }
+                _ => set.entries.push(compiled),
+            }
+        }
+
+        if let Some(root) = root {
+            set.load_gitleaksignore(&root.join(GITLEAKS_IGNORE_FILE));
+        }
+        set
+    }
+
+    fn load_gitleaksignore(&mut self, path: &Path) {
+        let Ok(text) = fs::read_to_string(path) else { return };
+        self.fingerprints.extend(
+            text.lines()
+                .map(str::trim)
+                .filter(|l| !l.is_empty() && !l.starts_with('#'))
+                .map(|l| l.replace('\\', "/")),
+        );
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty() && self.fingerprints.is_empty() && self.entries.is_empty()
+    }
+
+    /// `path` 为结果所属文件（相对扫描根目录或绝对路径），`commit` 只在历史扫描时传入
+    pub fn is_ignored(&self, secret: &SecretMatch, path: Option<&str>, commit: Option<&str>) -> bool {
+        if self.values.contains(&secret.value) {
+            return true;
+        }
+        let path = path.map(normalize);
+        let relative = match (&path, &self.root) {
+            (Some(path), Some(root)) => Some(path.strip_prefix(root.as_str()).and_then(|p| p.strip_prefix('/')).unwrap_or(path)),
+            (path, _) => path.as_deref(),
+        };
+        if let Some(relative) = relative {
+            if !self.fingerprints.is_empty() && fingerprint_matches(|f| self.fingerprints.contains(f), relative, secret, commit) {
+                return true;
+            }
+        }
+        self.entries.iter().any(|e| e.matches(secret, relative, commit))
+    }
+}
@@ -1,12 +1,24 @@
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use regex::Regex;
 use once_cell::sync::Lazy;
 use entropy::shannon_entropy;
 use std::str;
+use std::path::Path;
+use std::sync::{Arc, RwLock};
 use rayon::prelude::*;
 
 pub mod allowlist;
+pub mod confidence;
+pub mod config;
+pub mod decode;
+pub mod directory;
+pub mod history;
+pub mod hook;
+pub mod ignore;
+pub mod prefilter;
+pub mod report;
 pub mod rule;
+pub mod ruleset;
 pub mod rules_ai;
 pub mod rules_cloud;
 pub mod rules_communication;
@@ -15,35 +27,103 @@ pub mod rules_payment;
 pub mod rules_remaining;
 
 use allowlist::is_safe_value;
-use rule::get_all_rules;
+use ruleset::Candidate;
+pub use confidence::{Confidence, Severity};
+pub use decode::{EncodedSegment, Encoding};
+pub use ignore::IgnoreSet;
+pub use ruleset::{RuleSet, RuleSetStatus, RuleError};
 
 #[derive(Debug, Clone)]
 pub struct Rule {
     pub id: &'static str,
-    #[allow(dead_code)]
     pub description: &'static str,
     pub regex: Regex,
     pub entropy: Option<f64>,     
     pub keywords: &'static [&'static str],
+    pub severity: Severity,
+    pub confidence: Confidence,
 }
 
-#[derive(Serialize, Clone, Debug)]
+#[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct SecretMatch {
     pub kind: String,        
     pub value: String,       
     pub index: usize,
-    pub risk_level: String,
+    /// 规则的严重程度
+    pub risk_level: Severity,
+    /// 结合上下文调整后的置信度，0-100
+    pub confidence: u8,
     pub utf16_index: usize,        // JS 兼容索引 (关键修复)
     pub line_number: usize,        // 行号
     pub snippet: String,           // 代码片段
     pub snippet_start_line: usize, // 片段起始行
+    /// 解码 base64 / hex / URL 编码后发现时，`value` 为解码后的密钥，`index` 指向原文中的编码片段
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub encoded: Option<EncodedSegment>,
 }
 
-static RULES: Lazy<&'static [Rule]> = Lazy::new(|| get_all_rules());
+impl SecretMatch {
+    /// 结果在原文中对应的文本：编码片段或密钥本身，高亮和脱敏以它为准
+    pub fn matched_text(&self) -> &str {
+        self.encoded.as_ref().map_or(&self.value, |e| &e.text)
+    }
+}
+
+/// 当前生效的规则：内置规则叠加应用数据目录下的 gitleaks.toml
+static ACTIVE_RULES: Lazy<RwLock<Arc<RuleSet>>> = Lazy::new(|| RwLock::new(Arc::new(RuleSet::builtin())));
+
+pub fn active_rules() -> Arc<RuleSet> {
+    match ACTIVE_RULES.read() {
+        Ok(rules) => rules.clone(),
+        Err(poisoned) => poisoned.into_inner().clone(),
+    }
+}
+
+/// 重新读取 `<app_dir>/gitleaks.toml` 并替换当前规则；文件不存在时恢复为内置规则
+pub fn load_user_rules(app_dir: &Path) -> Arc<RuleSet> {
+    let rules = Arc::new(config::load_layer(&RuleSet::builtin(), &app_dir.join(config::USER_CONFIG_FILE)));
+    match ACTIVE_RULES.write() {
+        Ok(mut active) => *active = rules.clone(),
+        Err(poisoned) => *poisoned.into_inner() = rules.clone(),
+    }
+    rules
+}
+
+/// 扫描某个仓库时使用的规则：在当前规则上叠加仓库根目录的 `.gitleaks.toml`，仓库配置不能替换当前规则
+pub fn rules_for_repo(root: &Path) -> Arc<RuleSet> {
+    let active = active_rules();
+    let repo_config = root.join(config::REPO_CONFIG_FILE);
+    if repo_config.is_file() {
+        Arc::new(config::load_repo_layer(&active, &repo_config))
+    } else {
+        active
+    }
+}
 
 pub fn scan_text(text: &str) -> Vec<SecretMatch> {
-    let rules = *RULES;
-    
+    scan_with(&active_rules(), text, None)
+}
+
+/// 使用指定规则扫描，包括编码片段中的密钥。`path` 为文本所属文件的相对路径，用于规则和 allowlist 的路径条件
+pub fn scan_with(rules: &RuleSet, text: &str, path: Option<&str>) -> Vec<SecretMatch> {
+    let mut matches = find_matches(rules, text, path);
+
+    let mut budget = decode::DecodeBudget::default();
+    let decoded = decode::scan_decoded(text, decode::MAX_DECODE_DEPTH, &mut budget, &|decoded| find_matches(rules, decoded, path));
+    if !decoded.is_empty() {
+        matches.extend(decoded);
+        matches = finalize_matches(matches);
+    }
+
+    let mut cursor = TextCursor::default();
+    for m in &mut matches {
+        enrich_context(text, m, &mut cursor);
+    }
+    matches
+}
+
+/// 按片段并行扫描原文（不解码），结果已去重，行号和代码片段尚未计算
+fn find_matches(rules: &RuleSet, text: &str, path: Option<&str>) -> Vec<SecretMatch> {
     const FRAGMENT_SIZE: usize = 16 * 1024;
     const OVERLAP: usize = 512;
     let step = FRAGMENT_SIZE.saturating_sub(OVERLAP);
@@ -52,12 +132,8 @@ pub fn scan_text(text: &str) -> Vec<SecretMatch> {
 
     if total_len <= FRAGMENT_SIZE {
         let mut matches = Vec::new();
-        scan_fragment(text, 0, rules, &mut matches);
-        let mut final_matches = finalize_matches(matches);
-        for m in &mut final_matches {
-            enrich_context(text, m);
-        }
-        return final_matches;
+        scan_fragment(text, 0, rules, path, &mut matches);
+        return finalize_matches(matches);
     }
 
     let chunk_starts: Vec<usize> = (0..total_len).step_by(step).collect();
@@ -69,18 +145,18 @@ pub fn scan_text(text: &str) -> Vec<SecretMatch> {
 
             match str::from_utf8(chunk) {
                 Ok(fragment_str) => {
-                    scan_fragment(fragment_str, start, rules, &mut local_matches);
+                    scan_fragment(fragment_str, start, rules, path, &mut local_matches);
                 }
                 Err(e) => {
                     let valid_up_to = e.valid_up_to();
                     if valid_up_to == 0 && start + 4 < total_len {
                         if let Ok(sub_str) = str::from_utf8(&chunk[1..]) {
-                             scan_fragment(sub_str, start + 1, rules, &mut local_matches);
+                             scan_fragment(sub_str, start + 1, rules, path, &mut local_matches);
                         }
                     } else {
                         let valid_chunk = &chunk[..valid_up_to];
                         if let Ok(fragment_str) = str::from_utf8(valid_chunk) {
-                            scan_fragment(fragment_str, start, rules, &mut local_matches);
+                            scan_fragment(fragment_str, start, rules, path, &mut local_matches);
                         }
                     }
                 }
@@ -89,23 +165,43 @@ pub fn scan_text(text: &str) -> Vec<SecretMatch> {
         })
         .collect();
 
-    let mut unique_matches = finalize_matches(matches);
+    finalize_matches(matches)
+}
 
-    for m in &mut unique_matches {
-        enrich_context(text, m);
-    }
+/// 结果按位置排序后依次计算行号和 UTF-16 下标，只需扫描一遍全文（大文件中每个结果都从头数会退化成平方级）
+#[derive(Default)]
+struct TextCursor {
+    offset: usize,
+    utf16: usize,
+    /// `offset` 之前的换行数
+    newlines: usize,
+    /// `offset` 所在行的起始位置
+    line_start: usize,
+}
 
-    unique_matches
+impl TextCursor {
+    fn advance(&mut self, text: &str, to: usize) {
+        if to < self.offset {
+            *self = Self::default();
+        }
+        let skipped = &text[self.offset..to];
+        self.utf16 += skipped.encode_utf16().count();
+        self.newlines += skipped.bytes().filter(|&b| b == b'\n').count();
+        if let Some(i) = skipped.rfind('\n') {
+            self.line_start = self.offset + i + 1;
+        }
+        self.offset = to;
+    }
 }
 
-fn enrich_context(full_text: &str, m: &mut SecretMatch) {
-    let prefix = &full_text[..m.index];
-    m.utf16_index = prefix.encode_utf16().count();
+fn enrich_context(full_text: &str, m: &mut SecretMatch, cursor: &mut TextCursor) {
+    cursor.advance(full_text, m.index);
+    m.utf16_index = cursor.utf16;
 
-    let match_line_num = prefix.bytes().filter(|&b| b == b'\n').count() + 1;
+    let match_line_num = cursor.newlines + 1;
     m.line_number = match_line_num;
 
-    let match_line_start = prefix.rfind('\n').map(|i| i + 1).unwrap_or(0);
+    let match_line_start = cursor.line_start;
 
     let mut snippet_start = match_line_start;
     let mut lines_back = 0;
@@ -117,7 +213,7 @@ fn enrich_context(full_text: &str, m: &mut SecretMatch) {
     }
     m.snippet_start_line = match_line_num - lines_back;
 
-    let match_end = m.index + m.value.len();
+    let match_end = m.index + m.matched_text().len();
     let mut snippet_end = match_end;
     for _ in 0..3 {
         if let Some(next_nl) = full_text[snippet_end..].find('\n') {
@@ -131,15 +227,25 @@ fn enrich_context(full_text: &str, m: &mut SecretMatch) {
     m.snippet = full_text[snippet_start..snippet_end].trim_end().to_string();
 }
 
-fn scan_fragment(fragment_str: &str, base_offset: usize, rules: &[Rule], matches: &mut Vec<SecretMatch>) {
-    for rule in rules {
-        if !rule.keywords.is_empty() && !rule.keywords.iter().any(|kw| fragment_str.contains(kw)) {
-            continue;
+/// 只对预筛选出的候选规则执行正则
+fn scan_fragment(fragment_str: &str, base_offset: usize, rules: &RuleSet, path: Option<&str>, matches: &mut Vec<SecretMatch>) {
+    for index in rules.prefilter().candidates(fragment_str) {
+        let rule = &rules.rules[index];
+        if let Some(path_re) = &rule.path {
+            if !path.is_some_and(|p| path_re.is_match(p)) {
+                continue;
+            }
         }
 
+        let global_allowlists: Vec<_> = rules.allowlists.iter().filter(|a| a.applies_to(&rule.id)).collect();
+
         for cap in rule.regex.captures_iter(fragment_str) {
-            let m = cap.name("secret").or_else(|| cap.get(0));
+            let m = match rule.secret_group {
+                Some(group) => cap.get(group),
+                None => cap.name("secret").or_else(|| cap.get(0)),
+            };
             let Some(secret_match) = m else { continue };
+            let Some(whole_match) = cap.get(0) else { continue };
 
             let secret = secret_match.as_str();
             
@@ -154,18 +260,36 @@ fn scan_fragment(fragment_str: &str, base_offset: usize, rules: &[Rule], matches
                 }
             }
 
+            let line_start = fragment_str[..whole_match.start()].rfind('\n').map(|i| i + 1).unwrap_or(0);
+            let line_end = fragment_str[whole_match.end()..].find('\n').map(|i| whole_match.end() + i).unwrap_or(fragment_str.len());
+            let line = &fragment_str[line_start..line_end];
+
+            if !rule.allowlists.is_empty() || !global_allowlists.is_empty() {
+                let candidate = Candidate {
+                    secret,
+                    matched: whole_match.as_str(),
+                    line,
+                    path,
+                };
+                if rule.allowlists.iter().chain(global_allowlists.iter().copied()).any(|a| a.allows(&candidate)) {
+                    continue;
+                }
+            }
+
             let start_in_fragment = secret_match.start();
             let global_index = base_offset + start_in_fragment;
 
             matches.push(SecretMatch {
-                kind: rule.id.to_string(),
+                kind: rule.id.clone(),
                 value: secret.to_string(),
                 index: global_index,
-                risk_level: "High".to_string(),
+                risk_level: rule.severity,
+                confidence: confidence::score(rule, secret, line, (secret_match.start() - line_start, secret_match.end() - line_start), path),
                 utf16_index: 0,
                 line_number: 0,
                 snippet: String::new(),
                 snippet_start_line: 0,
+                encoded: None,
             });
         }
     }
@@ -174,8 +298,11 @@ fn scan_fragment(fragment_str: &str, base_offset: usize, rules: &[Rule], matches
 fn finalize_matches(mut matches: Vec<SecretMatch>) -> Vec<SecretMatch> {
     if matches.is_empty() { return matches; }
 
+    // 同一位置多条规则命中时保留原文中最长、其次置信度最高的那条
     matches.sort_by(|a, b| {
-        a.index.cmp(&b.index).then_with(|| b.value.len().cmp(&a.value.len())) 
+        a.index.cmp(&b.index)
+            .then_with(|| b.matched_text().len().cmp(&a.matched_text().len()))
+            .then_with(|| b.confidence.cmp(&a.confidence))
     });
 
     let mut unique_matches = Vec::new();
@@ -183,7 +310,7 @@ fn finalize_matches(mut matches: Vec<SecretMatch>) -> Vec<SecretMatch> {
 
     for m in matches {
         let start = m.index;
-        let len = m.value.len();
+        let len = m.matched_text().len();
         let end = start + len;
 
         if start < last_end {
@@ -0,0 +1,177 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::OnceLock;
+use aho_corasick::{AhoCorasick, AhoCorasickBuilder};
+use regex::{RegexSet, RegexSetBuilder};
+use super::ruleset::CompiledRule;
+
+// ============================================================================
+// Rule Prefilter
+// ============================================================================
+//
+// 扫描片段前先挑出可能命中的规则，只对这些规则执行正则：
+//   - 所有规则的关键字（转成小写）构建成一个 Aho-Corasick 自动机，对片段做一次不区分大小写的匹配，
+//     命中的关键字对应的规则成为候选
+//   - 没有关键字的规则无法按关键字筛选，它们的正则每 REGEX_SET_GROUP 条合并成一个 RegexSet，
+//     一次扫描得出哪些规则有命中
+// 候选规则按在 RuleSet 中的顺序返回，与逐条扫描时的结果顺序一致。
+
+/// 这些规则大多是「字符集{长度}」的形式，全部合并后 DFA 状态数爆炸，遇到压缩代码等长串高熵文本时
+/// 惰性 DFA 缓存反复清空，比逐条执行还慢；分成小组后在各种输入上都稳定（见 benches/secret_scan.rs）
+const REGEX_SET_GROUP: usize = 8;
+/// RegexSet 编译大小上限，超出时该组规则退回到逐条执行
+const REGEX_SET_SIZE_LIMIT: usize = 64 * 1024 * 1024;
+
+/// 一组没有关键字的规则
+struct RuleGroup {
+    rules: Vec<usize>,
+    /// 与 `rules` 一一对应；为 None 时这些规则总是作为候选
+    set: Option<RegexSet>,
+}
+
+pub struct Prefilter {
+    rule_count: usize,
+    /// 所有去重后的小写关键字；构建失败时为 None，带关键字的规则全部作为候选
+    keywords: Option<AhoCorasick>,
+    /// 关键字（模式编号）→ 规则下标
+    keyword_rules: Vec<Vec<usize>>,
+    /// 关键字包含非 ASCII 字符时，片段先整体转成小写再匹配
+    lowercase_text: bool,
+    /// 没有关键字的规则
+    unconditional: Vec<RuleGroup>,
+}
+
+impl Prefilter {
+    pub fn new(rules: &[CompiledRule]) -> Self {
+        let mut patterns: Vec<String> = Vec::new();
+        let mut pattern_ids: HashMap<String, usize> = HashMap::new();
+        let mut keyword_rules: Vec<Vec<usize>> = Vec::new();
+        let mut unconditional = Vec::new();
+
+        for (index, rule) in rules.iter().enumerate() {
+            let keywords: Vec<String> = rule.keywords.iter().map(|k| k.to_lowercase()).collect();
+            // 与旧版 `contains` 判断一致：空关键字总是命中
+            if keywords.is_empty() || keywords.iter().any(|k| k.is_empty()) {
+                unconditional.push(index);
+                continue;
+            }
+            for keyword in keywords {
+                let id = *pattern_ids.entry(keyword.clone()).or_insert_with(|| {
+                    patterns.push(keyword);
+                    keyword_rules.push(Vec::new());
+                    patterns.len() - 1
+                });
+                if keyword_rules[id].last() != Some(&index) {
+                    keyword_rules[id].push(index);
+                }
+            }
+        }
+
+        let lowercase_text = patterns.iter().any(|p| !p.is_ascii());
+        let keywords = if patterns.is_empty() {
+            None
+        } else {
+            AhoCorasickBuilder::new()
+                .ascii_case_insensitive(true)
+                .build(&patterns)
+                .map_err(|e| eprintln!("[Secrets] Keyword prefilter disabled: {}", e))
+                .ok()
+        };
+        let unconditional = unconditional.chunks(REGEX_SET_GROUP)
+            .map(|group| RuleGroup {
+                rules: group.to_vec(),
+                set: RegexSetBuilder::new(group.iter().map(|&i| rules[i].regex.as_str()))
+                    .size_limit(REGEX_SET_SIZE_LIMIT)
+                    .build()
+                    .map_err(|e| eprintln!("[Secrets] Regex prefilter disabled for {} rules: {}", group.len(), e))
+                    .ok(),
+            })
+            .collect();
+
+        Self {
+            rule_count: rules.len(),
+            keywords,
+            keyword_rules,
+            lowercase_text,
+            unconditional,
+        }
+    }
+
+    /// 片段中可能命中的规则下标，升序
+    pub fn candidates(&self, fragment: &str) -> Vec<usize> {
+        let mut hit = vec![false; self.rule_count];
+
+        match &self.keywords {
+            Some(automaton) => {
+                let lowered;
+                let haystack = if self.lowercase_text {
+                    lowered = fragment.to_lowercase();
+                    lowered.as_str()
+                } else {
+                    fragment
+                };
+                // 关键字之间可能互相包含（如 `key` 和 `api_key`），需要重叠匹配；所有关键字都出现过后提前结束
+                let mut seen = vec![false; self.keyword_rules.len()];
+                let mut remaining = seen.len();
+                for m in automaton.find_overlapping_iter(haystack) {
+                    let pattern = m.pattern().as_usize();
+                    if seen[pattern] {
+                        continue;
+                    }
+                    seen[pattern] = true;
+                    for &index in &self.keyword_rules[pattern] {
+                        hit[index] = true;
+                    }
+                    remaining -= 1;
+                    if remaining == 0 {
+                        break;
+                    }
+                }
+            }
+            None => {
+                for &index in self.keyword_rules.iter().flatten() {
+                    hit[index] = true;
+                }
+            }
+        }
+
+        for group in &self.unconditional {
+            match &group.set {
+                Some(set) => {
+                    for i in set.matches(fragment).iter() {
+                        hit[group.rules[i]] = true;
+                    }
+                }
+                None => {
+                    for &index in &group.rules {
+                        hit[index] = true;
+                    }
+                }
+            }
+        }
+
+        hit.iter().enumerate().filter(|(_, &h)| h).map(|(i, _)| i).collect()
+    }
+}
+
+/// `RuleSet` 中缓存的预筛选器：第一次扫描时构建，克隆后清空（克隆通常是为了修改规则）
+#[derive(Default)]
+pub struct PrefilterCache(OnceLock<Prefilter>);
+
+impl PrefilterCache {
+    pub fn get_or_build(&self, rules: &[CompiledRule]) -> &Prefilter {
+        self.0.get_or_init(|| Prefilter::new(rules))
+    }
+}
+
+impl Clone for PrefilterCache {
+    fn clone(&self) -> Self {
+        Self::default()
+    }
+}
+
+impl fmt::Debug for PrefilterCache {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(if self.0.get().is_some() { "PrefilterCache(built)" } else { "PrefilterCache(empty)" })
+    }
+}
@@ -0,0 +1,383 @@
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+use entropy::shannon_entropy;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use super::directory::FileSecretMatch;
+use super::history::HistoryFinding;
+use super::{RuleSet, SecretMatch, Severity};
+
+// ============================================================================
+// Secret Scan Reports
+// ============================================================================
+//
+// 把扫描结果导出为 SARIF 2.1.0、gitleaks 兼容 JSON（`gitleaks --report-format json`）或 CSV，
+// 方便导入 CI 扫描器使用的同一套工具。报告中不出现密钥原文：Secret 字段固定为 REDACTED，
+// 片段只包含密钥所在的那一行（上下文行里可能还有别的敏感值），其中的密钥同样替换为 REDACTED。
+
+const REDACTED: &str = "REDACTED";
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ReportFormat {
+    Sarif,
+    Json,
+    Csv,
+}
+
+/// 一条待导出的结果。前端直接传入 FileSecretMatch / HistoryFinding，多余字段忽略
+#[derive(Debug, Clone, Deserialize)]
+pub struct ReportFinding {
+    #[serde(flatten)]
+    pub secret: SecretMatch,
+    #[serde(default)]
+    pub path: String,
+    #[serde(default)]
+    pub commit: Option<String>,
+    #[serde(default)]
+    pub author: String,
+    #[serde(default)]
+    pub email: String,
+    #[serde(default)]
+    pub date: String,
+    #[serde(default)]
+    pub message: String,
+}
+
+impl From<FileSecretMatch> for ReportFinding {
+    fn from(f: FileSecretMatch) -> Self {
+        Self {
+            secret: f.secret,
+            path: f.path,
+            commit: None,
+            author: String::new(),
+            email: String::new(),
+            date: String::new(),
+            message: String::new(),
+        }
+    }
+}
+
+impl From<HistoryFinding> for ReportFinding {
+    fn from(f: HistoryFinding) -> Self {
+        Self {
+            secret: f.secret,
+            path: f.path,
+            commit: Some(f.commit),
+            author: f.author,
+            email: f.email,
+            date: f.date,
+            message: f.message,
+        }
+    }
+}
+
+/// 行列号均从 1 开始；`end_column` 为最后一个字符之后的列（与 SARIF 一致）
+struct Region {
+    start_line: usize,
+    end_line: usize,
+    start_column: usize,
+    end_column: usize,
+    /// 密钥所在行，密钥已替换为 REDACTED
+    line: String,
+}
+
+/// 与 gitleaks 相同的格式：`file:rule:line`，历史结果前面加上提交 `commit:`。
+/// 可以直接写入 .gitleaksignore
+pub fn fingerprint(path: &str, rule_id: &str, line: usize, commit: Option<&str>) -> String {
+    match commit {
+        Some(commit) => format!("{}:{}:{}:{}", commit, path, rule_id, line),
+        None => format!("{}:{}:{}", path, rule_id, line),
+    }
+}
+
+impl ReportFinding {
+    pub fn fingerprint(&self) -> String {
+        fingerprint(&self.path, &self.secret.kind, self.secret.line_number, self.commit.as_deref())
+    }
+
+    /// 多行密钥（私钥等）的每一行都替换掉；编码中发现的密钥替换整个编码片段
+    fn redact(&self, text: &str) -> String {
+        self.secret.matched_text().lines()
+            .filter(|l| !l.trim().is_empty())
+            .fold(text.to_string(), |acc, part| acc.replace(part, REDACTED))
+    }
+
+    /// 列号从片段中密钥所在的那一行计算，找不到时为 1
+    fn region(&self) -> Region {
+        let secret = &self.secret;
+        let line = secret.snippet.lines()
+            .nth(secret.line_number.saturating_sub(secret.snippet_start_line))
+            .unwrap_or("");
+        let value_lines: Vec<&str> = secret.matched_text().lines().collect();
+        let first = value_lines.first().copied().unwrap_or("");
+        let start_column = line.find(first).map(|i| line[..i].chars().count() + 1).unwrap_or(1);
+        let end_column = match value_lines.len() {
+            0 | 1 => start_column + first.chars().count(),
+            _ => value_lines.last().map(|l| l.chars().count() + 1).unwrap_or(1),
+        };
+        Region {
+            start_line: secret.line_number,
+            end_line: secret.line_number + value_lines.len().saturating_sub(1),
+            start_column,
+            end_column,
+            line: self.redact(line).trim().to_string(),
+        }
+    }
+}
+
+fn description<'a>(rules: &'a RuleSet, rule_id: &'a str) -> &'a str {
+    rules.rules.iter()
+        .find(|r| r.id == rule_id)
+        .map(|r| r.description.as_str())
+        .unwrap_or(rule_id)
+}
+
+fn sarif_level(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Critical | Severity::High => "error",
+        Severity::Medium => "warning",
+        Severity::Low => "note",
+    }
+}
+
+/// gitleaks 报告里的 Tags，用来带上严重程度和置信度；解码后发现的结果与 gitleaks 一样带上
+/// `decoded:<编码>` 和 `decode-depth:<层数>`
+fn tags(secret: &SecretMatch) -> Vec<String> {
+    let mut tags = vec![
+        format!("severity:{:?}", secret.risk_level).to_lowercase(),
+        format!("confidence:{}", secret.confidence),
+    ];
+    if let Some(encoded) = &secret.encoded {
+        tags.extend(encoded.encodings.iter().map(|e| format!("decoded:{:?}", e).to_lowercase()));
+        tags.push(format!("decode-depth:{}", encoded.encodings.len()));
+    }
+    tags
+}
+
+fn write_sarif(findings: &[ReportFinding], rules: &RuleSet, out: &mut dyn Write) -> Result<(), String> {
+    // 只列出结果中出现过的规则，ruleIndex 指向这里的下标
+    let mut rule_ids: Vec<&str> = Vec::new();
+    for f in findings {
+        if !rule_ids.contains(&f.secret.kind.as_str()) {
+            rule_ids.push(&f.secret.kind);
+        }
+    }
+    let sarif_rules: Vec<_> = rule_ids.iter()
+        .map(|id| json!({
+            "id": id,
+            "name": id,
+            "shortDescription": { "text": description(rules, id) },
+        }))
+        .collect();
+
+    let results: Vec<_> = findings.iter()
+        .map(|f| {
+            let region = f.region();
+            let mut partial = json!({ "ctxrunFingerprint/v1": f.fingerprint() });
+            if let Some(commit) = &f.commit {
+                partial["commitSha"] = json!(commit);
+                partial["author"] = json!(f.author);
+                partial["email"] = json!(f.email);
+                partial["date"] = json!(f.date);
+                partial["commitMessage"] = json!(f.message);
+            }
+            json!({
+                "ruleId": f.secret.kind,
+                "ruleIndex": rule_ids.iter().position(|id| *id == f.secret.kind),
+                "level": sarif_level(f.secret.risk_level),
+                "message": { "text": format!("{} has detected a secret in {}", f.secret.kind, f.path) },
+                "locations": [{
+                    "physicalLocation": {
+                        "artifactLocation": { "uri": f.path },
+                        "region": {
+                            "startLine": region.start_line,
+                            "startColumn": region.start_column,
+                            "endLine": region.end_line,
+                            "endColumn": region.end_column,
+                            "snippet": { "text": region.line },
+                        },
+                    },
+                }],
+                "partialFingerprints": partial,
+                "properties": {
+                    "severity": f.secret.risk_level,
+                    "confidence": f.secret.confidence,
+                    "tags": tags(&f.secret),
+                },
+            })
+        })
+        .collect();
+
+    let report = json!({
+        "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": "CtxRun",
+                    "semanticVersion": env!("CARGO_PKG_VERSION"),
+                    "rules": sarif_rules,
+                },
+            },
+            "results": results,
+        }],
+    });
+    serde_json::to_writer_pretty(&mut *out, &report).map_err(|e| e.to_string())?;
+    writeln!(out).map_err(|e| e.to_string())
+}
+
+/// `gitleaks --report-format json` 的字段
+#[derive(Serialize)]
+#[serde(rename_all = "PascalCase")]
+struct GitleaksFinding<'a> {
+    #[serde(rename = "RuleID")]
+    rule_id: &'a str,
+    description: &'a str,
+    start_line: usize,
+    end_line: usize,
+    start_column: usize,
+    end_column: usize,
+    #[serde(rename = "Match")]
+    match_: String,
+    secret: &'static str,
+    file: &'a str,
+    symlink_file: &'static str,
+    commit: &'a str,
+    entropy: f32,
+    author: &'a str,
+    email: &'a str,
+    date: &'a str,
+    message: &'a str,
+    tags: Vec<String>,
+    fingerprint: String,
+}
+
+fn gitleaks_finding<'a>(f: &'a ReportFinding, rules: &'a RuleSet) -> GitleaksFinding<'a> {
+    let region = f.region();
+    GitleaksFinding {
+        rule_id: &f.secret.kind,
+        description: description(rules, &f.secret.kind),
+        start_line: region.start_line,
+        end_line: region.end_line,
+        start_column: region.start_column,
+        end_column: region.end_column,
+        match_: region.line,
+        secret: REDACTED,
+        file: &f.path,
+        symlink_file: "",
+        commit: f.commit.as_deref().unwrap_or(""),
+        entropy: shannon_entropy(&f.secret.value),
+        author: &f.author,
+        email: &f.email,
+        date: &f.date,
+        message: &f.message,
+        tags: tags(&f.secret),
+        fingerprint: f.fingerprint(),
+    }
+}
+
+fn write_json(findings: &[ReportFinding], rules: &RuleSet, out: &mut dyn Write) -> Result<(), String> {
+    let rows: Vec<_> = findings.iter().map(|f| gitleaks_finding(f, rules)).collect();
+    serde_json::to_writer_pretty(&mut *out, &rows).map_err(|e| e.to_string())?;
+    writeln!(out).map_err(|e| e.to_string())
+}
+
+/// 列顺序与 `gitleaks --report-format csv` 相同，额外加了 Description
+#[derive(Serialize)]
+struct CsvRow<'a> {
+    #[serde(rename = "RuleID")]
+    rule_id: &'a str,
+    #[serde(rename = "Description")]
+    description: &'a str,
+    #[serde(rename = "Commit")]
+    commit: &'a str,
+    #[serde(rename = "File")]
+    file: &'a str,
+    #[serde(rename = "SymlinkFile")]
+    symlink_file: &'static str,
+    #[serde(rename = "Secret")]
+    secret: &'static str,
+    #[serde(rename = "Match")]
+    match_: String,
+    #[serde(rename = "StartLine")]
+    start_line: usize,
+    #[serde(rename = "EndLine")]
+    end_line: usize,
+    #[serde(rename = "StartColumn")]
+    start_column: usize,
+    #[serde(rename = "EndColumn")]
+    end_column: usize,
+    #[serde(rename = "Author")]
+    author: &'a str,
+    #[serde(rename = "Message")]
+    message: &'a str,
+    #[serde(rename = "Date")]
+    date: &'a str,
+    #[serde(rename = "Email")]
+    email: &'a str,
+    #[serde(rename = "Fingerprint")]
+    fingerprint: String,
+    /// 与 gitleaks 一致，多个标签用空格分隔
+    #[serde(rename = "Tags")]
+    tags: String,
+}
+
+fn write_csv(findings: &[ReportFinding], rules: &RuleSet, out: &mut dyn Write) -> Result<(), String> {
+    // 报告给扫描工具读取，不写 Excel 用的 BOM
+    let mut wtr = csv::WriterBuilder::new().has_headers(true).from_writer(out);
+    for f in findings {
+        let g = gitleaks_finding(f, rules);
+        wtr.serialize(CsvRow {
+            rule_id: g.rule_id,
+            description: g.description,
+            commit: g.commit,
+            file: g.file,
+            symlink_file: g.symlink_file,
+            secret: g.secret,
+            match_: g.match_,
+            start_line: g.start_line,
+            end_line: g.end_line,
+            start_column: g.start_column,
+            end_column: g.end_column,
+            author: g.author,
+            message: g.message,
+            date: g.date,
+            email: g.email,
+            fingerprint: g.fingerprint,
+            tags: g.tags.join(" "),
+        }).map_err(|e| e.to_string())?;
+    }
+    wtr.flush().map_err(|e| e.to_string())
+}
+
+/// `rules` 只用于查找规则描述
+pub fn write_report(findings: &[ReportFinding], format: ReportFormat, rules: &RuleSet, out: &mut dyn Write) -> Result<(), String> {
+    match format {
+        ReportFormat::Sarif => write_sarif(findings, rules, out),
+        ReportFormat::Json => write_json(findings, rules, out),
+        ReportFormat::Csv => write_csv(findings, rules, out),
+    }
+}
+
+/// 导出扫描结果，返回写入的结果数
+#[tauri::command]
+pub async fn export_secret_report(
+    findings: Vec<ReportFinding>,
+    format: ReportFormat,
+    save_path: String,
+    project_root: Option<String>
+) -> Result<usize, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let rules = match &project_root {
+            Some(root) => super::rules_for_repo(Path::new(root)),
+            None => super::active_rules(),
+        };
+        let file = File::create(&save_path).map_err(|e| e.to_string())?;
+        let mut out = BufWriter::new(file);
+        write_report(&findings, format, &rules, &mut out)?;
+        out.flush().map_err(|e| e.to_string())?;
+        Ok(findings.len())
+    }).await.map_err(|e| e.to_string())?
+}
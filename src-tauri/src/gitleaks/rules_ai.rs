@@ -1,5 +1,5 @@
 use regex::Regex;
-use super::Rule;
+use super::{Confidence, Rule, Severity};
 
 pub fn ai_rules() -> Vec<Rule> {
     vec![
@@ -10,6 +10,8 @@ pub fn ai_rules() -> Vec<Rule> {
             regex: Regex::new(r"sk-[0-9a-zA-Z]{48}").unwrap(),
             entropy: Some(3.5),
             keywords: &["sk-"],
+            severity: Severity::High,
+            confidence: Confidence::Medium,
         },
         // OpenAI Organization ID (optional but often leaked)
         Rule {
@@ -18,6 +20,8 @@ pub fn ai_rules() -> Vec<Rule> {
             regex: Regex::new(r"org-[0-9a-zA-Z]{24}").unwrap(),
             entropy: Some(3.0),
             keywords: &["org-"],
+            severity: Severity::Low,
+            confidence: Confidence::Medium,
         },
 
         // Anthropic API Key
@@ -27,6 +31,8 @@ pub fn ai_rules() -> Vec<Rule> {
             regex: Regex::new(r"sk-ant-[0-9a-zA-Z]{48,}").unwrap(),
             entropy: Some(3.5),
             keywords: &["sk-ant-"],
+            severity: Severity::High,
+            confidence: Confidence::High,
         },
 
         // Cohere API Key
@@ -36,6 +42,8 @@ pub fn ai_rules() -> Vec<Rule> {
             regex: Regex::new(r"[0-9a-zA-Z]{40}").unwrap(),
             entropy: Some(3.5),
             keywords: &[],
+            severity: Severity::High,
+            confidence: Confidence::Low,
         },
 
         // Hugging Face Access Token
@@ -45,6 +53,8 @@ pub fn ai_rules() -> Vec<Rule> {
             regex: Regex::new(r"hf_[0-9a-zA-Z]{40}").unwrap(),
             entropy: Some(3.5),
             keywords: &["hf_"],
+            severity: Severity::High,
+            confidence: Confidence::High,
         },
 
         // Perplexity API Key
@@ -54,6 +64,8 @@ pub fn ai_rules() -> Vec<Rule> {
             regex: Regex::new(r"pplx-[0-9a-zA-Z]{40,}").unwrap(),
             entropy: Some(3.5),
             keywords: &["pplx-"],
+            severity: Severity::High,
+            confidence: Confidence::High,
         },
 
         // PrivateAI API Key (private.ai)
@@ -63,6 +75,8 @@ pub fn ai_rules() -> Vec<Rule> {
             regex: Regex::new(r"pk_live_[0-9a-zA-Z]{40,}").unwrap(),
             entropy: Some(3.5),
             keywords: &["pk_live_"],
+            severity: Severity::High,
+            confidence: Confidence::High,
         },
 
         // DeepSeek API Key
@@ -72,6 +86,8 @@ pub fn ai_rules() -> Vec<Rule> {
             regex: Regex::new(r"sk-[0-9a-zA-Z]{32}").unwrap(),
             entropy: Some(3.2),
             keywords: &["sk-"],
+            severity: Severity::High,
+            confidence: Confidence::Medium,
         },
     ]
 }
\ No newline at end of file
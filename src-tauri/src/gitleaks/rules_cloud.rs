@@ -1,5 +1,5 @@
 use regex::Regex;
-use super::Rule;
+use super::{Confidence, Rule, Severity};
 
 pub fn cloud_rules() -> Vec<Rule> {
     vec![
@@ -10,6 +10,8 @@ pub fn cloud_rules() -> Vec<Rule> {
             regex: Regex::new(r"(AKIA|ASIA|AROA|AIDA)[0-9A-Z]{16}").unwrap(),
             entropy: Some(3.0),
             keywords: &["akia", "asia", "aroa", "aida"],
+            severity: Severity::Medium,
+            confidence: Confidence::High,
         },
         // AWS Secret Access Key
         Rule {
@@ -18,6 +20,8 @@ pub fn cloud_rules() -> Vec<Rule> {
             regex: Regex::new(r"[0-9a-zA-Z/+]{40}").unwrap(),
             entropy: Some(3.5),
             keywords: &[],
+            severity: Severity::Critical,
+            confidence: Confidence::Low,
         },
         // AWS Session Token (temporary credentials)
         Rule {
@@ -26,6 +30,8 @@ pub fn cloud_rules() -> Vec<Rule> {
             regex: Regex::new(r"(?i)AQoDYXdzE[0-9a-zA-Z/+]{100,}={0,2}").unwrap(),
             entropy: Some(3.2),
             keywords: &["aqodyxdze", "aqodyxdz"],
+            severity: Severity::High,
+            confidence: Confidence::High,
         },
         // AWS MWS Key
         Rule {
@@ -34,6 +40,8 @@ pub fn cloud_rules() -> Vec<Rule> {
             regex: Regex::new(r"amzn\.mws\.[0-9a-f]{8}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{12}").unwrap(),
             entropy: Some(3.0),
             keywords: &["amzn.mws."],
+            severity: Severity::High,
+            confidence: Confidence::High,
         },
 
         // Azure Client ID / Application ID
@@ -43,6 +51,8 @@ pub fn cloud_rules() -> Vec<Rule> {
             regex: Regex::new(r"[0-9a-f]{8}-([0-9a-f]{4}-){3}[0-9a-f]{12}").unwrap(),
             entropy: Some(2.8),
             keywords: &[],
+            severity: Severity::Low,
+            confidence: Confidence::Low,
         },
         // Azure Tenant ID
         Rule {
@@ -51,6 +61,8 @@ pub fn cloud_rules() -> Vec<Rule> {
             regex: Regex::new(r"[0-9a-f]{8}-([0-9a-f]{4}-){3}[0-9a-f]{12}").unwrap(),
             entropy: Some(2.8),
             keywords: &[],
+            severity: Severity::Low,
+            confidence: Confidence::Low,
         },
         // Azure Client Secret
         Rule {
@@ -59,6 +71,8 @@ pub fn cloud_rules() -> Vec<Rule> {
             regex: Regex::new(r"[~]{0,1}[0-9a-zA-Z+/=]{44}").unwrap(),
             entropy: Some(3.5),
             keywords: &[],
+            severity: Severity::Critical,
+            confidence: Confidence::Low,
         },
 
         // Google Cloud API Key
@@ -68,6 +82,8 @@ pub fn cloud_rules() -> Vec<Rule> {
             regex: Regex::new(r"AIza[0-9A-Za-z\\-_]{35}").unwrap(),
             entropy: Some(3.2),
             keywords: &["aiza"],
+            severity: Severity::Medium,
+            confidence: Confidence::High,
         },
         // Google OAuth Access Token
         Rule {
@@ -76,6 +92,8 @@ pub fn cloud_rules() -> Vec<Rule> {
             regex: Regex::new(r"ya29\.[0-9A-Za-z\-_]+").unwrap(),
             entropy: Some(3.5),
             keywords: &["ya29."],
+            severity: Severity::High,
+            confidence: Confidence::High,
         },
         // Google Service Account Key
         Rule {
@@ -84,6 +102,8 @@ pub fn cloud_rules() -> Vec<Rule> {
             regex: Regex::new(r#""type": "service_account""#).unwrap(),
             entropy: None,
             keywords: &["service_account"],
+            severity: Severity::Critical,
+            confidence: Confidence::High,
         },
 
         // DigitalOcean Personal Access Token
@@ -93,6 +113,8 @@ pub fn cloud_rules() -> Vec<Rule> {
             regex: Regex::new(r"do[v0][0-9a-zA-Z]{40}").unwrap(),
             entropy: Some(3.5),
             keywords: &["dov0"],
+            severity: Severity::High,
+            confidence: Confidence::High,
         },
 
         // Heroku API Key
@@ -102,6 +124,8 @@ pub fn cloud_rules() -> Vec<Rule> {
             regex: Regex::new(r"[0-9a-f]{8}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{12}").unwrap(),
             entropy: Some(3.0),
             keywords: &[],
+            severity: Severity::High,
+            confidence: Confidence::Low,
         },
 
         // Cloudflare API Key / Global API Key
@@ -111,6 +135,8 @@ pub fn cloud_rules() -> Vec<Rule> {
             regex: Regex::new(r"[0-9a-z]{37}").unwrap(),
             entropy: Some(3.2),
             keywords: &[],
+            severity: Severity::High,
+            confidence: Confidence::Low,
         },
         // Cloudflare API Token
         Rule {
@@ -119,6 +145,8 @@ pub fn cloud_rules() -> Vec<Rule> {
             regex: Regex::new(r"[A-Za-z0-9_\\-]{40}").unwrap(),
             entropy: Some(3.5),
             keywords: &[],
+            severity: Severity::High,
+            confidence: Confidence::Low,
         },
 
         // Alibaba Cloud Access Key ID
@@ -128,6 +156,8 @@ pub fn cloud_rules() -> Vec<Rule> {
             regex: Regex::new(r"LTAI[0-9a-zA-Z]{16}").unwrap(),
             entropy: Some(3.0),
             keywords: &["ltai"],
+            severity: Severity::High,
+            confidence: Confidence::High,
         },
         // Alibaba Cloud Access Key Secret
         Rule {
@@ -136,6 +166,8 @@ pub fn cloud_rules() -> Vec<Rule> {
             regex: Regex::new(r"[0-9a-zA-Z]{30}").unwrap(),
             entropy: Some(3.5),
             keywords: &[],
+            severity: Severity::Critical,
+            confidence: Confidence::Low,
         },
 
         // HashiCorp Terraform Cloud Token
@@ -145,6 +177,8 @@ pub fn cloud_rules() -> Vec<Rule> {
             regex: Regex::new(r"[0-9a-zA-Z]{14}\.atlasv1\.[0-9a-zA-Z+=/]{80,}").unwrap(),
             entropy: Some(3.8),
             keywords: &["atlasv1"],
+            severity: Severity::Critical,
+            confidence: Confidence::High,
         },
 
         // HashiCorp Vault Token
@@ -154,6 +188,8 @@ pub fn cloud_rules() -> Vec<Rule> {
             regex: Regex::new(r"[sb]\.[0-9a-zA-Z]{24}").unwrap(),
             entropy: Some(3.2),
             keywords: &["s.", "b."],
+            severity: Severity::Critical,
+            confidence: Confidence::Medium,
         },
     ]
 }
\ No newline at end of file
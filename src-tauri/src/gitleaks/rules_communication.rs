@@ -1,5 +1,5 @@
 use regex::Regex;
-use super::Rule;
+use super::{Confidence, Rule, Severity};
 
 pub fn communication_rules() -> Vec<Rule> {
     vec![
@@ -10,6 +10,8 @@ pub fn communication_rules() -> Vec<Rule> {
             regex: Regex::new(r"https://hooks.slack.com/services/T[A-Z0-9]{8,10}/B[A-Z0-9]{8,10}/[A-Za-z0-9]{24}").unwrap(),
             entropy: Some(3.0),
             keywords: &["hooks.slack.com/services"],
+            severity: Severity::Medium,
+            confidence: Confidence::High,
         },
         // Slack Bot Token
         Rule {
@@ -18,6 +20,8 @@ pub fn communication_rules() -> Vec<Rule> {
             regex: Regex::new(r"xoxb-[0-9]{10,13}-[0-9]{10,13}-[A-Za-z0-9]{24}").unwrap(),
             entropy: Some(3.8),
             keywords: &["xoxb-"],
+            severity: Severity::High,
+            confidence: Confidence::High,
         },
         // Slack User Token
         Rule {
@@ -26,6 +30,8 @@ pub fn communication_rules() -> Vec<Rule> {
             regex: Regex::new(r"xox[p|r|s|e]-[0-9]{10,13}-[0-9]{10,13}-[A-Za-z0-9]{24}").unwrap(),
             entropy: Some(3.8),
             keywords: &["xoxp-", "xoxr-", "xoxs-", "xoxe-"],
+            severity: Severity::High,
+            confidence: Confidence::High,
         },
 
         // Discord Bot Token
@@ -35,6 +41,8 @@ pub fn communication_rules() -> Vec<Rule> {
             regex: Regex::new(r"[MN][A-Za-z\\d]{23}\\.[XP][A-Za-z\\d]{5}\\.[A-Za-z\\d]{27}").unwrap(),
             entropy: Some(3.5),
             keywords: &[],
+            severity: Severity::High,
+            confidence: Confidence::Low,
         },
         // Discord Webhook URL
         Rule {
@@ -43,6 +51,8 @@ pub fn communication_rules() -> Vec<Rule> {
             regex: Regex::new(r"https://discord(app)?\\.com/api/webhooks/[0-9]{18,19}/[A-Za-z0-9_\\-]{68}").unwrap(),
             entropy: Some(3.8),
             keywords: &["discord.com/api/webhooks", "discordapp.com/api/webhooks"],
+            severity: Severity::Medium,
+            confidence: Confidence::High,
         },
 
         // Twilio API Key
@@ -52,6 +62,8 @@ pub fn communication_rules() -> Vec<Rule> {
             regex: Regex::new(r"SK[0-9a-fA-F]{32}").unwrap(),
             entropy: Some(3.5),
             keywords: &["SK"],
+            severity: Severity::High,
+            confidence: Confidence::Medium,
         },
 
         // Telegram Bot Token
@@ -61,6 +73,8 @@ pub fn communication_rules() -> Vec<Rule> {
             regex: Regex::new(r"[0-9]{8,10}:[A-Za-z0-9_\\-]{35}").unwrap(),
             entropy: Some(3.5),
             keywords: &[],
+            severity: Severity::High,
+            confidence: Confidence::Low,
         },
 
         // Mailgun API Key
@@ -70,6 +84,8 @@ pub fn communication_rules() -> Vec<Rule> {
             regex: Regex::new(r"key-[0-9a-zA-Z]{32}").unwrap(),
             entropy: Some(3.5),
             keywords: &["key-"],
+            severity: Severity::High,
+            confidence: Confidence::Medium,
         },
 
         // SendGrid API Key
@@ -79,6 +95,8 @@ pub fn communication_rules() -> Vec<Rule> {
             regex: Regex::new(r"SG\\.[0-9A-Za-z_\\-]{22}\\.[0-9A-Za-z_\\-]{43}").unwrap(),
             entropy: Some(3.8),
             keywords: &["SG."],
+            severity: Severity::High,
+            confidence: Confidence::High,
         },
 
         // Sendinblue (Brevo) API Key
@@ -88,6 +106,8 @@ pub fn communication_rules() -> Vec<Rule> {
             regex: Regex::new(r"xkeysib-[0-9a-f]{64}-[0-9a-f]{16}").unwrap(),
             entropy: Some(3.8),
             keywords: &["xkeysib-"],
+            severity: Severity::High,
+            confidence: Confidence::High,
         },
 
         // Mailchimp API Key
@@ -97,6 +117,8 @@ pub fn communication_rules() -> Vec<Rule> {
             regex: Regex::new(r"[0-9a-f]{32}-us[0-9]{1,2}").unwrap(),
             entropy: Some(3.5),
             keywords: &["-us"],
+            severity: Severity::High,
+            confidence: Confidence::Medium,
         },
 
         // MessageBird API Key
@@ -106,6 +128,8 @@ pub fn communication_rules() -> Vec<Rule> {
             regex: Regex::new(r"[A-Za-z0-9]{25}").unwrap(),
             entropy: Some(3.2),
             keywords: &[],
+            severity: Severity::High,
+            confidence: Confidence::Low,
         },
 
         // Mattermost Personal Access Token
@@ -115,6 +139,8 @@ pub fn communication_rules() -> Vec<Rule> {
             regex: Regex::new(r"[a-z0-9]{26}").unwrap(),
             entropy: Some(3.2),
             keywords: &[],
+            severity: Severity::High,
+            confidence: Confidence::Low,
         },
 
         // Asana Personal Access Token
@@ -124,6 +150,8 @@ pub fn communication_rules() -> Vec<Rule> {
             regex: Regex::new(r"[0-9]/[0-9a-zA-Z]{36}").unwrap(),
             entropy: Some(3.2),
             keywords: &[],
+            severity: Severity::High,
+            confidence: Confidence::Low,
         },
 
         // HubSpot API Key
@@ -133,6 +161,8 @@ pub fn communication_rules() -> Vec<Rule> {
             regex: Regex::new(r"[0-9A-F]{8}-[0-9A-F]{4}-[0-9A-F]{4}-[0-9A-F]{4}-[0-9A-F]{12}").unwrap(),
             entropy: Some(3.0),
             keywords: &[],
+            severity: Severity::High,
+            confidence: Confidence::Low,
         },
 
         // Intercom Access Token
@@ -142,6 +172,8 @@ pub fn communication_rules() -> Vec<Rule> {
             regex: Regex::new(r"[A-Za-z0-9_]{40,}").unwrap(),
             entropy: Some(3.5),
             keywords: &[],
+            severity: Severity::High,
+            confidence: Confidence::Low,
         },
     ]
 }
\ No newline at end of file
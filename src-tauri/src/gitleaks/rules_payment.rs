@@ -1,5 +1,5 @@
 use regex::Regex;
-use super::Rule;
+use super::{Confidence, Rule, Severity};
 
 pub fn payment_rules() -> Vec<Rule> {
     vec![
@@ -10,6 +10,8 @@ pub fn payment_rules() -> Vec<Rule> {
             regex: Regex::new(r"sk_(live|test)_[0-9a-zA-Z]{24}").unwrap(),
             entropy: Some(3.5),
             keywords: &["sk_live_", "sk_test_"],
+            severity: Severity::Critical,
+            confidence: Confidence::High,
         },
         // Stripe Publishable Key
         Rule {
@@ -18,6 +20,8 @@ pub fn payment_rules() -> Vec<Rule> {
             regex: Regex::new(r"pk_(live|test)_[0-9a-zA-Z]{24}").unwrap(),
             entropy: Some(3.0),
             keywords: &["pk_live_", "pk_test_"],
+            severity: Severity::Low,
+            confidence: Confidence::High,
         },
         // Stripe Restricted Key
         Rule {
@@ -26,6 +30,8 @@ pub fn payment_rules() -> Vec<Rule> {
             regex: Regex::new(r"rk_(live|test)_[0-9a-zA-Z]{24}").unwrap(),
             entropy: Some(3.5),
             keywords: &["rk_live_", "rk_test_"],
+            severity: Severity::Medium,
+            confidence: Confidence::High,
         },
 
         // Square Access Token
@@ -35,6 +41,8 @@ pub fn payment_rules() -> Vec<Rule> {
             regex: Regex::new(r"sq0atp-[0-9A-Za-z\\-_]{22}").unwrap(),
             entropy: Some(3.2),
             keywords: &["sq0atp-"],
+            severity: Severity::High,
+            confidence: Confidence::High,
         },
         // Square OAuth Secret
         Rule {
@@ -43,6 +51,8 @@ pub fn payment_rules() -> Vec<Rule> {
             regex: Regex::new(r"sq0csp-[0-9A-Za-z\\-_]{43}").unwrap(),
             entropy: Some(3.5),
             keywords: &["sq0csp-"],
+            severity: Severity::Critical,
+            confidence: Confidence::High,
         },
 
         // PayPal Braintree Access Token
@@ -52,6 +62,8 @@ pub fn payment_rules() -> Vec<Rule> {
             regex: Regex::new(r"access_token\\$production\\$[0-9a-z]{16}\\$[0-9a-f]{32}").unwrap(),
             entropy: Some(3.8),
             keywords: &["access_token$production"],
+            severity: Severity::Critical,
+            confidence: Confidence::High,
         },
 
         // Plaid Client ID / Secret
@@ -61,6 +73,8 @@ pub fn payment_rules() -> Vec<Rule> {
             regex: Regex::new(r"^[0-9a-f]{24}$").unwrap(),
             entropy: Some(3.0),
             keywords: &[],
+            severity: Severity::Medium,
+            confidence: Confidence::Low,
         },
         Rule {
             id: "plaid-secret",
@@ -68,6 +82,8 @@ pub fn payment_rules() -> Vec<Rule> {
             regex: Regex::new(r"^[0-9a-f]{30}$").unwrap(),
             entropy: Some(3.5),
             keywords: &[],
+            severity: Severity::High,
+            confidence: Confidence::Low,
         },
 
         // Coinbase API Key
@@ -77,6 +93,8 @@ pub fn payment_rules() -> Vec<Rule> {
             regex: Regex::new(r"[0-9a-f]{32}").unwrap(),
             entropy: Some(3.5),
             keywords: &[],
+            severity: Severity::High,
+            confidence: Confidence::Low,
         },
 
         // Finicity API Key
@@ -86,6 +104,8 @@ pub fn payment_rules() -> Vec<Rule> {
             regex: Regex::new(r"[0-9a-f]{32}").unwrap(),
             entropy: Some(3.5),
             keywords: &[],
+            severity: Severity::High,
+            confidence: Confidence::Low,
         },
         // Finicity App Key
         Rule {
@@ -94,6 +114,8 @@ pub fn payment_rules() -> Vec<Rule> {
             regex: Regex::new(r"[0-9a-f]{32}").unwrap(),
             entropy: Some(3.5),
             keywords: &[],
+            severity: Severity::High,
+            confidence: Confidence::Low,
         },
 
         // Flutterwave Secret Key
@@ -103,6 +125,8 @@ pub fn payment_rules() -> Vec<Rule> {
             regex: Regex::new(r"FLWSECK-[0-9a-zA-Z]{32}-X").unwrap(),
             entropy: Some(3.2),
             keywords: &["FLWSECK-"],
+            severity: Severity::Critical,
+            confidence: Confidence::High,
         },
         // Flutterwave Public Key
         Rule {
@@ -111,6 +135,8 @@ pub fn payment_rules() -> Vec<Rule> {
             regex: Regex::new(r"FLWPUBK-[0-9a-zA-Z]{32}-X").unwrap(),
             entropy: Some(3.0),
             keywords: &["FLWPUBK-"],
+            severity: Severity::Low,
+            confidence: Confidence::High,
         },
 
         // GoCardless Access Token
@@ -120,6 +146,8 @@ pub fn payment_rules() -> Vec<Rule> {
             regex: Regex::new(r"live_[0-9a-zA-Z]{40}|sandbox_[0-9a-zA-Z]{40}").unwrap(),
             entropy: Some(3.5),
             keywords: &["live_", "sandbox_"],
+            severity: Severity::Critical,
+            confidence: Confidence::Medium,
         },
     ]
 }
\ No newline at end of file
@@ -1,5 +1,5 @@
 use regex::Regex;
-use super::Rule;
+use super::{Confidence, Rule, Severity};
 
 pub fn remaining_rules() -> Vec<Rule> {
     vec![
@@ -10,6 +10,8 @@ pub fn remaining_rules() -> Vec<Rule> {
             regex: Regex::new(r"[0-9a-zA-Z]{32}").unwrap(),
             entropy: Some(3.5),
             keywords: &[],
+            severity: Severity::High,
+            confidence: Confidence::Low,
         },
 
         // GitLab Personal Access Token
@@ -19,6 +21,8 @@ pub fn remaining_rules() -> Vec<Rule> {
             regex: Regex::new(r"glpat-[0-9A-Za-z\\-_]{20}").unwrap(),
             entropy: Some(3.5),
             keywords: &["glpat-"],
+            severity: Severity::High,
+            confidence: Confidence::High,
         },
 
         // Artifactory API Key
@@ -28,6 +32,8 @@ pub fn remaining_rules() -> Vec<Rule> {
             regex: Regex::new(r"(AKC[a-zA-Z0-9]{10,})").unwrap(),
             entropy: Some(3.5),
             keywords: &["AKC"],
+            severity: Severity::High,
+            confidence: Confidence::Medium,
         },
 
         // Snyk API Token
@@ -37,6 +43,8 @@ pub fn remaining_rules() -> Vec<Rule> {
             regex: Regex::new(r"[0-9a-f]{8}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{12}").unwrap(),
             entropy: Some(3.0),
             keywords: &[],
+            severity: Severity::High,
+            confidence: Confidence::Low,
         },
 
         // Postman API Key
@@ -46,6 +54,8 @@ pub fn remaining_rules() -> Vec<Rule> {
             regex: Regex::new(r"PMAK-[0-9a-f]{24}-[0-9a-f]{12}").unwrap(),
             entropy: Some(3.5),
             keywords: &["PMAK-"],
+            severity: Severity::High,
+            confidence: Confidence::High,
         },
 
         // Notion Integration Token
@@ -55,6 +65,8 @@ pub fn remaining_rules() -> Vec<Rule> {
             regex: Regex::new(r"secret_[A-Za-z0-9]{43}").unwrap(),
             entropy: Some(3.5),
             keywords: &["secret_"],
+            severity: Severity::High,
+            confidence: Confidence::Medium,
         },
 
         // Linear API Key
@@ -64,6 +76,8 @@ pub fn remaining_rules() -> Vec<Rule> {
             regex: Regex::new(r"lin_api_[0-9a-zA-Z]{40}").unwrap(),
             entropy: Some(3.5),
             keywords: &["lin_api_"],
+            severity: Severity::High,
+            confidence: Confidence::High,
         },
 
         // LaunchDarkly Access Token
@@ -73,6 +87,8 @@ pub fn remaining_rules() -> Vec<Rule> {
             regex: Regex::new(r"[0-9a-zA-Z]{40}").unwrap(),
             entropy: Some(3.5),
             keywords: &[],
+            severity: Severity::High,
+            confidence: Confidence::Low,
         },
 
         // Okta Access Token
@@ -82,6 +98,8 @@ pub fn remaining_rules() -> Vec<Rule> {
             regex: Regex::new(r"00[a-zA-Z0-9]{40,}").unwrap(),
             entropy: Some(3.5),
             keywords: &["00"],
+            severity: Severity::High,
+            confidence: Confidence::Medium,
         },
 
         // 1Password Service Account Token
@@ -91,6 +109,8 @@ pub fn remaining_rules() -> Vec<Rule> {
             regex: Regex::new(r"ops_eyJ[a-zA-Z0-9+/]{250,}={0,3}").unwrap(),
             entropy: Some(4.0),
             keywords: &["ops_eyj"],
+            severity: Severity::Critical,
+            confidence: Confidence::High,
         },
 
         // PlanetScale Password / Database Password
@@ -100,6 +120,8 @@ pub fn remaining_rules() -> Vec<Rule> {
             regex: Regex::new(r"pscale_pw_[0-9a-zA-Z]{40,}").unwrap(),
             entropy: Some(3.8),
             keywords: &["pscale_pw_"],
+            severity: Severity::Critical,
+            confidence: Confidence::High,
         },
 
         // Prefect API Key
@@ -109,6 +131,8 @@ pub fn remaining_rules() -> Vec<Rule> {
             regex: Regex::new(r"pnu_[0-9a-zA-Z]{38}").unwrap(),
             entropy: Some(3.5),
             keywords: &["pnu_"],
+            severity: Severity::High,
+            confidence: Confidence::High,
         },
 
         // RapidAPI Key
@@ -118,6 +142,8 @@ pub fn remaining_rules() -> Vec<Rule> {
             regex: Regex::new(r"[0-9a-zA-Z]{50}").unwrap(),
             entropy: Some(3.5),
             keywords: &[],
+            severity: Severity::High,
+            confidence: Confidence::Low,
         },
 
         // Readme.io API Key
@@ -127,6 +153,8 @@ pub fn remaining_rules() -> Vec<Rule> {
             regex: Regex::new(r"rdme_[0-9a-zA-Z]{40}").unwrap(),
             entropy: Some(3.5),
             keywords: &["rdme_"],
+            severity: Severity::High,
+            confidence: Confidence::High,
         },
 
         // Scalingo API Token
@@ -136,6 +164,8 @@ pub fn remaining_rules() -> Vec<Rule> {
             regex: Regex::new(r"tk-us-[0-9a-zA-Z]{48}").unwrap(),
             entropy: Some(3.5),
             keywords: &["tk-us-"],
+            severity: Severity::High,
+            confidence: Confidence::High,
         },
 
         // Sendbird Access Token
@@ -145,6 +175,8 @@ pub fn remaining_rules() -> Vec<Rule> {
             regex: Regex::new(r"[0-9a-f]{40}").unwrap(),
             entropy: Some(3.5),
             keywords: &[],
+            severity: Severity::High,
+            confidence: Confidence::Low,
         },
 
         // Sentry Access Token
@@ -154,6 +186,8 @@ pub fn remaining_rules() -> Vec<Rule> {
             regex: Regex::new(r"[0-9a-f]{64}").unwrap(),
             entropy: Some(3.8),
             keywords: &[],
+            severity: Severity::High,
+            confidence: Confidence::Low,
         },
 
         // Shopify Shared Secret / Private App Password
@@ -163,6 +197,8 @@ pub fn remaining_rules() -> Vec<Rule> {
             regex: Regex::new(r"shpss_[0-9a-f]{32}").unwrap(),
             entropy: Some(3.5),
             keywords: &["shpss_"],
+            severity: Severity::High,
+            confidence: Confidence::High,
         },
         // Shopify Access Token
         Rule {
@@ -171,6 +207,8 @@ pub fn remaining_rules() -> Vec<Rule> {
             regex: Regex::new(r"shpat_[0-9a-f]{32}").unwrap(),
             entropy: Some(3.5),
             keywords: &["shpat_"],
+            severity: Severity::High,
+            confidence: Confidence::High,
         },
 
         // SonarQube Token
@@ -180,6 +218,8 @@ pub fn remaining_rules() -> Vec<Rule> {
             regex: Regex::new(r"[0-9a-f]{40}").unwrap(),
             entropy: Some(3.5),
             keywords: &[],
+            severity: Severity::High,
+            confidence: Confidence::Low,
         },
 
         // SquareSpace API Key
@@ -189,6 +229,8 @@ pub fn remaining_rules() -> Vec<Rule> {
             regex: Regex::new(r"[0-9a-f]{32}").unwrap(),
             entropy: Some(3.5),
             keywords: &[],
+            severity: Severity::High,
+            confidence: Confidence::Low,
         },
 
         // Typeform Access Token
@@ -198,6 +240,8 @@ pub fn remaining_rules() -> Vec<Rule> {
             regex: Regex::new(r"tfp_[0-9a-z\\-_]{40,}").unwrap(),
             entropy: Some(3.5),
             keywords: &["tfp_"],
+            severity: Severity::High,
+            confidence: Confidence::High,
         },
     ]
 }
\ No newline at end of file
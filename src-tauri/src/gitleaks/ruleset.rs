@@ -0,0 +1,172 @@
+use regex::Regex;
+use serde::Serialize;
+use super::{Confidence, Rule, Severity};
+use super::prefilter::{Prefilter, PrefilterCache};
+use super::rule::get_all_rules;
+
+// ============================================================================
+// Compiled Rule Set
+// ============================================================================
+//
+// 内置规则（`Rule`，编译期写死）和从 gitleaks TOML 加载的规则统一转换成
+// `CompiledRule`，扫描器只面向 `RuleSet` 工作。
+
+/// 单条规则，字段含义与 gitleaks 配置一致
+#[derive(Debug, Clone)]
+pub struct CompiledRule {
+    pub id: String,
+    pub description: String,
+    pub regex: Regex,
+    /// 密钥所在的捕获组；None 表示取名为 `secret` 的捕获组，没有则取整个匹配
+    pub secret_group: Option<usize>,
+    pub entropy: Option<f64>,
+    pub keywords: Vec<String>,
+    /// 只扫描路径匹配的文件；扫描不带路径的文本时跳过该规则
+    pub path: Option<Regex>,
+    pub allowlists: Vec<Allowlist>,
+    pub severity: Severity,
+    /// 基础置信度，扫描时再按上下文调整
+    pub confidence: Confidence,
+}
+
+impl From<&Rule> for CompiledRule {
+    fn from(rule: &Rule) -> Self {
+        Self {
+            id: rule.id.to_string(),
+            description: rule.description.to_string(),
+            regex: rule.regex.clone(),
+            secret_group: None,
+            entropy: rule.entropy,
+            keywords: rule.keywords.iter().map(|k| k.to_string()).collect(),
+            path: None,
+            allowlists: Vec::new(),
+            severity: rule.severity,
+            confidence: rule.confidence,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RegexTarget {
+    Secret,
+    Match,
+    Line,
+}
+
+/// gitleaks 的 allowlist：regexes / stopwords / paths 中配置了的检查项，
+/// 默认任一命中即放行（condition = "OR"），"AND" 时需全部命中
+#[derive(Debug, Clone)]
+pub struct Allowlist {
+    pub description: String,
+    pub match_all: bool,
+    pub regex_target: RegexTarget,
+    pub regexes: Vec<Regex>,
+    pub stopwords: Vec<String>,
+    pub paths: Vec<Regex>,
+    /// 仅对全局 allowlist 有意义：为空表示作用于所有规则
+    pub target_rules: Vec<String>,
+}
+
+/// 一次命中的上下文，供 allowlist 判断
+pub struct Candidate<'a> {
+    pub secret: &'a str,
+    pub matched: &'a str,
+    pub line: &'a str,
+    pub path: Option<&'a str>,
+}
+
+impl Allowlist {
+    pub fn applies_to(&self, rule_id: &str) -> bool {
+        self.target_rules.is_empty() || self.target_rules.iter().any(|r| r == rule_id)
+    }
+
+    pub fn allows(&self, c: &Candidate) -> bool {
+        let mut checks = Vec::with_capacity(3);
+        if !self.paths.is_empty() {
+            checks.push(c.path.is_some_and(|p| self.paths.iter().any(|re| re.is_match(p))));
+        }
+        if !self.regexes.is_empty() {
+            let target = match self.regex_target {
+                RegexTarget::Secret => c.secret,
+                RegexTarget::Match => c.matched,
+                RegexTarget::Line => c.line,
+            };
+            checks.push(self.regexes.iter().any(|re| re.is_match(target)));
+        }
+        if !self.stopwords.is_empty() {
+            let secret = c.secret.to_lowercase();
+            checks.push(self.stopwords.iter().any(|w| secret.contains(w.as_str())));
+        }
+
+        if checks.is_empty() {
+            return false;
+        }
+        if self.match_all {
+            checks.iter().all(|&b| b)
+        } else {
+            checks.iter().any(|&b| b)
+        }
+    }
+}
+
+/// 规则加载或编译失败的记录。整个文件解析失败时 `rule_id` 为 None
+#[derive(Debug, Clone, Serialize)]
+pub struct RuleError {
+    pub source: String,
+    pub rule_id: Option<String>,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct RuleSet {
+    pub rules: Vec<CompiledRule>,
+    /// 全局 allowlist
+    pub allowlists: Vec<Allowlist>,
+    /// 已生效的配置文件，按叠加顺序
+    pub sources: Vec<String>,
+    pub errors: Vec<RuleError>,
+    /// 被配置文件移除的下层规则（`disabledRules` 或不带 [extend] 的用户配置）
+    pub dropped_rules: Vec<String>,
+    /// 第一次扫描时按 `rules` 构建，之后不应再修改 `rules`（克隆出的副本会重新构建）
+    pub(crate) prefilter: PrefilterCache,
+}
+
+/// 返回给前端的规则加载状态
+#[derive(Debug, Clone, Serialize)]
+pub struct RuleSetStatus {
+    pub rule_count: usize,
+    pub sources: Vec<String>,
+    pub errors: Vec<RuleError>,
+    pub dropped_rules: Vec<String>,
+}
+
+impl RuleSet {
+    pub fn builtin() -> Self {
+        Self {
+            rules: get_all_rules().iter().map(CompiledRule::from).collect(),
+            ..Self::default()
+        }
+    }
+
+    /// 同 id 的规则整条替换，否则追加到末尾
+    pub fn upsert(&mut self, rule: CompiledRule) {
+        match self.rules.iter_mut().find(|r| r.id == rule.id) {
+            Some(existing) => *existing = rule,
+            None => self.rules.push(rule),
+        }
+        self.prefilter = PrefilterCache::default();
+    }
+
+    pub fn prefilter(&self) -> &Prefilter {
+        self.prefilter.get_or_build(&self.rules)
+    }
+
+    pub fn status(&self) -> RuleSetStatus {
+        RuleSetStatus {
+            rule_count: self.rules.len(),
+            sources: self.sources.clone(),
+            errors: self.errors.clone(),
+            dropped_rules: self.dropped_rules.clone(),
+        }
+    }
+}
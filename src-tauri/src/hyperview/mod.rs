@@ -1,7 +1,7 @@
 pub mod sniffer;
 pub mod protocol;
 
-use self::sniffer::FileMeta;
+use self::sniffer::{FileMeta, PreviewText};
 
 #[tauri::command]
 pub async fn get_file_meta(path: String) -> Result<FileMeta, String> {
@@ -10,3 +10,10 @@ pub async fn get_file_meta(path: String) -> Result<FileMeta, String> {
         sniffer::detect_file_type(&path)
     }).await.map_err(|e| e.to_string())?
 }
+
+#[tauri::command]
+pub async fn read_preview_text(path: String) -> Result<PreviewText, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        sniffer::read_text(&path)
+    }).await.map_err(|e| e.to_string())?
+}
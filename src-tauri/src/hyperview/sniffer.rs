@@ -2,6 +2,7 @@ use serde::Serialize;
 use std::path::Path;
 use std::fs::File;
 use std::io::Read;
+use crate::charset;
 
 #[derive(Debug, Serialize, Clone, PartialEq)]
 #[serde(rename_all = "camelCase")]
@@ -25,6 +26,36 @@ pub struct FileMeta {
     pub size: u64,
     pub preview_type: PreviewType,
     pub mime: String,
+    /// 文本类文件（Code / Markdown）检测到的编码，其余为 None
+    pub encoding: Option<String>,
+}
+
+/// `read_preview_text` 的返回值：已转成 UTF-8 的文本
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PreviewText {
+    pub content: String,
+    pub encoding: String,
+}
+
+/// 编码检测读取的字节数，比二进制判断多读一些，让 chardetng 有足够的样本
+const ENCODING_SNIFF_BYTES: u64 = 64 * 1024;
+
+fn sniff_encoding(path: &Path) -> Option<String> {
+    let file = File::open(path).ok()?;
+    let mut head = Vec::new();
+    file.take(ENCODING_SNIFF_BYTES).read_to_end(&mut head).ok()?;
+    Some(charset::detect_prefix(&head).name().to_string())
+}
+
+/// 按检测到的编码读取文本文件，预览 GBK、Shift_JIS、UTF-16 等文件时不会乱码
+pub fn read_text(path_str: &str) -> Result<PreviewText, String> {
+    let bytes = std::fs::read(path_str).map_err(|e| e.to_string())?;
+    let decoded = charset::decode(&bytes);
+    Ok(PreviewText {
+        encoding: decoded.encoding_name().to_string(),
+        content: decoded.text,
+    })
 }
 
 /// 读取文件头几个字节来辅助判断
@@ -89,11 +120,17 @@ pub fn detect_file_type(path_str: &str) -> Result<FileMeta, String> {
         }
     }
 
+    let encoding = match p_type {
+        PreviewType::Code | PreviewType::Markdown => sniff_encoding(path),
+        _ => None,
+    };
+
     Ok(FileMeta {
         path: path_str.to_string(),
         name,
         size,
         preview_type: p_type,
         mime,
+        encoding,
     })
 }
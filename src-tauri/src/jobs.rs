@@ -0,0 +1,40 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+
+// ============================================================================
+// Cancellable Jobs
+// ============================================================================
+//
+// 长时间运行的后台任务（上下文导出、密钥扫描）的取消标记，以前端生成的 job id 为键。
+// 任务开始时 `register`，结束时 `finish`；前端通过各自的 cancel 命令调用 `cancel`。
+
+#[derive(Default)]
+pub struct JobRegistry(Mutex<HashMap<String, Arc<AtomicBool>>>);
+
+impl JobRegistry {
+    pub fn register(&self, job_id: &str) -> Arc<AtomicBool> {
+        let flag = Arc::new(AtomicBool::new(false));
+        if let Ok(mut jobs) = self.0.lock() {
+            jobs.insert(job_id.to_string(), flag.clone());
+        }
+        flag
+    }
+
+    /// 返回是否找到了该任务
+    pub fn cancel(&self, job_id: &str) -> bool {
+        match self.0.lock().ok().and_then(|jobs| jobs.get(job_id).cloned()) {
+            Some(flag) => {
+                flag.store(true, Ordering::Relaxed);
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn finish(&self, job_id: &str) {
+        if let Ok(mut jobs) = self.0.lock() {
+            jobs.remove(job_id);
+        }
+    }
+}
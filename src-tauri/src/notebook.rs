@@ -0,0 +1,138 @@
+use std::path::Path;
+use serde::Deserialize;
+use serde_json::Value;
+
+// ============================================================================
+// Jupyter Notebook Extraction
+// ============================================================================
+//
+// `.ipynb` 原文是 JSON，输出里常带 base64 图片，直接放进上下文既浪费 token 又难读。
+// 这里按顺序输出各个 cell，格式参照 jupytext 的 percent 格式：
+//
+//   # %% [markdown]
+//   ...
+//   # %% [code]
+//   ...
+//   # %% [output]
+//   ...
+//
+// 上下文和 Git diff 共用，diff 两侧使用相同的格式即可逐 cell 比较。
+
+/// 输出单条 output 的默认字符数上限
+const DEFAULT_MAX_OUTPUT_CHARS: usize = 2000;
+
+/// 原始 `.ipynb` 的读取上限。输出里的图片会让文件远大于普通源码的上限，
+/// 所以调用方先按这个上限读取，再对提取出的 cell 文本应用普通的大小限制
+pub const MAX_NOTEBOOK_SOURCE_SIZE: u64 = 64 * 1024 * 1024;
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct NotebookOptions {
+    /// 是否附带 cell 的文本输出（stream、text/plain、error），图片等二进制输出始终丢弃
+    #[serde(default)]
+    pub include_outputs: bool,
+    /// 每条输出最多保留的字符数
+    #[serde(default = "default_max_output_chars")]
+    pub max_output_chars: usize,
+}
+
+fn default_max_output_chars() -> usize {
+    DEFAULT_MAX_OUTPUT_CHARS
+}
+
+impl Default for NotebookOptions {
+    fn default() -> Self {
+        Self { include_outputs: false, max_output_chars: DEFAULT_MAX_OUTPUT_CHARS }
+    }
+}
+
+pub fn is_notebook(path: &Path) -> bool {
+    path.extension().is_some_and(|e| e.eq_ignore_ascii_case("ipynb"))
+}
+
+pub struct Notebook {
+    /// kernel 的语言名（如 "python"、"r"、"julia"），取自 metadata
+    pub language: Option<String>,
+    cells: Vec<Value>,
+}
+
+impl Notebook {
+    pub fn parse(json: &str) -> Result<Self, String> {
+        let mut root: Value = serde_json::from_str(json).map_err(|e| e.to_string())?;
+        let language = root.get("metadata")
+            .and_then(|m| m.pointer("/kernelspec/language").or_else(|| m.pointer("/language_info/name")))
+            .and_then(Value::as_str)
+            .map(str::to_string);
+        match root.get_mut("cells").map(Value::take) {
+            Some(Value::Array(cells)) => Ok(Self { language, cells }),
+            _ => Err("Not a Jupyter notebook: missing cells".to_string()),
+        }
+    }
+
+    /// 按顺序输出所有 cell。`code` 用于处理代码 cell 的源码（例如剥离注释）
+    pub fn render(&self, options: &NotebookOptions, mut code: impl FnMut(&str) -> String) -> String {
+        let mut out = String::new();
+        for cell in &self.cells {
+            let kind = cell.get("cell_type").and_then(Value::as_str).unwrap_or("code");
+            let source = join_text(cell.get("source"));
+
+            push_section(&mut out, kind, &if kind == "code" { code(&source) } else { source });
+
+            if kind == "code" && options.include_outputs {
+                let outputs = cell.get("outputs").and_then(Value::as_array).map(Vec::as_slice).unwrap_or_default();
+                for text in outputs.iter().filter_map(output_text) {
+                    push_section(&mut out, "output", &truncate(&text, options.max_output_chars));
+                }
+            }
+        }
+        out
+    }
+}
+
+fn push_section(out: &mut String, kind: &str, body: &str) {
+    if !out.is_empty() {
+        out.push('\n');
+    }
+    out.push_str("# %% [");
+    out.push_str(kind);
+    out.push_str("]\n");
+    let body = body.trim_end_matches('\n');
+    if !body.is_empty() {
+        out.push_str(body);
+        out.push('\n');
+    }
+}
+
+/// nbformat 中的多行文本既可能是字符串，也可能是按行拆开的字符串数组
+fn join_text(value: Option<&Value>) -> String {
+    match value {
+        Some(Value::String(s)) => s.clone(),
+        Some(Value::Array(lines)) => lines.iter().filter_map(Value::as_str).collect(),
+        _ => String::new(),
+    }
+}
+
+/// 只保留文本输出；只有图片、HTML 等富媒体数据的输出返回 None
+fn output_text(output: &Value) -> Option<String> {
+    let text = match output.get("output_type").and_then(Value::as_str)? {
+        "stream" => join_text(output.get("text")),
+        "execute_result" | "display_data" => join_text(output.pointer("/data/text~1plain")),
+        "error" => {
+            let name = output.get("ename").and_then(Value::as_str).unwrap_or("Error");
+            let value = output.get("evalue").and_then(Value::as_str).unwrap_or("");
+            format!("{}: {}", name, value)
+        }
+        _ => return None,
+    };
+    (!text.trim().is_empty()).then_some(text)
+}
+
+fn truncate(text: &str, max_chars: usize) -> String {
+    match text.char_indices().nth(max_chars) {
+        Some((cut, _)) => format!(
+            "{}\n... [{} more characters truncated]",
+            &text[..cut],
+            text[cut..].chars().count()
+        ),
+        None => text.to_string(),
+    }
+}
@@ -2,7 +2,7 @@ import { useState, useEffect, useRef } from 'react';
 import { Editor, OnMount } from '@monaco-editor/react';
 import { Copy, FileText, Loader2, AlertCircle, Search } from 'lucide-react';
 import { FileNode } from '@/types/context';
-import { getSelectedPaths, generateHeader } from '@/lib/context_assembler';
+import { getSelectedPaths, generateHeaderWithRepoMap, mergeIgnoreConfig } from '@/lib/context_assembler';
 import { writeText } from '@tauri-apps/plugin-clipboard-manager';
 import { useAppStore } from '@/store/useAppStore';
 import { useContextStore } from '@/store/useContextStore';
@@ -18,8 +18,11 @@ export function ContextPreview({ fileTree }: ContextPreviewProps) {
   const [content, setContent] = useState<string>('');
   const [isLoading, setIsLoading] = useState(true);
   const [isCopied, setIsCopied] = useState(false);
-  const { language, theme } = useAppStore();
-  const { removeComments } = useContextStore();
+  const { language, theme, globalIgnore } = useAppStore();
+  const {
+    removeComments, outputFormat, skeletonPaths, autoSkeleton, notebookOutputs, redactSecrets,
+    includeRepoMap, projectRoot, projectIgnore, getSkeletonOptions, getNotebookOptions
+  } = useContextStore();
 
   const editorRef = useRef<any>(null);
   const monacoRef = useRef<any>(null);
@@ -39,11 +42,20 @@ export function ContextPreview({ fileTree }: ContextPreviewProps) {
 
       setIsLoading(true);
       try {
-        const header = generateHeader(fileTree, removeComments);
+        const header = await generateHeaderWithRepoMap(
+            fileTree,
+            removeComments,
+            includeRepoMap ? projectRoot : null,
+            mergeIgnoreConfig(globalIgnore, projectIgnore)
+        );
         const text = await invoke<string>('get_context_content', {
             paths,
             header,
-            removeComments
+            removeComments,
+            format: outputFormat,
+            skeleton: getSkeletonOptions(),
+            notebook: getNotebookOptions(),
+            redactSecrets
         });
 
         if (isMounted) setContent(text);
@@ -61,7 +73,7 @@ export function ContextPreview({ fileTree }: ContextPreviewProps) {
       clearTimeout(timer);
       setContent('');
     };
-  }, [fileTree, removeComments]);
+  }, [fileTree, removeComments, outputFormat, skeletonPaths, autoSkeleton, notebookOutputs, redactSecrets, includeRepoMap]);
 
   useEffect(() => {
     if (monacoRef.current) {
@@ -5,6 +5,8 @@ import { basename } from '@tauri-apps/api/path';
 import { writeTextFile } from '@tauri-apps/plugin-fs';
 import { writeText as writeClipboard } from '@tauri-apps/plugin-clipboard-manager';
 import { invoke } from '@tauri-apps/api/core';
+import { listen } from '@tauri-apps/api/event';
+import { v4 as uuidv4 } from 'uuid';
 import {
   FolderOpen, RefreshCw, Loader2, FileJson,
   PanelLeft, Search, ArrowRight, SlidersHorizontal, ChevronUp,
@@ -15,16 +17,19 @@ import { useAppStore, DEFAULT_MODELS } from '@/store/useAppStore';
 import { usePreviewStore } from '@/store/usePreviewStore';
 import { scanProject } from '@/lib/fs_helper';
 import { calculateIdealTreeWidth, flattenTree } from '@/lib/tree_utils';
-import { getSelectedPaths, generateHeader } from '@/lib/context_assembler';
+import { getSelectedPaths, generateHeaderWithRepoMap, mergeIgnoreConfig, RedactionReport } from '@/lib/context_assembler';
 import { FileTreeNode } from './FileTreeNode';
-import { TokenDashboard } from './TokenDashboard';
+import { TokenDashboard, ExportProgress } from './TokenDashboard';
 import { FilterManager } from './FilterManager';
 import { ContextPreview } from './ContextPreview';
-import { ScanResultDialog, SecretMatch } from './ScanResultDialog';
+import { ScanResultDialog, SecretMatch, matchedText } from './ScanResultDialog';
+import { SplitContextDialog } from './SplitContextDialog';
+import { GitContextDialog } from './GitContextDialog';
+import { SecretScanDialog } from './SecretScanDialog';
 import { cn } from '@/lib/utils';
 import { getText } from '@/lib/i18n';
 import { Toast, ToastType } from '@/components/ui/Toast';
-import { FileNode } from '@/types/context';
+import { FileNode, OUTPUT_FORMAT_EXTENSIONS } from '@/types/context';
 import { FixedSizeList as List } from 'react-window';
 import AutoSizer from 'react-virtualized-auto-sizer';
 
@@ -43,11 +48,13 @@ interface RowProps {
     onToggleSelect: (id: string, checked: boolean) => void;
     onToggleExpand: (id: string) => void;
     onPreview?: (path: string) => void;
+    skeletonPaths: Set<string>;
+    onToggleSkeleton: (path: string) => void;
   };
 }
 
 const Row = memo(function Row({ index, style, data }: RowProps) {
-  const { items, onToggleSelect, onToggleExpand, onPreview } = data;
+  const { items, onToggleSelect, onToggleExpand, onPreview, skeletonPaths, onToggleSkeleton } = data;
   const item = items[index];
 
   return (
@@ -60,6 +67,8 @@ const Row = memo(function Row({ index, style, data }: RowProps) {
       onToggleSelect={onToggleSelect}
       onToggleExpand={onToggleExpand}
       onPreview={onPreview}
+      isSkeleton={skeletonPaths.has(item.node.path)}
+      onToggleSkeleton={onToggleSkeleton}
     />
   );
 });
@@ -70,7 +79,8 @@ export function ContextView() {
     projectIgnore, updateProjectIgnore,
     refreshTreeStatus,
     setProjectRoot, setFileTree, setIsScanning, toggleSelect,
-    removeComments, detectSecrets, invertSelection,
+    removeComments, outputFormat, detectSecrets, redactSecrets, invertSelection,
+    skeletonPaths, toggleSkeleton, getSkeletonOptions, getNotebookOptions, includeRepoMap,
     expandedIds, toggleExpand
   } = useContextStore();
 
@@ -85,6 +95,11 @@ export function ContextView() {
 
   const [pathInput, setPathInput] = useState('');
   const [isGenerating, setIsGenerating] = useState(false);
+  const [exportProgress, setExportProgress] = useState<ExportProgress | null>(null);
+  const exportJobRef = useRef<string | null>(null);
+  const [splitDialog, setSplitDialog] = useState<{ isOpen: boolean; fileName: string }>({ isOpen: false, fileName: '' });
+  const [isGitDialogOpen, setIsGitDialogOpen] = useState(false);
+  const [isSecretScanOpen, setIsSecretScanOpen] = useState(false);
   const [showFilters, setShowFilters] = useState(false); 
   const [rightViewMode, setRightViewMode] = useState<'dashboard' | 'preview'>('dashboard');
 
@@ -139,8 +154,17 @@ export function ContextView() {
     items: flatData,
     onToggleSelect: toggleSelect,
     onToggleExpand: toggleExpand,
-    onPreview: openPreview
-  }), [flatData, toggleSelect, toggleExpand, openPreview]);
+    onPreview: openPreview,
+    skeletonPaths: new Set(skeletonPaths),
+    onToggleSkeleton: toggleSkeleton
+  }), [flatData, toggleSelect, toggleExpand, openPreview, skeletonPaths, toggleSkeleton]);
+
+  const buildHeader = () => generateHeaderWithRepoMap(
+    fileTree,
+    removeComments,
+    includeRepoMap ? projectRoot : null,
+    mergeIgnoreConfig(globalIgnore, projectIgnore)
+  );
 
   const triggerToast = (msg: string, type: ToastType = 'success') => {
     setToastState({ show: true, msg, type });
@@ -163,7 +187,7 @@ export function ContextView() {
     const now = new Date();
     const timeStr = `${now.getFullYear()}${String(now.getMonth() + 1).padStart(2, '0')}${String(now.getDate()).padStart(2, '0')}_${String(now.getHours()).padStart(2, '0')}${String(now.getMinutes()).padStart(2, '0')}`;
 
-    return `${namePart}_${timeStr}.txt`;
+    return `${namePart}_${timeStr}.${OUTPUT_FORMAT_EXTENSIONS[outputFormat]}`;
   };
 
   const executeFinalAction = async (text: string, action: 'copy' | 'save', savePath?: string) => {
@@ -176,7 +200,7 @@ export function ContextView() {
               if (!filePath) {
                   const defaultPath = await getDefaultSavePath();
                   filePath = await save({
-                      filters: [{ name: 'Text File', extensions: ['txt', 'md', 'json'] }],
+                      filters: [{ name: 'Text File', extensions: ['txt', 'md', 'json', 'xml'] }],
                       defaultPath: defaultPath
                   }) || undefined;
               }
@@ -199,7 +223,7 @@ export function ContextView() {
       }
 
       try {
-          const results = await invoke<SecretMatch[]>('scan_for_secrets', { content: text });
+          const results = await invoke<SecretMatch[]>('scan_for_secrets', { content: text, projectRoot });
 
           if (results && results.length > 0) {
               setScanState({
@@ -233,7 +257,8 @@ export function ContextView() {
                   continue;
               }
               const jsIndex = match.utf16_index;
-              const val = match.value;
+              // 解码后发现的密钥替换整个编码片段
+              const val = matchedText(match);
               let maskedValue = '';
               if (val.length <= 8) {
                   maskedValue = '*'.repeat(val.length);
@@ -252,6 +277,48 @@ export function ContextView() {
       await executeFinalAction(finalText, pendingAction, pendingSavePath);
   };
 
+  // 流式复制 / 保存：Rust 端分批写出并推送进度，可中途取消。
+  // 取消时返回 null；开启自动脱敏时 report 为脱敏报告
+  const runStreamingExport = async (command: 'copy_context_to_clipboard' | 'save_context_to_file', args: Record<string, unknown>) => {
+    const jobId = uuidv4();
+    exportJobRef.current = jobId;
+    const unlisten = await listen<ExportProgress & { job_id: string }>('context-export-progress', (event) => {
+      if (event.payload.job_id === jobId) {
+        setExportProgress({ done: event.payload.done, total: event.payload.total });
+      }
+    });
+    try {
+      const report = await invoke<RedactionReport | null>(command, { ...args, jobId, redactSecrets, projectRoot });
+      return { report };
+    } catch (err) {
+      if (err === 'Export cancelled') {
+        triggerToast(getText('context', 'toastExportCancelled', language), 'info');
+        return null;
+      }
+      throw err;
+    } finally {
+      unlisten();
+      exportJobRef.current = null;
+      setExportProgress(null);
+    }
+  };
+
+  const exportDoneMessage = (key: 'toastCopied' | 'toastSaved', report: RedactionReport | null) => {
+    const message = getText('context', key, language);
+    if (!report || report.total === 0) return message;
+    return `${message} ${getText('context', 'toastRedacted', language, { count: report.total.toString() })}`;
+  };
+
+  const handleOpenSplit = async () => {
+    setSplitDialog({ isOpen: true, fileName: await getDefaultSavePath() });
+  };
+
+  const handleCancelExport = () => {
+    if (exportJobRef.current) {
+      invoke('cancel_context_export', { jobId: exportJobRef.current });
+    }
+  };
+
   const handleCopyContext = async () => {
     if (isGenerating) return;
     setIsGenerating(true);
@@ -259,14 +326,15 @@ export function ContextView() {
       const paths = getSelectedPaths(fileTree);
       if (paths.length === 0) return;
 
-      const header = generateHeader(fileTree, removeComments);
+      const header = await buildHeader();
 
-      if (detectSecrets) {
-        const text = await invoke<string>('get_context_content', { paths, header, removeComments });
+      // 自动脱敏已经替换了密钥，不再需要逐条确认
+      if (detectSecrets && !redactSecrets) {
+        const text = await invoke<string>('get_context_content', { paths, header, removeComments, format: outputFormat, skeleton: getSkeletonOptions(), notebook: getNotebookOptions() });
         await processWithSecurityCheck(text, 'copy');
       } else {
-        await invoke('copy_context_to_clipboard', { paths, header, removeComments });
-        triggerToast(getText('context', 'toastCopied', language), 'success');
+        const done = await runStreamingExport('copy_context_to_clipboard', { paths, header, removeComments, format: outputFormat, skeleton: getSkeletonOptions(), notebook: getNotebookOptions() });
+        if (done) triggerToast(exportDoneMessage('toastCopied', done.report), 'success');
       }
     } catch (err) {
       console.error("Failed to copy:", err);
@@ -281,11 +349,11 @@ export function ContextView() {
     setIsGenerating(true);
     try {
       const paths = getSelectedPaths(fileTree);
-      const header = generateHeader(fileTree, removeComments);
+      const header = await buildHeader();
 
       const defaultPath = await getDefaultSavePath();
       const filePath = await save({
-        filters: [{ name: 'Text File', extensions: ['txt', 'md', 'json'] }],
+        filters: [{ name: 'Text File', extensions: ['txt', 'md', 'json', 'xml'] }],
         defaultPath: defaultPath
       });
 
@@ -294,17 +362,20 @@ export function ContextView() {
         return;
       }
 
-      if (detectSecrets) {
-        const text = await invoke<string>('get_context_content', { paths, header, removeComments });
+      if (detectSecrets && !redactSecrets) {
+        const text = await invoke<string>('get_context_content', { paths, header, removeComments, format: outputFormat, skeleton: getSkeletonOptions(), notebook: getNotebookOptions() });
         await processWithSecurityCheck(text, 'save', filePath);
       } else {
-        await invoke('save_context_to_file', {
+        const done = await runStreamingExport('save_context_to_file', {
           paths,
           header,
           removeComments,
+          format: outputFormat,
+          skeleton: getSkeletonOptions(),
+          notebook: getNotebookOptions(),
           savePath: filePath
         });
-        triggerToast(getText('context', 'toastSaved', language), 'success');
+        if (done) triggerToast(exportDoneMessage('toastSaved', done.report), 'success');
       }
     } catch (err) {
       console.error("Failed to generate:", err);
@@ -318,13 +389,7 @@ export function ContextView() {
     if (!path.trim()) return;
     setIsScanning(true);
     try {
-      const effectiveConfig = {
-        dirs: Array.from(new Set([...globalIgnore.dirs, ...projectIgnore.dirs])),
-        files: Array.from(new Set([...globalIgnore.files, ...projectIgnore.files])),
-        extensions: Array.from(new Set([...globalIgnore.extensions, ...projectIgnore.extensions])),
-      };
-
-      const tree = await scanProject(path, effectiveConfig);
+      const tree = await scanProject(path, mergeIgnoreConfig(globalIgnore, projectIgnore));
       setFileTree(tree);
       setProjectRoot(path);
       
@@ -506,6 +571,11 @@ export function ContextView() {
                      onCopy={handleCopyContext}
                      onSave={handleSaveToFile}
                      isGenerating={isGenerating}
+                     exportProgress={exportProgress}
+                     onCancel={handleCancelExport}
+                     onSplit={handleOpenSplit}
+                     onGitChanges={() => setIsGitDialogOpen(true)}
+                     onSecretScan={() => setIsSecretScanOpen(true)}
                    />
                 ) : (
                    <div className="h-full">
@@ -530,6 +600,29 @@ export function ContextView() {
         onConfirm={handleScanConfirm}
         onCancel={() => setScanState(prev => ({ ...prev, isOpen: false }))}
       />
+
+      {/* 分段输出弹窗 */}
+      <SplitContextDialog
+        isOpen={splitDialog.isOpen}
+        fileTree={fileTree}
+        defaultFileName={splitDialog.fileName}
+        models={activeModels}
+        onClose={() => setSplitDialog(prev => ({ ...prev, isOpen: false }))}
+      />
+
+      {/* Git 变更上下文弹窗 */}
+      <GitContextDialog
+        isOpen={isGitDialogOpen}
+        projectRoot={projectRoot}
+        onClose={() => setIsGitDialogOpen(false)}
+      />
+
+      {/* 仓库密钥扫描弹窗 */}
+      <SecretScanDialog
+        isOpen={isSecretScanOpen}
+        projectRoot={projectRoot}
+        onClose={() => setIsSecretScanOpen(false)}
+      />
     </div>
   );
 }
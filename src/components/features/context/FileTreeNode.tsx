@@ -1,4 +1,4 @@
-import { ChevronRight, ChevronDown, Folder, FileCode, Lock, Eye } from 'lucide-react';
+import { ChevronRight, ChevronDown, Folder, FileCode, Lock, Eye, Shrink } from 'lucide-react';
 import { FileNode } from '@/types/context';
 import { cn } from '@/lib/utils';
 import { useAppStore } from '@/store/useAppStore';
@@ -14,6 +14,8 @@ interface FileTreeNodeProps {
   onToggleSelect: (id: string, checked: boolean) => void;
   onToggleExpand: (id: string) => void;
   onPreview?: (path: string) => void;
+  isSkeleton?: boolean;
+  onToggleSkeleton?: (path: string) => void;
 }
 
 export function FileTreeNode({
@@ -24,7 +26,9 @@ export function FileTreeNode({
   style,
   onToggleSelect,
   onToggleExpand,
-  onPreview
+  onPreview,
+  isSkeleton,
+  onToggleSkeleton
 }: FileTreeNodeProps) {
   const { language } = useAppStore();
 
@@ -56,6 +60,11 @@ export function FileTreeNode({
     if (onPreview) onPreview(node.path);
   };
 
+  const handleSkeletonClick = (e: React.MouseEvent) => {
+    e.stopPropagation();
+    if (onToggleSkeleton) onToggleSkeleton(node.path);
+  };
+
   const Icon = hasChildren ? (isExpanded ? ChevronDown : ChevronRight) : null;
   const TypeIcon = node.kind === 'dir' ? Folder : FileCode;
 
@@ -105,6 +114,22 @@ export function FileTreeNode({
         {node.name}
       </span>
 
+      {/* 骨架模式开关：开启后常驻显示 */}
+      {node.kind === 'file' && !node.isLocked && onToggleSkeleton && (
+        <button
+          onClick={handleSkeletonClick}
+          className={cn(
+            "p-1 hover:bg-background rounded mr-1 transition-all",
+            isSkeleton
+              ? "text-primary"
+              : "opacity-0 group-hover:opacity-100 text-muted-foreground hover:text-primary"
+          )}
+          title={getText('context', 'skeletonToggle', language)}
+        >
+          <Shrink size={12} />
+        </button>
+      )}
+
       {/* 新增：悬浮时显示的预览按钮 (仅文件显示) */}
       {node.kind === 'file' && !node.isLocked && onPreview && (
         <button
@@ -0,0 +1,190 @@
+import { useState, useEffect } from 'react';
+import { invoke } from '@tauri-apps/api/core';
+import { writeText } from '@tauri-apps/plugin-clipboard-manager';
+import { GitBranch, X, Copy, Check, Loader2 } from 'lucide-react';
+import { cn } from '@/lib/utils';
+import { useAppStore } from '@/store/useAppStore';
+import { useContextStore } from '@/store/useContextStore';
+import { getText } from '@/lib/i18n';
+
+// 与 Rust 端 git::ChangedFile / context::git_context::GitContext 对应
+interface ChangedFile {
+  path: string;
+  status: string;
+  old_path: string | null;
+}
+
+interface GitContext {
+  base_commit: string;
+  files: ChangedFile[];
+  content: string;
+}
+
+interface GitContextDialogProps {
+  isOpen: boolean;
+  projectRoot: string | null;
+  onClose: () => void;
+}
+
+const STATUS_COLORS: Record<string, string> = {
+  Added: 'text-green-500',
+  Modified: 'text-yellow-500',
+  Renamed: 'text-blue-500',
+  Deleted: 'text-red-500'
+};
+
+export function GitContextDialog({ isOpen, projectRoot, onClose }: GitContextDialogProps) {
+  const { language } = useAppStore();
+  const { removeComments, outputFormat, getSkeletonOptions, getNotebookOptions, redactSecrets } = useContextStore();
+
+  const [baseRef, setBaseRef] = useState('');
+  const [mergeBase, setMergeBase] = useState(true);
+  const [includeDiff, setIncludeDiff] = useState(false);
+  const [skipDeleted, setSkipDeleted] = useState(true);
+  const [result, setResult] = useState<GitContext | null>(null);
+  const [isLoading, setIsLoading] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+  const [copied, setCopied] = useState(false);
+
+  useEffect(() => {
+    if (isOpen) {
+      setResult(null);
+      setError(null);
+    }
+  }, [isOpen]);
+
+  if (!isOpen) return null;
+
+  const handleCollect = async () => {
+    if (!projectRoot) return;
+
+    setIsLoading(true);
+    setError(null);
+    try {
+      const ctx = await invoke<GitContext>('get_git_context', {
+        projectPath: projectRoot,
+        selection: {
+          base_ref: baseRef.trim() || null,
+          merge_base: mergeBase,
+          include_diff: includeDiff,
+          skip_deleted: skipDeleted
+        },
+        header: { summary: '', structure: '' },
+        removeComments,
+        format: outputFormat,
+        skeleton: getSkeletonOptions(),
+        notebook: getNotebookOptions(),
+        redactSecrets
+      });
+      setResult(ctx);
+    } catch (err) {
+      setResult(null);
+      setError(String(err));
+    } finally {
+      setIsLoading(false);
+    }
+  };
+
+  const handleCopy = async () => {
+    if (!result) return;
+    await writeText(result.content);
+    setCopied(true);
+    setTimeout(() => setCopied(false), 2000);
+  };
+
+  const checkbox = (checked: boolean, onChange: (v: boolean) => void, label: string) => (
+    <label className="flex items-center gap-1.5 text-xs text-muted-foreground cursor-pointer select-none">
+      <input
+        type="checkbox"
+        checked={checked}
+        onChange={e => onChange(e.target.checked)}
+        className="w-3.5 h-3.5 rounded accent-primary cursor-pointer"
+      />
+      {label}
+    </label>
+  );
+
+  return (
+    <div className="fixed inset-0 z-[100] bg-black/60 backdrop-blur-sm flex items-center justify-center animate-in fade-in duration-200 p-4">
+      <div className="w-full max-w-[600px] bg-background border border-border rounded-xl shadow-2xl flex flex-col overflow-hidden animate-in zoom-in-95 duration-200 max-h-[85vh]">
+
+        {/* Header */}
+        <div className="p-6 pb-4 border-b border-border shrink-0">
+          <div className="flex items-start gap-4">
+            <div className="w-12 h-12 rounded-full bg-primary/10 text-primary flex items-center justify-center shrink-0">
+              <GitBranch size={22} />
+            </div>
+            <div>
+              <h3 className="font-semibold text-lg text-foreground">{getText('context', 'gitTitle', language)}</h3>
+              <p className="text-sm text-muted-foreground mt-1 leading-relaxed">{getText('context', 'gitDesc', language)}</p>
+            </div>
+            <button onClick={onClose} className="ml-auto text-muted-foreground hover:text-foreground">
+              <X size={20} />
+            </button>
+          </div>
+
+          <div className="flex items-center gap-2 mt-4">
+            <input
+              value={baseRef}
+              onChange={e => setBaseRef(e.target.value)}
+              onKeyDown={e => e.key === 'Enter' && handleCollect()}
+              placeholder={getText('context', 'gitBaseRefPlaceholder', language)}
+              className="flex-1 bg-secondary/50 border border-border rounded-md px-3 py-1.5 text-sm font-mono outline-none focus:ring-1 focus:ring-primary/50"
+            />
+            <button
+              onClick={handleCollect}
+              disabled={isLoading || !projectRoot}
+              className="flex items-center gap-2 px-4 py-1.5 text-sm font-medium rounded-md bg-primary text-primary-foreground hover:bg-primary/90 disabled:opacity-50 transition-colors"
+            >
+              {isLoading ? <Loader2 size={14} className="animate-spin" /> : <GitBranch size={14} />}
+              {getText('context', 'gitAction', language)}
+            </button>
+          </div>
+          <div className="flex flex-wrap items-center gap-4 mt-3">
+            {checkbox(mergeBase, setMergeBase, getText('context', 'gitMergeBase', language))}
+            {checkbox(includeDiff, setIncludeDiff, getText('context', 'gitIncludeDiff', language))}
+            {checkbox(skipDeleted, setSkipDeleted, getText('context', 'gitSkipDeleted', language))}
+          </div>
+          {error && <p className="text-xs text-destructive mt-2">{error}</p>}
+        </div>
+
+        {/* Changed files */}
+        <div className="flex-1 overflow-y-auto p-4 custom-scrollbar bg-secondary/5 space-y-1 min-h-0">
+          {!result && !isLoading && (
+            <p className="text-xs text-muted-foreground text-center py-6">{getText('context', 'gitEmpty', language)}</p>
+          )}
+          {result && result.files.length === 0 && (
+            <p className="text-xs text-muted-foreground text-center py-6">{getText('context', 'gitNoChanges', language)}</p>
+          )}
+          {result?.files.map(file => (
+            <div key={file.path} className="flex items-center gap-3 px-2 py-1 text-xs font-mono">
+              <span className={cn("w-16 shrink-0", STATUS_COLORS[file.status] || 'text-muted-foreground')}>{file.status}</span>
+              <span className="truncate text-foreground" title={file.path}>
+                {file.old_path ? `${file.old_path} → ${file.path}` : file.path}
+              </span>
+            </div>
+          ))}
+        </div>
+
+        {/* Footer */}
+        {result && result.files.length > 0 && (
+          <div className="p-4 border-t border-border flex items-center justify-between shrink-0">
+            <span className="text-xs text-muted-foreground font-mono">
+              {getText('context', 'gitBaseCommit', language, { commit: result.base_commit.slice(0, 8) })}
+            </span>
+            <button
+              onClick={handleCopy}
+              className={cn(
+                "flex items-center gap-2 px-4 py-2 text-sm font-medium rounded-md transition-colors",
+                copied ? "bg-green-500/10 text-green-600" : "bg-secondary hover:bg-secondary/80 text-foreground"
+              )}
+            >
+              {copied ? <Check size={14} /> : <Copy size={14} />}
+              {getText('common', copied ? 'copied' : 'copy', language)}
+            </button>
+          </div>
+        )}
+      </div>
+    </div>
+  );
+}
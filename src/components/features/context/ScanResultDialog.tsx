@@ -1,19 +1,104 @@
 import { useState, useEffect } from 'react';
 import { invoke } from '@tauri-apps/api/core';
-import { ShieldAlert, AlertTriangle, ShieldCheck, X, CheckSquare, Square, ArrowRight, ArrowRightLeft, MinusCircle } from 'lucide-react';
+import { ShieldAlert, AlertTriangle, ShieldCheck, X, CheckSquare, Square, ArrowRight, ArrowRightLeft, MinusCircle, ArrowDownWideNarrow, EyeOff, Binary } from 'lucide-react';
 import { cn } from '@/lib/utils';
 import { useAppStore } from '@/store/useAppStore';
+import { useContextStore } from '@/store/useContextStore';
 import { getText } from '@/lib/i18n';
+import { EncodedSegment, LOW_CONFIDENCE, SecretSeverity, SecretSort } from '@/types/context';
 
 export interface SecretMatch {
   kind: String;
   value: String;
   index: number;
-  risk_level: 'High' | 'Medium';
+  risk_level: SecretSeverity;
+  confidence: number;
   utf16_index: number;
   line_number: number;
   snippet: string;
   snippet_start_line: number;
+  encoded?: EncodedSegment;
+}
+
+// 结果在原文中对应的文本：解码后发现的密钥对应整个编码片段
+export const matchedText = (match: SecretMatch) => match.encoded?.text ?? match.value.toString();
+
+const SEVERITY_RANK: Record<SecretSeverity, number> = { Low: 0, Medium: 1, High: 2, Critical: 3 };
+
+const SEVERITY_STYLES: Record<SecretSeverity, string> = {
+  Critical: "bg-red-500/15 text-red-600 border-red-500/30",
+  High: "bg-orange-500/10 text-orange-600 border-orange-500/20",
+  Medium: "bg-yellow-500/10 text-yellow-600 border-yellow-500/20",
+  Low: "bg-secondary text-muted-foreground border-border",
+};
+
+// 按设置隐藏低置信度结果并排序，'position' 保持后端返回的顺序
+export function arrangeSecretMatches<T extends SecretMatch>(items: T[], sort: SecretSort, hideLowConfidence: boolean): T[] {
+  const visible = hideLowConfidence ? items.filter(m => m.confidence >= LOW_CONFIDENCE) : items;
+  if (sort === 'position') return visible;
+  return [...visible].sort((a, b) =>
+    SEVERITY_RANK[b.risk_level] - SEVERITY_RANK[a.risk_level] || b.confidence - a.confidence
+  );
+}
+
+export function SeverityBadge({ match }: { match: SecretMatch }) {
+  return (
+    <span
+      className={cn("text-[10px] px-1.5 py-0.5 rounded border font-mono shrink-0", SEVERITY_STYLES[match.risk_level])}
+      title={`confidence ${match.confidence}`}
+    >
+      {match.risk_level} · {match.confidence}%
+    </span>
+  );
+}
+
+// 解码后发现的密钥，标出经过的编码
+export function EncodedBadge({ match }: { match: SecretMatch }) {
+  const { language } = useAppStore();
+  if (!match.encoded) return null;
+  const chain = match.encoded.encodings.join(' › ');
+  return (
+    <span
+      className="flex items-center gap-1 text-[10px] px-1.5 py-0.5 rounded border border-purple-500/20 bg-purple-500/10 text-purple-600 font-mono shrink-0"
+      title={getText('context', 'secretDecodedTooltip', language, { chain })}
+    >
+      <Binary size={10} />
+      {chain}
+    </span>
+  );
+}
+
+// 排序与低置信度过滤开关，两个设置保存在 useContextStore 中
+export function SecretFindingControls() {
+  const { language } = useAppStore();
+  const { secretSort, setSecretSort, hideLowConfidenceSecrets, setHideLowConfidenceSecrets } = useContextStore();
+
+  return (
+    <div className="flex items-center gap-1">
+      <button
+        onClick={() => setSecretSort(secretSort === 'risk' ? 'position' : 'risk')}
+        className={cn(
+          "flex items-center gap-1.5 px-2.5 py-1 text-xs font-medium rounded transition-colors",
+          secretSort === 'risk' ? "bg-primary/10 text-primary" : "text-muted-foreground hover:bg-secondary hover:text-foreground"
+        )}
+        title={getText('context', 'secretSortRiskTooltip', language)}
+      >
+        <ArrowDownWideNarrow size={14} />
+        {getText('context', 'secretSortRisk', language)}
+      </button>
+      <button
+        onClick={() => setHideLowConfidenceSecrets(!hideLowConfidenceSecrets)}
+        className={cn(
+          "flex items-center gap-1.5 px-2.5 py-1 text-xs font-medium rounded transition-colors",
+          hideLowConfidenceSecrets ? "bg-primary/10 text-primary" : "text-muted-foreground hover:bg-secondary hover:text-foreground"
+        )}
+        title={getText('context', 'secretHideLowTooltip', language, { score: String(LOW_CONFIDENCE) })}
+      >
+        <EyeOff size={14} />
+        {getText('context', 'secretHideLow', language)}
+      </button>
+    </div>
+  );
 }
 
 interface ScanResultDialogProps {
@@ -25,17 +110,20 @@ interface ScanResultDialogProps {
 
 export function ScanResultDialog({ isOpen, results, onConfirm, onCancel }: ScanResultDialogProps) {
   const { language } = useAppStore();
+  const { secretSort, hideLowConfidenceSecrets } = useContextStore();
 
   const [selectedIndices, setSelectedIndices] = useState<Set<number>>(new Set());
   const [ignorePermanently, setIgnorePermanently] = useState(false);
 
+  // 隐藏的低置信度结果不脱敏，也不会被加入永久白名单
+  const visible = arrangeSecretMatches(results, secretSort, hideLowConfidenceSecrets);
+
   useEffect(() => {
     if (isOpen && results.length > 0) {
-      const allIndices = new Set(results.map(r => r.index));
-      setSelectedIndices(allIndices);
+      setSelectedIndices(new Set(visible.map(r => r.index)));
       setIgnorePermanently(false);
     }
-  }, [isOpen, results]);
+  }, [isOpen, results, hideLowConfidenceSecrets]);
 
   if (!isOpen) return null;
 
@@ -47,7 +135,7 @@ export function ScanResultDialog({ isOpen, results, onConfirm, onCancel }: ScanR
   };
 
   const handleSelectAll = () => {
-    setSelectedIndices(new Set(results.map(r => r.index)));
+    setSelectedIndices(new Set(visible.map(r => r.index)));
   };
 
   const handleDeselectAll = () => {
@@ -56,7 +144,7 @@ export function ScanResultDialog({ isOpen, results, onConfirm, onCancel }: ScanR
 
   const handleInvert = () => {
     const newSet = new Set<number>();
-    results.forEach(r => {
+    visible.forEach(r => {
       if (!selectedIndices.has(r.index)) {
         newSet.add(r.index);
       }
@@ -74,13 +162,13 @@ export function ScanResultDialog({ isOpen, results, onConfirm, onCancel }: ScanR
 
   const handleConfirm = async () => {
     if (ignorePermanently) {
-        const toIgnore = results.filter(r => !selectedIndices.has(r.index));
+        const toIgnore = visible.filter(r => !selectedIndices.has(r.index));
         if (toIgnore.length > 0) {
             try {
                 const secrets = toIgnore.map(r => ({
                     id: '',
                     value: r.value.toString(),
-                    rule_id: r.kind.toString(),
+                    detected_by: r.kind.toString(),
                     created_at: 0
                 }));
                 await invoke('add_ignored_secrets', { secrets });
@@ -182,11 +270,14 @@ export function ScanResultDialog({ isOpen, results, onConfirm, onCancel }: ScanR
             <ArrowRightLeft size={14} />
             {getText('context', 'invertSel', language)}
           </button>
+          <div className="ml-auto">
+            <SecretFindingControls />
+          </div>
         </div>
 
         {/* List */}
         <div className="flex-1 overflow-y-auto p-4 custom-scrollbar bg-secondary/5 space-y-3 min-h-0">
-            {results.map((item) => {
+            {visible.map((item) => {
                 const isSelected = selectedIndices.has(item.index);
 
                 return (
@@ -213,12 +304,14 @@ export function ScanResultDialog({ isOpen, results, onConfirm, onCancel }: ScanR
                                     <span className="text-[10px] text-muted-foreground bg-secondary px-1.5 py-0.5 rounded font-mono">
                                         Line {item.line_number}
                                     </span>
+                                    <SeverityBadge match={item} />
+                                    <EncodedBadge match={item} />
                                 </div>
                             </div>
                             
                             <span className={cn(
                                 "text-[10px] px-1.5 py-0.5 rounded font-mono uppercase shrink-0",
-                                item.risk_level === 'High' ? "bg-red-500/10 text-red-500" : "bg-yellow-500/10 text-yellow-500"
+                                item.risk_level === 'Critical' || item.risk_level === 'High' ? "bg-red-500/10 text-red-500" : "bg-yellow-500/10 text-yellow-500"
                             )}>
                                 {isSelected ? getText('context', 'willRedact', language) : getText('context', 'keepRaw', language)}
                             </span>
@@ -226,7 +319,7 @@ export function ScanResultDialog({ isOpen, results, onConfirm, onCancel }: ScanR
                         
                         {/* Snippet View */}
                         <div className="pl-7">
-                            {renderSnippet(item.snippet, item.value.toString(), item.snippet_start_line)}
+                            {renderSnippet(item.snippet, matchedText(item), item.snippet_start_line)}
                         </div>
 
                         {isSelected && (
@@ -234,7 +327,7 @@ export function ScanResultDialog({ isOpen, results, onConfirm, onCancel }: ScanR
                                 <ArrowRight size={12} className="text-muted-foreground/30" />
                                 <span className="text-[10px] text-muted-foreground uppercase font-bold tracking-wider">Becomes:</span>
                                 <code className="text-[10px] bg-green-500/10 text-green-600 px-1.5 py-0.5 rounded border border-green-500/20 font-mono">
-                                    {getMaskedValue(item.value)}
+                                    {getMaskedValue(matchedText(item))}
                                 </code>
                             </div>
                         )}
@@ -0,0 +1,347 @@
+import { useState, useEffect, useRef } from 'react';
+import { invoke } from '@tauri-apps/api/core';
+import { listen } from '@tauri-apps/api/event';
+import { save } from '@tauri-apps/plugin-dialog';
+import { v4 as uuidv4 } from 'uuid';
+import { ShieldAlert, ShieldCheck, X, Loader2, History, FolderSearch, GitCommit, Download } from 'lucide-react';
+import { useAppStore } from '@/store/useAppStore';
+import { useContextStore } from '@/store/useContextStore';
+import { getText } from '@/lib/i18n';
+import { mergeIgnoreConfig } from '@/lib/context_assembler';
+import { cn } from '@/lib/utils';
+import { SecretMatch, SecretFindingControls, SeverityBadge, EncodedBadge, arrangeSecretMatches } from './ScanResultDialog';
+
+// 与 Rust 端 gitleaks::directory::FileSecretMatch 对应
+export interface FileSecretMatch extends SecretMatch {
+  path: string;
+}
+
+interface DirectoryScan {
+  findings: FileSecretMatch[];
+  files_scanned: number;
+  skipped_binary: number;
+  skipped_large: number;
+  // 读取失败的文件，未扫描
+  skipped_unreadable: number;
+}
+
+// 与 Rust 端 gitleaks::history::HistoryFinding 对应
+export interface HistoryFinding extends FileSecretMatch {
+  commit: string;
+  author: string;
+  email: string;
+  date: string;
+  message: string;
+  occurrences: number;
+}
+
+interface ScanProgress {
+  job_id: string;
+  done: number;
+  total: number;
+}
+
+// 与 Rust 端 gitleaks::hook::HookStatus 对应
+interface HookStatus {
+  installed: boolean;
+  foreign_hook: boolean;
+  hook_path: string;
+  cli_path: string | null;
+}
+
+type ScanMode = 'tree' | 'history';
+
+// 与 Rust 端 gitleaks::report::ReportFormat 对应
+type ReportFormat = 'sarif' | 'json' | 'csv';
+
+const REPORT_FORMATS: { format: ReportFormat; label: string; extension: string }[] = [
+  { format: 'sarif', label: 'SARIF', extension: 'sarif' },
+  { format: 'json', label: 'JSON', extension: 'json' },
+  { format: 'csv', label: 'CSV', extension: 'csv' },
+];
+
+interface SecretScanDialogProps {
+  isOpen: boolean;
+  projectRoot: string | null;
+  onClose: () => void;
+}
+
+const maskValue = (value: string) =>
+  value.length <= 8 ? '*'.repeat(value.length) : value.substring(0, 4) + '*'.repeat(Math.min(value.length - 4, 16));
+
+export function SecretScanDialog({ isOpen, projectRoot, onClose }: SecretScanDialogProps) {
+  const { language, globalIgnore } = useAppStore();
+  const { projectIgnore, secretSort, hideLowConfidenceSecrets } = useContextStore();
+
+  const [mode, setMode] = useState<ScanMode>('tree');
+  const [range, setRange] = useState('');
+  const [findings, setFindings] = useState<(FileSecretMatch | HistoryFinding)[] | null>(null);
+  const [summary, setSummary] = useState<string | null>(null);
+  const [progress, setProgress] = useState<{ done: number; total: number } | null>(null);
+  const [isScanning, setIsScanning] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+  const [hookStatus, setHookStatus] = useState<HookStatus | null>(null);
+  const [isHookBusy, setIsHookBusy] = useState(false);
+  const jobRef = useRef<string | null>(null);
+
+  useEffect(() => {
+    if (isOpen) {
+      setFindings(null);
+      setSummary(null);
+      setError(null);
+    }
+  }, [isOpen, mode]);
+
+  useEffect(() => {
+    if (!isOpen || !projectRoot) return;
+    // 不是 git 仓库时没有状态，不显示钩子设置
+    invoke<HookStatus>('get_secret_hook_status', { projectPath: projectRoot })
+      .then(setHookStatus)
+      .catch(() => setHookStatus(null));
+  }, [isOpen, projectRoot]);
+
+  if (!isOpen) return null;
+
+  const visible = findings ? arrangeSecretMatches(findings, secretSort, hideLowConfidenceSecrets) : null;
+
+  const handleScan = async () => {
+    if (!projectRoot || isScanning) return;
+
+    const jobId = uuidv4();
+    jobRef.current = jobId;
+    setIsScanning(true);
+    setError(null);
+    setFindings(null);
+    setSummary(null);
+    const unlisten = await listen<ScanProgress>('secret-scan-progress', (event) => {
+      if (event.payload.job_id === jobId) {
+        setProgress({ done: event.payload.done, total: event.payload.total });
+      }
+    });
+    try {
+      if (mode === 'tree') {
+        const result = await invoke<DirectoryScan>('scan_directory', {
+          root: projectRoot,
+          config: mergeIgnoreConfig(globalIgnore, projectIgnore),
+          jobId
+        });
+        setFindings(result.findings);
+        let summary = getText('context', 'secretScanTreeSummary', language, {
+          files: String(result.files_scanned),
+          skipped: String(result.skipped_binary + result.skipped_large)
+        });
+        if (result.skipped_unreadable > 0) {
+          summary += getText('context', 'secretScanUnreadable', language, { count: String(result.skipped_unreadable) });
+        }
+        setSummary(summary);
+      } else {
+        const result = await invoke<HistoryFinding[]>('scan_git_history', {
+          projectPath: projectRoot,
+          range: range.trim() || null,
+          jobId
+        });
+        setFindings(result);
+      }
+    } catch (err) {
+      if (err !== 'Scan cancelled') setError(String(err));
+    } finally {
+      unlisten();
+      jobRef.current = null;
+      setProgress(null);
+      setIsScanning(false);
+    }
+  };
+
+  const handleCancel = () => {
+    if (jobRef.current) invoke('cancel_secret_scan', { jobId: jobRef.current });
+  };
+
+  const handleToggleHook = async () => {
+    if (!projectRoot || !hookStatus || isHookBusy) return;
+    setIsHookBusy(true);
+    setError(null);
+    try {
+      const command = hookStatus.installed ? 'uninstall_secret_hook' : 'install_secret_hook';
+      setHookStatus(await invoke<HookStatus>(command, { projectPath: projectRoot }));
+    } catch (err) {
+      setError(String(err));
+    } finally {
+      setIsHookBusy(false);
+    }
+  };
+
+  const handleExport = async (format: ReportFormat, extension: string) => {
+    if (!visible || visible.length === 0) return;
+    try {
+      const savePath = await save({
+        filters: [{ name: format.toUpperCase(), extensions: [extension] }],
+        defaultPath: `secrets-report.${extension}`
+      });
+      if (!savePath) return;
+      const count = await invoke<number>('export_secret_report', { findings: visible, format, savePath, projectRoot });
+      setSummary(getText('context', 'secretReportSaved', language, { count: String(count) }));
+    } catch (err) {
+      setError(String(err));
+    }
+  };
+
+  const handleClose = () => {
+    handleCancel();
+    onClose();
+  };
+
+  return (
+    <div className="fixed inset-0 z-[100] bg-black/60 backdrop-blur-sm flex items-center justify-center animate-in fade-in duration-200 p-4">
+      <div className="w-full max-w-[720px] bg-background border border-border rounded-xl shadow-2xl flex flex-col overflow-hidden animate-in zoom-in-95 duration-200 max-h-[85vh]">
+
+        {/* Header */}
+        <div className="p-6 pb-4 border-b border-border shrink-0">
+          <div className="flex items-start gap-4">
+            <div className="w-12 h-12 rounded-full bg-primary/10 text-primary flex items-center justify-center shrink-0">
+              <ShieldAlert size={22} />
+            </div>
+            <div>
+              <h3 className="font-semibold text-lg text-foreground">{getText('context', 'secretScanTitle', language)}</h3>
+              <p className="text-sm text-muted-foreground mt-1 leading-relaxed">
+                {getText('context', mode === 'tree' ? 'secretScanTreeDesc' : 'secretScanDesc', language)}
+              </p>
+            </div>
+            <button onClick={handleClose} className="ml-auto text-muted-foreground hover:text-foreground">
+              <X size={20} />
+            </button>
+          </div>
+
+          <div className="flex items-center gap-2 mt-4">
+            <div className="flex items-center gap-1 p-1 rounded-lg border border-border bg-card shrink-0">
+              {([['tree', FolderSearch, 'secretScanModeTree'], ['history', History, 'secretScanModeHistory']] as const).map(([value, Icon, label]) => (
+                <button
+                  key={value}
+                  onClick={() => setMode(value)}
+                  disabled={isScanning}
+                  className={cn(
+                    "flex items-center gap-1.5 px-2.5 py-1 rounded-md text-xs font-medium transition-colors",
+                    mode === value ? "bg-primary/10 text-primary" : "text-muted-foreground hover:text-foreground hover:bg-secondary/50"
+                  )}
+                >
+                  <Icon size={12} />
+                  {getText('context', label, language)}
+                </button>
+              ))}
+            </div>
+            {mode === 'history' ? (
+              <input
+                value={range}
+                onChange={e => setRange(e.target.value)}
+                onKeyDown={e => e.key === 'Enter' && handleScan()}
+                placeholder={getText('context', 'secretScanRangePlaceholder', language)}
+                className="flex-1 min-w-0 bg-secondary/50 border border-border rounded-md px-3 py-1.5 text-sm font-mono outline-none focus:ring-1 focus:ring-primary/50"
+              />
+            ) : (
+              <div className="flex-1" />
+            )}
+            {isScanning ? (
+              <button
+                onClick={handleCancel}
+                className="flex items-center gap-2 px-4 py-1.5 text-sm font-medium rounded-md border border-border text-muted-foreground hover:text-destructive hover:border-destructive/30 transition-colors"
+              >
+                <Loader2 size={14} className="animate-spin" />
+                {progress ? `${progress.done}/${progress.total}` : getText('context', 'btnCancelExport', language)}
+              </button>
+            ) : (
+              <button
+                onClick={handleScan}
+                disabled={!projectRoot}
+                className="flex items-center gap-2 px-4 py-1.5 text-sm font-medium rounded-md bg-primary text-primary-foreground hover:bg-primary/90 disabled:opacity-50 transition-colors"
+              >
+                <ShieldAlert size={14} />
+                {getText('context', 'secretScanAction', language)}
+              </button>
+            )}
+          </div>
+          {hookStatus && (
+            <div className="flex items-center gap-2 mt-3 text-xs min-w-0">
+              <GitCommit size={14} className="text-muted-foreground shrink-0" />
+              <span className="font-medium text-foreground shrink-0">{getText('context', 'secretHook', language)}</span>
+              <span
+                className={cn("truncate", hookStatus.foreign_hook ? "text-yellow-600" : "text-muted-foreground")}
+                title={hookStatus.hook_path}
+              >
+                {getText('context', hookStatus.installed ? 'secretHookInstalled' : hookStatus.foreign_hook ? 'secretHookForeign' : 'secretHookDesc', language)}
+              </span>
+              <button
+                onClick={handleToggleHook}
+                disabled={isHookBusy || hookStatus.foreign_hook}
+                className="ml-auto flex items-center gap-1.5 px-2.5 py-1 rounded-md border border-border text-muted-foreground hover:text-foreground hover:bg-secondary/50 disabled:opacity-50 transition-colors shrink-0"
+              >
+                {isHookBusy && <Loader2 size={12} className="animate-spin" />}
+                {getText('context', hookStatus.installed ? 'secretHookUninstall' : 'secretHookInstall', language)}
+              </button>
+            </div>
+          )}
+          {error && <p className="text-xs text-destructive mt-2">{error}</p>}
+        </div>
+
+        {/* Findings */}
+        <div className="flex-1 overflow-y-auto p-4 custom-scrollbar bg-secondary/5 space-y-2 min-h-0">
+          {!findings && !isScanning && (
+            <p className="text-xs text-muted-foreground text-center py-6">{getText('context', 'secretScanEmpty', language)}</p>
+          )}
+          {findings && findings.length === 0 && (
+            <div className="flex flex-col items-center gap-2 py-6 text-green-600">
+              <ShieldCheck size={28} />
+              <span className="text-xs">{getText('context', 'secretScanClean', language)}</span>
+            </div>
+          )}
+          {visible?.map(f => (
+            <div key={`${'commit' in f ? f.commit : ''}:${f.path}:${f.line_number}:${f.kind}`} className="p-3 rounded-md border border-border bg-background text-xs space-y-1">
+              <div className="flex items-center gap-2 min-w-0">
+                <span className="px-1.5 py-0.5 rounded bg-destructive/10 text-destructive font-mono shrink-0">{f.kind}</span>
+                <span className="font-mono text-foreground truncate" title={f.path}>{f.path}:{f.line_number}</span>
+                <SeverityBadge match={f} />
+                <EncodedBadge match={f} />
+                <span className="ml-auto font-mono text-muted-foreground shrink-0">{maskValue(String(f.value))}</span>
+              </div>
+              {'commit' in f && <div className="flex items-center gap-2 text-muted-foreground min-w-0">
+                <span className="font-mono shrink-0">{f.commit.slice(0, 8)}</span>
+                <span className="shrink-0">{f.date}</span>
+                <span className="shrink-0">{f.author}</span>
+                <span className="truncate" title={f.message}>{f.message}</span>
+                {f.occurrences > 1 && (
+                  <span className="ml-auto shrink-0">
+                    {getText('context', 'secretScanOccurrences', language, { count: String(f.occurrences) })}
+                  </span>
+                )}
+              </div>}
+            </div>
+          ))}
+        </div>
+
+        {findings && (findings.length > 0 || summary) && (
+          <div className="p-4 border-t border-border flex items-center justify-between text-xs text-muted-foreground shrink-0">
+            <div className="flex items-center gap-3">
+              <span>{findings.length > 0 && getText('context', 'issuesFound', language, { count: String(findings.length) })}</span>
+              {findings.length > 0 && <SecretFindingControls />}
+            </div>
+            <div className="flex items-center gap-3">
+              {summary && <span>{summary}</span>}
+              {findings.length > 0 && (
+                <div className="flex items-center gap-1" title={getText('context', 'secretReportExport', language)}>
+                  <Download size={12} />
+                  {REPORT_FORMATS.map(({ format, label, extension }) => (
+                    <button
+                      key={format}
+                      onClick={() => handleExport(format, extension)}
+                      className="px-2 py-0.5 rounded border border-border hover:text-foreground hover:bg-secondary/50 transition-colors"
+                    >
+                      {label}
+                    </button>
+                  ))}
+                </div>
+              )}
+            </div>
+          </div>
+        )}
+      </div>
+    </div>
+  );
+}
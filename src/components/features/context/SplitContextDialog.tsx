@@ -0,0 +1,215 @@
+import { useState, useEffect } from 'react';
+import { invoke } from '@tauri-apps/api/core';
+import { save } from '@tauri-apps/plugin-dialog';
+import { writeText } from '@tauri-apps/plugin-clipboard-manager';
+import { Scissors, X, Copy, Check, Save, Loader2 } from 'lucide-react';
+import { FileNode, OUTPUT_FORMAT_EXTENSIONS } from '@/types/context';
+import { AIModelConfig, isEstimatedCount, tokenizerOf } from '@/types/model';
+import { getSelectedPaths, generateHeaderWithRepoMap, mergeIgnoreConfig } from '@/lib/context_assembler';
+import { cn } from '@/lib/utils';
+import { useAppStore } from '@/store/useAppStore';
+import { useContextStore } from '@/store/useContextStore';
+import { getText } from '@/lib/i18n';
+
+// 与 Rust 端 context::split::ContextPart 对应
+interface ContextPart {
+  index: number;
+  total: number;
+  content: string;
+  tokens: number;
+  files: string[];
+}
+
+interface SplitContextDialogProps {
+  isOpen: boolean;
+  fileTree: FileNode[];
+  defaultFileName: string;
+  models: AIModelConfig[];
+  onClose: () => void;
+}
+
+const DEFAULT_PART_TOKENS = 30000;
+
+export function SplitContextDialog({ isOpen, fileTree, defaultFileName, models, onClose }: SplitContextDialogProps) {
+  const { language, globalIgnore } = useAppStore();
+  const {
+    removeComments, outputFormat, includeRepoMap, projectRoot, projectIgnore, getSkeletonOptions, getNotebookOptions,
+    targetModelId, setTargetModelId, redactSecrets
+  } = useContextStore();
+
+  const [maxTokens, setMaxTokens] = useState(DEFAULT_PART_TOKENS);
+  const [parts, setParts] = useState<ContextPart[]>([]);
+  const [isSplitting, setIsSplitting] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+  const [copiedIndex, setCopiedIndex] = useState<number | null>(null);
+
+  useEffect(() => {
+    if (isOpen) {
+      setParts([]);
+      setError(null);
+    }
+  }, [isOpen]);
+
+  if (!isOpen) return null;
+
+  // 每段的 Token 上限按目标模型的分词器计算
+  const targetModel = models.find(m => m.id === targetModelId) ?? models[0];
+  const tokenizer = tokenizerOf(targetModel);
+  const estimated = isEstimatedCount(targetModel);
+
+  const handleSplit = async () => {
+    const paths = getSelectedPaths(fileTree);
+    if (paths.length === 0 || maxTokens <= 0) return;
+
+    setIsSplitting(true);
+    setError(null);
+    try {
+      const header = await generateHeaderWithRepoMap(
+        fileTree,
+        removeComments,
+        includeRepoMap ? projectRoot : null,
+        mergeIgnoreConfig(globalIgnore, projectIgnore)
+      );
+      const result = await invoke<ContextPart[]>('get_split_context', {
+        paths,
+        header,
+        removeComments,
+        format: outputFormat,
+        skeleton: getSkeletonOptions(),
+        notebook: getNotebookOptions(),
+        maxTokens,
+        tokenizer,
+        redactSecrets,
+        projectRoot
+      });
+      setParts(result);
+    } catch (err) {
+      setParts([]);
+      setError(String(err));
+    } finally {
+      setIsSplitting(false);
+    }
+  };
+
+  const handleCopy = async (part: ContextPart) => {
+    await writeText(part.content);
+    setCopiedIndex(part.index);
+    setTimeout(() => setCopiedIndex(null), 2000);
+  };
+
+  const handleSaveAll = async () => {
+    const savePath = await save({
+      filters: [{ name: 'Text File', extensions: [OUTPUT_FORMAT_EXTENSIONS[outputFormat]] }],
+      defaultPath: defaultFileName
+    });
+    if (!savePath) return;
+    try {
+      await invoke<string[]>('save_context_parts', { parts: parts.map(p => p.content), savePath });
+      onClose();
+    } catch (err) {
+      setError(String(err));
+    }
+  };
+
+  const fileName = (path: string) => path.split(/[\\/]/).pop() || path;
+
+  return (
+    <div className="fixed inset-0 z-[100] bg-black/60 backdrop-blur-sm flex items-center justify-center animate-in fade-in duration-200 p-4">
+      <div className="w-full max-w-[600px] bg-background border border-border rounded-xl shadow-2xl flex flex-col overflow-hidden animate-in zoom-in-95 duration-200 max-h-[85vh]">
+
+        {/* Header */}
+        <div className="p-6 pb-4 border-b border-border shrink-0">
+          <div className="flex items-start gap-4">
+            <div className="w-12 h-12 rounded-full bg-primary/10 text-primary flex items-center justify-center shrink-0">
+              <Scissors size={22} />
+            </div>
+            <div>
+              <h3 className="font-semibold text-lg text-foreground">{getText('context', 'splitTitle', language)}</h3>
+              <p className="text-sm text-muted-foreground mt-1 leading-relaxed">{getText('context', 'splitDesc', language)}</p>
+            </div>
+            <button onClick={onClose} className="ml-auto text-muted-foreground hover:text-foreground">
+              <X size={20} />
+            </button>
+          </div>
+
+          <div className="flex items-center gap-2 mt-4">
+            <label className="text-xs text-muted-foreground whitespace-nowrap">{getText('context', 'splitMaxTokens', language)}</label>
+            <input
+              type="number"
+              min={1}
+              value={maxTokens}
+              onChange={e => setMaxTokens(Math.max(0, parseInt(e.target.value) || 0))}
+              className="flex-1 bg-secondary/50 border border-border rounded-md px-3 py-1.5 text-sm font-mono outline-none focus:ring-1 focus:ring-primary/50"
+            />
+            <select
+              value={targetModel?.id ?? ''}
+              onChange={e => {
+                setTargetModelId(e.target.value);
+                setParts([]);
+              }}
+              className="max-w-[160px] bg-secondary/50 border border-border rounded-md px-2 py-1.5 text-sm outline-none focus:ring-1 focus:ring-primary/50"
+              title={getText('context', 'targetModelHint', language)}
+            >
+              {models.map(m => <option key={m.id} value={m.id}>{m.name}</option>)}
+            </select>
+            <button
+              onClick={handleSplit}
+              disabled={isSplitting || maxTokens <= 0}
+              className="flex items-center gap-2 px-4 py-1.5 text-sm font-medium rounded-md bg-primary text-primary-foreground hover:bg-primary/90 disabled:opacity-50 transition-colors"
+            >
+              {isSplitting ? <Loader2 size={14} className="animate-spin" /> : <Scissors size={14} />}
+              {getText('context', 'splitAction', language)}
+            </button>
+          </div>
+          {estimated && <p className="text-[10px] text-muted-foreground opacity-70 mt-2">≈ {getText('context', 'tokenEstimateNote', language)}</p>}
+          {error && <p className="text-xs text-destructive mt-2">{error}</p>}
+        </div>
+
+        {/* Parts */}
+        <div className="flex-1 overflow-y-auto p-4 custom-scrollbar bg-secondary/5 space-y-2 min-h-0">
+          {parts.length === 0 && !isSplitting && (
+            <p className="text-xs text-muted-foreground text-center py-6">{getText('context', 'splitEmpty', language)}</p>
+          )}
+          {parts.map(part => (
+            <div key={part.index} className="border border-border rounded-lg p-3 bg-background flex items-center gap-3">
+              <div className="flex-1 min-w-0">
+                <div className="text-sm font-medium text-foreground">
+                  {getText('context', 'splitPartLabel', language, { index: part.index.toString(), total: part.total.toString() })}
+                  <span className="ml-2 text-xs font-mono text-muted-foreground">{estimated ? '≈' : ''}{part.tokens.toLocaleString()} tokens</span>
+                </div>
+                <div className="text-xs text-muted-foreground truncate" title={part.files.join('\n')}>
+                  {part.files.length > 0 ? part.files.map(fileName).join(', ') : getText('context', 'splitHeaderOnly', language)}
+                </div>
+              </div>
+              <button
+                onClick={() => handleCopy(part)}
+                className={cn(
+                  "flex items-center gap-1.5 px-3 py-1.5 text-xs font-medium rounded-md border transition-colors shrink-0",
+                  copiedIndex === part.index
+                    ? "bg-green-500/10 border-green-500/30 text-green-600"
+                    : "border-border text-muted-foreground hover:text-primary hover:border-primary/30"
+                )}
+              >
+                {copiedIndex === part.index ? <Check size={12} /> : <Copy size={12} />}
+                {getText('common', copiedIndex === part.index ? 'copied' : 'copy', language)}
+              </button>
+            </div>
+          ))}
+        </div>
+
+        {/* Footer */}
+        {parts.length > 0 && (
+          <div className="p-4 border-t border-border flex justify-end shrink-0">
+            <button
+              onClick={handleSaveAll}
+              className="flex items-center gap-2 px-4 py-2 text-sm font-medium rounded-md bg-secondary hover:bg-secondary/80 text-foreground transition-colors"
+            >
+              <Save size={14} />
+              {getText('context', 'splitSaveAll', language)}
+            </button>
+          </div>
+        )}
+      </div>
+    </div>
+  );
+}
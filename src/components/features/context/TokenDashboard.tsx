@@ -2,18 +2,40 @@ import { useMemo, useState, useEffect } from 'react';
 import { invoke } from '@tauri-apps/api/core';
 import {
   CheckCircle2, AlertCircle, FileText, Database, Cpu, Save,
-  DollarSign, PieChart, TrendingUp, AlertTriangle, Eraser, X, ShieldCheck, Loader2
+  DollarSign, PieChart, TrendingUp, AlertTriangle, Eraser, X, ShieldCheck, Loader2, Shrink, FolderTree, Scissors, GitBranch, EyeOff, NotebookText, Network, ShieldAlert
 } from 'lucide-react';
-import { ContextStats, getSelectedPaths } from '@/lib/context_assembler';
+import { ContextStats, FileStatus, getSelectedPaths } from '@/lib/context_assembler';
 import { analyzeContext } from '@/lib/context_analytics';
-import { FileNode } from '@/types/context';
-import { AIModelConfig } from '@/types/model';
+import { FileNode, OutputFormat } from '@/types/context';
+import { AIModelConfig, TokenizerKind, isEstimatedCount, tokenizerOf } from '@/types/model';
 import { cn } from '@/lib/utils';
 import { useAppStore } from '@/store/useAppStore';
 import { useContextStore } from '@/store/useContextStore';
 import { getText } from '@/lib/i18n';
 import { NumberTicker } from '@/components/ui/NumberTicker';
 
+const OUTPUT_FORMATS: { value: OutputFormat; label: string }[] = [
+  { value: 'xml', label: 'XML' },
+  { value: 'markdown', label: 'Markdown' },
+  { value: 'json', label: 'JSON' },
+  { value: 'plain', label: 'Text' }
+];
+
+const FILE_STATUS_KEYS: Record<FileStatus, string> = {
+  included: 'statusIncluded',
+  binary: 'statusBinary',
+  too_large: 'statusTooLarge',
+  error: 'statusError'
+};
+
+const baseName = (path: string) => path.split(/[\\/]/).pop() || path;
+
+// 流式复制 / 保存的进度（已写出的文件数 / 总数）
+export interface ExportProgress {
+  done: number;
+  total: number;
+}
+
 interface TokenDashboardProps {
   stats?: any;
   fileTree: FileNode[];
@@ -21,6 +43,11 @@ interface TokenDashboardProps {
   onCopy: () => void;
   onSave: () => void;
   isGenerating: boolean;
+  exportProgress?: ExportProgress | null;
+  onCancel?: () => void;
+  onSplit?: () => void;
+  onGitChanges?: () => void;
+  onSecretScan?: () => void;
 }
 
 export function TokenDashboard({
@@ -28,13 +55,27 @@ export function TokenDashboard({
   models,
   onCopy,
   onSave,
-  isGenerating
+  isGenerating,
+  exportProgress,
+  onCancel,
+  onSplit,
+  onGitChanges,
+  onSecretScan
 }: TokenDashboardProps) {
   const { language } = useAppStore();
-  const { removeComments, setRemoveComments, toggleSelect, detectSecrets, setDetectSecrets } = useContextStore();
+  const {
+    removeComments, setRemoveComments, outputFormat, setOutputFormat,
+    skeletonPaths, autoSkeleton, setAutoSkeleton, getSkeletonOptions,
+    notebookOutputs, setNotebookOutputs, getNotebookOptions, includeRepoMap, setIncludeRepoMap,
+    toggleSelect, detectSecrets, setDetectSecrets, redactSecrets, setRedactSecrets, projectRoot,
+    targetModelId, setTargetModelId
+  } = useContextStore();
 
   const [stats, setStats] = useState<ContextStats>({ file_count: 0, total_size: 0, total_tokens: 0 });
+  // 各分词器下的总 Token 数，用于按模型显示占用和成本
+  const [tokenTotals, setTokenTotals] = useState<Partial<Record<TokenizerKind, number>>>({});
   const [isCalculating, setIsCalculating] = useState(false);
+  const [groupMode, setGroupMode] = useState<'directory' | 'extension'>('directory');
 
   // 乐观更新：文件数量不需要问 Rust，前端直接算，实现 0 延迟响应
   const instantFileCount = useMemo(() => {
@@ -49,6 +90,17 @@ export function TokenDashboard({
     return count;
   }, [fileTree]);
 
+  // 文件明细和总数按目标模型的分词器统计
+  const targetModel = useMemo(
+    () => models.find(m => m.id === targetModelId) ?? models[0],
+    [models, targetModelId]
+  );
+  const targetTokenizer = tokenizerOf(targetModel);
+  const tokenizerKey = useMemo(
+    () => Array.from(new Set(models.map(tokenizerOf))).sort().join(','),
+    [models]
+  );
+
   useEffect(() => {
     let isMounted = true;
 
@@ -57,7 +109,10 @@ export function TokenDashboard({
 
       // 如果没有文件，直接归零
       if (paths.length === 0) {
-        if (isMounted) setStats({ file_count: 0, total_size: 0, total_tokens: 0 });
+        if (isMounted) {
+          setStats({ file_count: 0, total_size: 0, total_tokens: 0 });
+          setTokenTotals({});
+        }
         return;
       }
 
@@ -65,12 +120,24 @@ export function TokenDashboard({
       setIsCalculating(true);
 
       try {
-        const res = await invoke<ContextStats>('calculate_context_stats', {
-          paths: paths,
-          removeComments: removeComments
-        });
+        // 目标模型的分词器排第一；其余模型用到的分词器只取总数（有缓存，重复统计很快）
+        const others = tokenizerKey.split(',').filter(k => k && k !== targetTokenizer) as TokenizerKind[];
+        const tokenizers = [targetTokenizer, ...others];
+        const results = await Promise.all(tokenizers.map(tokenizer =>
+          invoke<ContextStats>('calculate_context_stats', {
+            paths: paths,
+            removeComments: removeComments,
+            format: outputFormat,
+            skeleton: getSkeletonOptions(),
+            notebook: getNotebookOptions(),
+            tokenizer
+          })
+        ));
 
-        if (isMounted) setStats(res);
+        if (isMounted) {
+          setStats(results[0]);
+          setTokenTotals(Object.fromEntries(tokenizers.map((k, i) => [k, results[i].total_tokens])));
+        }
       } catch (err) {
         console.error("Stats calculation failed:", err);
       } finally {
@@ -83,11 +150,19 @@ export function TokenDashboard({
       isMounted = false;
       clearTimeout(timer);
     };
-  }, [fileTree, removeComments]);
+  }, [fileTree, removeComments, outputFormat, skeletonPaths, autoSkeleton, notebookOutputs, targetTokenizer, tokenizerKey]);
 
   const analytics = useMemo(() => {
-    return analyzeContext(fileTree, stats.total_tokens, models);
-  }, [fileTree, stats.total_tokens, models]);
+    return analyzeContext(fileTree, model => tokenTotals[tokenizerOf(model)] ?? stats.total_tokens, models);
+  }, [fileTree, tokenTotals, stats.total_tokens, models]);
+
+  const tokensEstimated = isEstimatedCount(targetModel);
+
+  const topFiles = useMemo(() => (stats.files ?? []).slice(0, 5), [stats.files]);
+  const groups = useMemo(
+    () => ((groupMode === 'directory' ? stats.by_directory : stats.by_extension) ?? []).slice(0, 6),
+    [stats.by_directory, stats.by_extension, groupMode]
+  );
 
   const formatSize = (bytes: number) => {
     if (bytes === 0) return '0 B';
@@ -124,9 +199,11 @@ export function TokenDashboard({
         />
         <StatCard
             icon={<Cpu className="text-orange-500" />}
-            label={getText('context', 'statTokens', language)}
+            label={targetModel ? `${getText('context', 'statTokens', language)} · ${targetModel.name}` : getText('context', 'statTokens', language)}
             value={stats.total_tokens.toLocaleString()}
             rawValue={stats.total_tokens}
+            prefix={tokensEstimated ? '≈' : undefined}
+            hint={tokensEstimated ? getText('context', 'tokenEstimateNote', language) : undefined}
             highlight
             loading={isCalculating}
         />
@@ -160,6 +237,32 @@ export function TokenDashboard({
             </div>
          </button>
 
+         {/* 自动脱敏开关 */}
+         <button
+           onClick={() => setRedactSecrets(!redactSecrets)}
+           className={cn(
+             "flex items-center gap-3 px-4 py-2 rounded-lg border transition-all duration-200 shadow-sm",
+             redactSecrets
+               ? "bg-orange-500/10 border-orange-500/30 text-orange-600"
+               : "bg-card border-border text-muted-foreground hover:bg-secondary/50"
+           )}
+           title={getText('context', 'redactSecretsTooltip', language)}
+         >
+            <div className={cn(
+                "w-8 h-4 rounded-full relative transition-colors duration-300",
+                redactSecrets ? "bg-orange-500" : "bg-slate-300 dark:bg-slate-600"
+            )}>
+                <div className={cn(
+                    "absolute top-0.5 w-3 h-3 bg-white rounded-full transition-transform duration-300 shadow-sm",
+                    redactSecrets ? "left-4.5 translate-x-0" : "left-0.5"
+                )} style={{ left: redactSecrets ? '18px' : '2px' }} />
+            </div>
+            <div className="flex items-center gap-2">
+                <EyeOff size={16} />
+                <span className="text-sm font-medium">{getText('context', 'redactSecrets', language)}</span>
+            </div>
+         </button>
+
          {/* 移除注释开关 */}
          <button
            onClick={() => setRemoveComments(!removeComments)}
@@ -184,6 +287,105 @@ export function TokenDashboard({
                 <span className="text-sm font-medium">{getText('context', 'removeComments', language)}</span>
             </div>
          </button>
+
+         {/* 自动骨架开关 */}
+         <button
+           onClick={() => setAutoSkeleton(!autoSkeleton)}
+           className={cn(
+             "flex items-center gap-3 px-4 py-2 rounded-lg border transition-all duration-200 shadow-sm",
+             autoSkeleton
+               ? "bg-primary/10 border-primary/30 text-primary"
+               : "bg-card border-border text-muted-foreground hover:bg-secondary/50"
+           )}
+           title={getText('context', 'autoSkeletonTooltip', language)}
+         >
+            <div className={cn(
+                "w-8 h-4 rounded-full relative transition-colors duration-300",
+                autoSkeleton ? "bg-primary" : "bg-slate-300 dark:bg-slate-600"
+            )}>
+                <div className={cn(
+                    "absolute top-0.5 w-3 h-3 bg-white rounded-full transition-transform duration-300 shadow-sm",
+                    autoSkeleton ? "left-4.5 translate-x-0" : "left-0.5"
+                )} style={{ left: autoSkeleton ? '18px' : '2px' }} />
+            </div>
+            <div className="flex items-center gap-2">
+                <Shrink size={16} />
+                <span className="text-sm font-medium">{getText('context', 'autoSkeleton', language)}</span>
+            </div>
+         </button>
+
+         {/* Notebook 输出开关 */}
+         <button
+           onClick={() => setNotebookOutputs(!notebookOutputs)}
+           className={cn(
+             "flex items-center gap-3 px-4 py-2 rounded-lg border transition-all duration-200 shadow-sm",
+             notebookOutputs
+               ? "bg-primary/10 border-primary/30 text-primary"
+               : "bg-card border-border text-muted-foreground hover:bg-secondary/50"
+           )}
+           title={getText('context', 'notebookOutputsTooltip', language)}
+         >
+            <div className={cn(
+                "w-8 h-4 rounded-full relative transition-colors duration-300",
+                notebookOutputs ? "bg-primary" : "bg-slate-300 dark:bg-slate-600"
+            )}>
+                <div className={cn(
+                    "absolute top-0.5 w-3 h-3 bg-white rounded-full transition-transform duration-300 shadow-sm",
+                    notebookOutputs ? "left-4.5 translate-x-0" : "left-0.5"
+                )} style={{ left: notebookOutputs ? '18px' : '2px' }} />
+            </div>
+            <div className="flex items-center gap-2">
+                <NotebookText size={16} />
+                <span className="text-sm font-medium">{getText('context', 'notebookOutputs', language)}</span>
+            </div>
+         </button>
+
+         {/* 仓库符号图开关 */}
+         <button
+           onClick={() => setIncludeRepoMap(!includeRepoMap)}
+           className={cn(
+             "flex items-center gap-3 px-4 py-2 rounded-lg border transition-all duration-200 shadow-sm",
+             includeRepoMap
+               ? "bg-primary/10 border-primary/30 text-primary"
+               : "bg-card border-border text-muted-foreground hover:bg-secondary/50"
+           )}
+           title={getText('context', 'repoMapTooltip', language)}
+         >
+            <div className={cn(
+                "w-8 h-4 rounded-full relative transition-colors duration-300",
+                includeRepoMap ? "bg-primary" : "bg-slate-300 dark:bg-slate-600"
+            )}>
+                <div className={cn(
+                    "absolute top-0.5 w-3 h-3 bg-white rounded-full transition-transform duration-300 shadow-sm",
+                    includeRepoMap ? "left-4.5 translate-x-0" : "left-0.5"
+                )} style={{ left: includeRepoMap ? '18px' : '2px' }} />
+            </div>
+            <div className="flex items-center gap-2">
+                <Network size={16} />
+                <span className="text-sm font-medium">{getText('context', 'repoMap', language)}</span>
+            </div>
+         </button>
+
+         {/* 输出格式 */}
+         <div
+           className="flex items-center gap-1 p-1 rounded-lg border border-border bg-card shadow-sm"
+           title={getText('context', 'outputFormat', language)}
+         >
+            {OUTPUT_FORMATS.map(f => (
+              <button
+                key={f.value}
+                onClick={() => setOutputFormat(f.value)}
+                className={cn(
+                  "px-2.5 py-1 rounded-md text-xs font-medium transition-colors",
+                  outputFormat === f.value
+                    ? "bg-primary/10 text-primary"
+                    : "text-muted-foreground hover:bg-secondary/50"
+                )}
+              >
+                {f.label}
+              </button>
+            ))}
+         </div>
       </div>
 
       <div className="grid grid-cols-1 xl:grid-cols-2 gap-6">
@@ -225,6 +427,9 @@ export function TokenDashboard({
                  ))}
               </div>
               <p className="text-[10px] text-muted-foreground opacity-60">{getText('context', 'costNote', language)}</p>
+              {analytics.modelCosts.some(m => m.estimated) && (
+                <p className="text-[10px] text-muted-foreground opacity-60">≈ {getText('context', 'tokenEstimateNote', language)}</p>
+              )}
            </div>
         </div>
 
@@ -233,54 +438,74 @@ export function TokenDashboard({
            {/* 动态上下文窗口 */}
            <div className="bg-card border border-border rounded-xl p-5 shadow-sm space-y-4">
                <h3 className="text-sm font-semibold flex items-center gap-2"><TrendingUp size={16} /> {getText('context', 'contextUsage', language)}</h3>
-               <div className="space-y-3">
+               <div className="space-y-1">
                 {analytics.modelCosts.map(model => {
-                    const percent = Math.min(100, (stats.total_tokens / model.limit) * 100);
-                    const isOver = stats.total_tokens > model.limit;
+                    const percent = Math.min(100, (model.tokens / model.limit) * 100);
+                    const isOver = model.tokens > model.limit;
+                    const isTarget = model.modelId === targetModel?.id;
                     return (
-                        <div key={model.modelId} className="space-y-1.5">
+                        <button
+                          key={model.modelId}
+                          onClick={() => setTargetModelId(model.modelId)}
+                          className={cn(
+                            "w-full text-left space-y-1.5 p-1.5 -mx-1.5 rounded-md transition-colors",
+                            isTarget ? "bg-primary/5" : "hover:bg-secondary/50"
+                          )}
+                          title={getText('context', 'targetModelHint', language)}
+                        >
                             <div className="flex justify-between text-xs text-muted-foreground">
-                                <span>{model.modelName}</span>
+                                <span className={cn(isTarget && "text-primary font-medium")}>{model.modelName}</span>
                                 <span className={cn(isOver ? "text-destructive font-bold" : "")}>
+                                    <span className="font-mono opacity-70 mr-2" title={model.estimated ? getText('context', 'tokenEstimateNote', language) : undefined}>
+                                        {model.estimated ? '≈' : ''}{model.tokens.toLocaleString()}
+                                    </span>
                                     {percent.toFixed(1)}% <span className="opacity-50 text-[10px] ml-1">({(model.limit/1000).toFixed(0)}k)</span>
                                 </span>
                             </div>
                             <div className="h-1.5 bg-secondary rounded-full overflow-hidden">
                                 <div className={cn("h-full rounded-full transition-all duration-500", isOver ? "bg-destructive" : "bg-primary")} style={{ width: `${percent}%` }} />
                             </div>
-                        </div>
+                        </button>
                     )
                 })}
                </div>
            </div>
 
-           {/* Largest Files (Interactive) */}
+           {/* Token 消耗最多的文件 (Interactive) */}
            <div className="bg-card border border-border rounded-xl p-5 shadow-sm space-y-3">
               <div className="flex items-center justify-between mb-2">
                  <h3 className="text-sm font-semibold flex items-center gap-2"><AlertTriangle size={16} /> {getText('context', 'topFiles', language)}</h3>
-                 <span className="text-xs text-muted-foreground">{getText('context', 'largestFiles', language)}</span>
+                 <span className="text-xs text-muted-foreground">{getText('context', 'byTokens', language)}</span>
               </div>
               <div className="space-y-1">
-                 {analytics.topFiles.length === 0 && <span className="text-xs text-muted-foreground px-1">{getText('common', 'noFilesSelected', language)}</span>}
-                 {analytics.topFiles.map((f, i) => (
+                 {topFiles.length === 0 && <span className="text-xs text-muted-foreground px-1">{getText('common', 'noFilesSelected', language)}</span>}
+                 {topFiles.map((f, i) => (
                    <div
-                     key={f.id}
+                     key={f.path}
                      className="group/item relative flex items-center justify-between text-xs p-1.5 -mx-1.5 rounded-md hover:bg-secondary/50 transition-colors cursor-default"
                    >
                       <div className="flex items-center gap-2 truncate max-w-[70%]">
                          <span className="font-mono text-muted-foreground w-4 opacity-70">{i+1}.</span>
-                         <span className="truncate text-foreground font-medium" title={f.path}>{f.name}</span>
+                         <span className="truncate text-foreground font-medium" title={f.path}>{baseName(f.path)}</span>
+                         {f.status !== 'included' && (
+                           <span className="shrink-0 px-1.5 rounded bg-orange-500/10 text-orange-600 text-[10px]">
+                             {getText('context', FILE_STATUS_KEYS[f.status], language)}
+                           </span>
+                         )}
                       </div>
 
-                      <span className="font-mono text-muted-foreground transition-opacity duration-200 group-hover/item:opacity-0">
-                          {formatSize(f.size || 0)}
+                      <span
+                        className="font-mono text-muted-foreground transition-opacity duration-200 group-hover/item:opacity-0"
+                        title={`${f.lines.toLocaleString()} lines · ${formatSize(f.bytes)}`}
+                      >
+                          {f.tokens.toLocaleString()}
                       </span>
 
                       {/* Hover State: Show Remove Button */}
                       <button
                         onClick={(e) => {
                             e.stopPropagation();
-                            toggleSelect(f.id, false);
+                            toggleSelect(f.path, false);
                         }}
                         className="absolute right-1.5 opacity-0 group-hover/item:opacity-100 transition-all duration-200 p-1 hover:bg-destructive/10 hover:text-destructive text-muted-foreground rounded-sm scale-90 group-hover/item:scale-100"
                         title={getText('common', 'removeFromContext', language)}
@@ -291,6 +516,49 @@ export function TokenDashboard({
                  ))}
               </div>
            </div>
+
+           {/* 按目录 / 扩展名汇总 */}
+           <div className="bg-card border border-border rounded-xl p-5 shadow-sm space-y-3">
+              <div className="flex items-center justify-between mb-2">
+                 <h3 className="text-sm font-semibold flex items-center gap-2"><FolderTree size={16} /> {getText('context', 'tokenGroups', language)}</h3>
+                 <div className="flex items-center gap-1">
+                    {(['directory', 'extension'] as const).map(mode => (
+                      <button
+                        key={mode}
+                        onClick={() => setGroupMode(mode)}
+                        className={cn(
+                          "px-2 py-0.5 rounded-md text-xs transition-colors",
+                          groupMode === mode ? "bg-primary/10 text-primary" : "text-muted-foreground hover:bg-secondary/50"
+                        )}
+                      >
+                        {getText('context', mode === 'directory' ? 'byDirectory' : 'byExtension', language)}
+                      </button>
+                    ))}
+                 </div>
+              </div>
+              <div className="space-y-1.5">
+                 {groups.length === 0 && <span className="text-xs text-muted-foreground px-1">{getText('common', 'noFilesSelected', language)}</span>}
+                 {groups.map(g => {
+                    const percent = stats.total_tokens > 0 ? (g.tokens / stats.total_tokens) * 100 : 0;
+                    const label = groupMode === 'directory'
+                      ? (projectRoot && g.key.startsWith(projectRoot) ? g.key.slice(projectRoot.length).replace(/^[\\/]/, '') || '.' : g.key)
+                      : (g.key ? `.${g.key}` : getText('context', 'noExtension', language));
+                    return (
+                      <div key={g.key} className="space-y-1">
+                        <div className="flex items-center justify-between text-xs">
+                          <span className="truncate max-w-[65%] text-foreground" title={g.key}>{label}</span>
+                          <span className="font-mono text-muted-foreground">
+                            {g.tokens.toLocaleString()} <span className="opacity-50">({g.file_count})</span>
+                          </span>
+                        </div>
+                        <div className="h-1 w-full bg-secondary rounded-full overflow-hidden">
+                          <div className="h-full bg-primary/60" style={{ width: `${percent}%` }} />
+                        </div>
+                      </div>
+                    );
+                 })}
+              </div>
+           </div>
         </div>
       </div>
 
@@ -303,11 +571,31 @@ export function TokenDashboard({
          ) : (
            <div className="flex flex-wrap items-center gap-3 w-full justify-center">
              <button onClick={onCopy} disabled={isGenerating} className={cn("group relative inline-flex items-center justify-center gap-2 px-8 py-3 text-base font-semibold text-primary-foreground transition-all duration-200 bg-primary rounded-full shadow-lg shadow-primary/25 hover:bg-primary/90 hover:scale-105 active:scale-95 disabled:opacity-70 disabled:cursor-not-allowed disabled:scale-100 min-w-[200px] whitespace-nowrap", isGenerating && "cursor-wait")}>
-               {isGenerating ? (<><div className="w-4 h-4 border-2 border-white/30 border-t-white rounded-full animate-spin" /><span>{getText('context', 'processing', language)}</span></>) : (<><CheckCircle2 size={20} /><span>{getText('context', 'btnCopy', language)}</span></>)}
+               {isGenerating ? (<><div className="w-4 h-4 border-2 border-white/30 border-t-white rounded-full animate-spin" /><span>{getText('context', 'processing', language)}{exportProgress && exportProgress.total > 0 && ` ${Math.round((exportProgress.done / exportProgress.total) * 100)}%`}</span></>) : (<><CheckCircle2 size={20} /><span>{getText('context', 'btnCopy', language)}</span></>)}
              </button>
              <button onClick={onSave} disabled={isGenerating} className="inline-flex items-center justify-center gap-2 px-6 py-3 text-base font-medium text-foreground bg-secondary/80 border border-border rounded-full hover:bg-secondary hover:border-primary/30 transition-all active:scale-95 disabled:opacity-50 whitespace-nowrap">
                <Save size={20} /><span>{getText('context', 'btnSave', language)}</span>
              </button>
+             {onSplit && (
+               <button onClick={onSplit} disabled={isGenerating} className="inline-flex items-center justify-center gap-2 px-6 py-3 text-base font-medium text-foreground bg-secondary/80 border border-border rounded-full hover:bg-secondary hover:border-primary/30 transition-all active:scale-95 disabled:opacity-50 whitespace-nowrap">
+                 <Scissors size={20} /><span>{getText('context', 'btnSplit', language)}</span>
+               </button>
+             )}
+             {onGitChanges && projectRoot && (
+               <button onClick={onGitChanges} disabled={isGenerating} className="inline-flex items-center justify-center gap-2 px-6 py-3 text-base font-medium text-foreground bg-secondary/80 border border-border rounded-full hover:bg-secondary hover:border-primary/30 transition-all active:scale-95 disabled:opacity-50 whitespace-nowrap">
+                 <GitBranch size={20} /><span>{getText('context', 'btnGitChanges', language)}</span>
+               </button>
+             )}
+             {onSecretScan && projectRoot && (
+               <button onClick={onSecretScan} disabled={isGenerating} className="inline-flex items-center justify-center gap-2 px-6 py-3 text-base font-medium text-foreground bg-secondary/80 border border-border rounded-full hover:bg-secondary hover:border-primary/30 transition-all active:scale-95 disabled:opacity-50 whitespace-nowrap">
+                 <ShieldAlert size={20} /><span>{getText('context', 'btnSecretScan', language)}</span>
+               </button>
+             )}
+             {exportProgress && onCancel && (
+               <button onClick={onCancel} className="inline-flex items-center justify-center gap-2 px-5 py-3 text-base font-medium text-muted-foreground border border-border rounded-full hover:text-destructive hover:border-destructive/30 hover:bg-destructive/10 transition-all active:scale-95 whitespace-nowrap">
+                 <X size={18} /><span>{getText('context', 'btnCancelExport', language)}</span>
+               </button>
+             )}
            </div>
          )}
       </div>
@@ -315,7 +603,7 @@ export function TokenDashboard({
   );
 }
 
-function StatCard({ icon, label, value, rawValue, highlight, className, loading }: any) {
+function StatCard({ icon, label, value, rawValue, prefix, hint, highlight, className, loading }: any) {
     return (
       <div className={cn("bg-card border border-border rounded-xl p-4 flex flex-col items-center justify-center text-center gap-2 shadow-sm transition-all hover:shadow-md hover:border-primary/20", highlight && "bg-primary/5 border-primary/20 ring-1 ring-primary/10", className)}>
         <div className="p-2 bg-background rounded-full shadow-sm border border-border/50">{icon}</div>
@@ -325,7 +613,8 @@ function StatCard({ icon, label, value, rawValue, highlight, className, loading
              <div className={cn(
                  "text-xl md:text-2xl font-bold tracking-tight text-foreground truncate transition-opacity duration-300",
                  loading && "opacity-50"
-             )} title={String(value)}>
+             )} title={hint ? `${value} (${hint})` : String(value)}>
+                 {prefix && <span className="opacity-60 mr-0.5">{prefix}</span>}
                  {typeof rawValue === 'number' ? (
                     <NumberTicker value={rawValue} />
                  ) : (
@@ -1,6 +1,6 @@
 import { useEffect, useState } from "react";
-import { FileMeta } from "@/types/hyperview";
-import { readTextFile } from "@tauri-apps/plugin-fs";
+import { invoke } from "@tauri-apps/api/core";
+import { FileMeta, PreviewText } from "@/types/hyperview";
 import Editor from "@monaco-editor/react";
 import { Loader2 } from "lucide-react";
 
@@ -16,8 +16,8 @@ export function CodeRenderer({ meta }: { meta: FileMeta }) {
         if (meta.size > 1024 * 1024 * 5) { // > 5MB
              setContent("// File too large for simple preview.\n// Coming in Stage 2: Streaming Reader.");
         } else {
-             const text = await readTextFile(meta.path);
-             setContent(text);
+             const { content } = await invoke<PreviewText>("read_preview_text", { path: meta.path });
+             setContent(content);
         }
       } catch (e) {
         setContent(`Error reading file: ${e}`);
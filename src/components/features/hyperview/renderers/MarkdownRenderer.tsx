@@ -1,6 +1,6 @@
 import { useEffect, useState } from "react";
-import { FileMeta } from "@/types/hyperview";
-import { readTextFile } from "@tauri-apps/plugin-fs";
+import { invoke } from "@tauri-apps/api/core";
+import { FileMeta, PreviewText } from "@/types/hyperview";
 import ReactMarkdown from "react-markdown";
 import remarkGfm from "remark-gfm";
 import { Loader2 } from "lucide-react";
@@ -17,8 +17,8 @@ export function MarkdownRenderer({ meta }: { meta: FileMeta }) {
         if (meta.size > 1024 * 1024 * 2) { // > 2MB
              setContent("# File too large\n\nPreviewing large markdown files is disabled for performance.");
         } else {
-             const text = await readTextFile(meta.path);
-             setContent(text);
+             const { content } = await invoke<PreviewText>("read_preview_text", { path: meta.path });
+             setContent(content);
         }
       } catch (e) {
         setContent(`# Error\n\nCould not read file: ${e}`);
@@ -31,6 +31,8 @@ interface GitDiffFile {
   modified_content: string;
   is_binary: boolean; 
   is_large: boolean;  
+  original_encoding: string | null;
+  modified_encoding: string | null;
 }
 
 export function PatchView() {
@@ -3,24 +3,51 @@ import { invoke } from '@tauri-apps/api/core';
 import { writeText } from '@tauri-apps/plugin-clipboard-manager';
 import {
   Trash2, ShieldCheck, AlertCircle, RefreshCw,
-  Copy, Check
+  Copy, Check, Plus, X
 } from 'lucide-react';
 import { useAppStore } from '@/store/useAppStore';
 import { getText } from '@/lib/i18n';
 import { cn } from '@/lib/utils';
+import { SecretRulesStatus } from './SecretRulesStatus';
 
+// 非空的条件全部满足时放行
 interface IgnoredSecret {
   id: string;
-  value: string;
-  rule_id: string;
+  value: string | null;
+  value_regex: string | null;
+  rule_id: string | null;
+  path_glob: string | null;
+  project_root: string | null;
+  fingerprint: string | null;
+  expires_at: number | null;
+  // 加入白名单时报告该密钥的规则，只用于显示，不限定范围
+  detected_by: string | null;
   created_at: number;
 }
 
+type ScopeField = 'value' | 'value_regex' | 'rule_id' | 'path_glob' | 'project_root' | 'fingerprint';
+
+const SCOPE_FIELDS: { key: ScopeField; label: string; placeholder: string }[] = [
+  { key: 'value', label: 'value', placeholder: 'sk_live_...' },
+  { key: 'value_regex', label: 'valueRegex', placeholder: '^AKIA[0-9A-Z]{16}$' },
+  { key: 'rule_id', label: 'rule', placeholder: 'generic-api-key' },
+  { key: 'path_glob', label: 'pathGlob', placeholder: 'tests/**/*.py' },
+  { key: 'project_root', label: 'projectRoot', placeholder: '/path/to/project' },
+  { key: 'fingerprint', label: 'fingerprint', placeholder: 'src/config.ts:generic-api-key:12' },
+];
+
+const EMPTY_DRAFT: Record<ScopeField | 'expires', string> = {
+  value: '', value_regex: '', rule_id: '', path_glob: '', project_root: '', fingerprint: '', expires: '',
+};
+
 export function IgnoredSecretsManager() {
   const { language } = useAppStore();
   const [secrets, setSecrets] = useState<IgnoredSecret[]>([]);
   const [loading, setLoading] = useState(false);
   const [copiedId, setCopiedId] = useState<string | null>(null);
+  const [showForm, setShowForm] = useState(false);
+  const [draft, setDraft] = useState(EMPTY_DRAFT);
+  const [formError, setFormError] = useState<string | null>(null);
 
   const fetchSecrets = async () => {
     setLoading(true);
@@ -54,7 +81,32 @@ export function IgnoredSecretsManager() {
       setTimeout(() => setCopiedId(null), 2000);
   };
 
+  const handleAdd = async () => {
+    const entry: IgnoredSecret = {
+      id: '',
+      value: draft.value || null,
+      value_regex: draft.value_regex.trim() || null,
+      rule_id: draft.rule_id.trim() || null,
+      path_glob: draft.path_glob.trim() || null,
+      project_root: draft.project_root.trim() || null,
+      fingerprint: draft.fingerprint.trim() || null,
+      // 当天结束时过期
+      expires_at: draft.expires ? new Date(`${draft.expires}T23:59:59`).getTime() : null,
+      created_at: 0,
+    };
+    try {
+      await invoke('add_ignored_secrets', { secrets: [entry] });
+      setDraft(EMPTY_DRAFT);
+      setFormError(null);
+      setShowForm(false);
+      fetchSecrets();
+    } catch (e) {
+      setFormError(String(e));
+    }
+  };
+
   const formatDate = (ts: number) => new Date(ts).toLocaleDateString();
+  const now = Date.now();
 
   return (
     <div className="flex flex-col h-full animate-in fade-in slide-in-from-right-4 duration-200">
@@ -69,16 +121,68 @@ export function IgnoredSecretsManager() {
                 {getText('settings', 'securityDesc', language)}
             </p>
          </div>
-         <button
-           onClick={fetchSecrets}
-           disabled={loading}
-           className="p-2 hover:bg-secondary rounded-full transition-colors"
-           title={getText('library', 'refresh', language)}
-         >
-            <RefreshCw size={16} className={cn(loading && "animate-spin")} />
-         </button>
+         <div className="flex items-center gap-1">
+           <button
+             onClick={() => { setShowForm(v => !v); setFormError(null); }}
+             className={cn("p-2 hover:bg-secondary rounded-full transition-colors", showForm && "bg-secondary")}
+             title={getText('settings', 'addAllowlistEntry', language)}
+           >
+              {showForm ? <X size={16} /> : <Plus size={16} />}
+           </button>
+           <button
+             onClick={fetchSecrets}
+             disabled={loading}
+             className="p-2 hover:bg-secondary rounded-full transition-colors"
+             title={getText('library', 'refresh', language)}
+           >
+              <RefreshCw size={16} className={cn(loading && "animate-spin")} />
+           </button>
+         </div>
       </div>
 
+      <SecretRulesStatus />
+
+      {showForm && (
+        <div className="mb-4 p-3 rounded-lg bg-secondary/10 border border-border space-y-3 shrink-0">
+            <p className="text-[11px] text-muted-foreground">{getText('settings', 'allowlistEntryDesc', language)}</p>
+            <div className="grid grid-cols-2 gap-2">
+                {SCOPE_FIELDS.map(field => (
+                    <label key={field.key} className="space-y-1">
+                        <span className="text-[10px] font-bold text-muted-foreground uppercase tracking-wider">
+                            {getText('settings', field.label, language)}
+                        </span>
+                        <input
+                            value={draft[field.key]}
+                            onChange={(e) => setDraft(d => ({ ...d, [field.key]: e.target.value }))}
+                            placeholder={field.placeholder}
+                            className="w-full bg-secondary/30 border border-border rounded-lg px-3 py-1.5 text-xs outline-none focus:ring-2 focus:ring-primary/20 focus:border-primary/50 transition-all placeholder:text-muted-foreground/30 font-mono"
+                        />
+                    </label>
+                ))}
+                <label className="space-y-1">
+                    <span className="text-[10px] font-bold text-muted-foreground uppercase tracking-wider">
+                        {getText('settings', 'expiresAt', language)}
+                    </span>
+                    <input
+                        type="date"
+                        value={draft.expires}
+                        onChange={(e) => setDraft(d => ({ ...d, expires: e.target.value }))}
+                        className="w-full bg-secondary/30 border border-border rounded-lg px-3 py-1.5 text-xs outline-none focus:ring-2 focus:ring-primary/20 focus:border-primary/50 transition-all"
+                    />
+                </label>
+            </div>
+            <div className="flex items-center justify-between gap-2">
+                <span className="text-[11px] text-destructive truncate">{formError}</span>
+                <button
+                    onClick={handleAdd}
+                    className="px-3 py-1.5 text-xs rounded-md bg-primary text-primary-foreground hover:bg-primary/90 transition-colors shrink-0"
+                >
+                    {getText('settings', 'addAllowlistEntry', language)}
+                </button>
+            </div>
+        </div>
+      )}
+
       <div className="flex-1 bg-secondary/5 border border-border rounded-lg overflow-hidden flex flex-col min-h-0">
           <div className="grid grid-cols-12 gap-4 px-4 py-2 bg-secondary/20 border-b border-border text-xs font-bold text-muted-foreground uppercase tracking-wider shrink-0">
              {/* 调整列宽：内容占9列，时间占3列 */}
@@ -94,17 +198,48 @@ export function IgnoredSecretsManager() {
                  </div>
              )}
 
-             {secrets.map(item => (
-                <div key={item.id} className="grid grid-cols-12 gap-4 px-3 py-2.5 items-start hover:bg-secondary/40 rounded-md transition-colors text-xs group border-b border-border/30 last:border-0">
+             {secrets.map(item => {
+                const expired = item.expires_at !== null && item.expires_at <= now;
+                const scopes = [
+                    item.rule_id && `${getText('settings', 'rule', language)}: ${item.rule_id}`,
+                    item.path_glob && `${getText('settings', 'pathGlob', language)}: ${item.path_glob}`,
+                    item.project_root && `${getText('settings', 'projectRoot', language)}: ${item.project_root}`,
+                    item.fingerprint && `${getText('settings', 'fingerprint', language)}: ${item.fingerprint}`,
+                ].filter(Boolean) as string[];
+                // 没有精确值时显示正则，两者都没有表示匹配任意值
+                const display = item.value ?? (item.value_regex ? `/${item.value_regex}/` : getText('settings', 'anyValue', language));
+                return (
+                <div key={item.id} className={cn("grid grid-cols-12 gap-4 px-3 py-2.5 items-start hover:bg-secondary/40 rounded-md transition-colors text-xs group border-b border-border/30 last:border-0", expired && "opacity-50")}>
 
                     {/* 值显示区域：占9列 */}
                     <div className="col-span-9 flex gap-2 min-w-0">
-                        <div className="font-mono text-foreground break-all select-text leading-relaxed">
-                            {item.value}
+                        <div className="min-w-0 space-y-1">
+                            <div className={cn("font-mono break-all select-text leading-relaxed", item.value || item.value_regex ? "text-foreground" : "text-muted-foreground italic")}>
+                                {display}
+                            </div>
+                            {(scopes.length > 0 || item.detected_by || item.expires_at !== null) && (
+                                <div className="flex flex-wrap gap-1">
+                                    {scopes.map(scope => (
+                                        <span key={scope} className="px-1.5 py-0.5 rounded bg-secondary/60 text-[10px] text-muted-foreground font-mono break-all">
+                                            {scope}
+                                        </span>
+                                    ))}
+                                    {item.detected_by && (
+                                        <span className="px-1.5 py-0.5 text-[10px] text-muted-foreground">
+                                            {getText('settings', 'detectedBy', language, { rule: item.detected_by })}
+                                        </span>
+                                    )}
+                                    {item.expires_at !== null && (
+                                        <span className={cn("px-1.5 py-0.5 rounded text-[10px]", expired ? "bg-destructive/10 text-destructive" : "bg-yellow-500/10 text-yellow-600")}>
+                                            {getText('settings', expired ? 'expired' : 'expiresOn', language, { date: formatDate(item.expires_at) })}
+                                        </span>
+                                    )}
+                                </div>
+                            )}
                         </div>
                         {/* 复制按钮 */}
                         <button
-                            onClick={() => handleCopy(item.id, item.value)}
+                            onClick={() => handleCopy(item.id, item.value ?? item.value_regex ?? item.fingerprint ?? '')}
                             className={cn(
                                 "h-5 w-5 flex items-center justify-center rounded transition-all shrink-0 mt-0.5",
                                 copiedId === item.id ? "text-green-500 bg-green-500/10" : "text-muted-foreground hover:text-foreground hover:bg-secondary opacity-0 group-hover:opacity-100"
@@ -127,7 +262,8 @@ export function IgnoredSecretsManager() {
                         </button>
                     </div>
                 </div>
-             ))}
+                );
+             })}
           </div>
       </div>
 
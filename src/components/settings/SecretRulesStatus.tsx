@@ -0,0 +1,91 @@
+import { useEffect, useState } from 'react';
+import { invoke } from '@tauri-apps/api/core';
+import { FileCode2, RefreshCw, AlertTriangle } from 'lucide-react';
+import { useAppStore } from '@/store/useAppStore';
+import { getText } from '@/lib/i18n';
+import { cn } from '@/lib/utils';
+
+// 与 Rust 端 gitleaks::RuleSetStatus 对应
+interface RuleError {
+  source: string;
+  rule_id: string | null;
+  message: string;
+}
+
+interface RuleSetStatus {
+  rule_count: number;
+  sources: string[];
+  errors: RuleError[];
+  // 被配置文件的 disabledRules 等移除的下层规则
+  dropped_rules: string[];
+}
+
+// 应用数据目录下 gitleaks.toml 的加载状态，修改文件后点击重新加载
+export function SecretRulesStatus() {
+  const { language } = useAppStore();
+  const [status, setStatus] = useState<RuleSetStatus | null>(null);
+  const [loading, setLoading] = useState(false);
+
+  const reload = async () => {
+    setLoading(true);
+    try {
+      setStatus(await invoke<RuleSetStatus>('reload_secret_rules'));
+    } catch (e) {
+      console.error(e);
+    } finally {
+      setLoading(false);
+    }
+  };
+
+  useEffect(() => {
+    invoke<RuleSetStatus>('get_secret_rules_status').then(setStatus).catch(console.error);
+  }, []);
+
+  if (!status) return null;
+
+  return (
+    <div className="mb-4 p-3 border border-border rounded-lg bg-secondary/5 text-xs shrink-0">
+      <div className="flex items-center justify-between gap-2">
+        <div className="flex items-center gap-2 min-w-0">
+          <FileCode2 size={14} className="text-muted-foreground shrink-0" />
+          <span className="font-medium text-foreground">{getText('settings', 'secretRules', language)}</span>
+          <span className="text-muted-foreground truncate">
+            {getText('settings', 'secretRulesCount', language, { count: String(status.rule_count) })}
+            {' · '}
+            {status.sources.length > 0 ? status.sources.join(', ') : getText('settings', 'secretRulesBuiltin', language)}
+          </span>
+        </div>
+        <button
+          onClick={reload}
+          disabled={loading}
+          className="p-1.5 hover:bg-secondary rounded-full transition-colors shrink-0"
+          title={getText('settings', 'secretRulesReload', language)}
+        >
+          <RefreshCw size={14} className={cn(loading && "animate-spin")} />
+        </button>
+      </div>
+      <p className="mt-1 text-muted-foreground">{getText('settings', 'secretRulesDesc', language)}</p>
+      {status.dropped_rules.length > 0 && (
+        <p className="mt-2 flex gap-1.5 items-start text-yellow-600 dark:text-yellow-500">
+          <AlertTriangle size={12} className="shrink-0 mt-0.5" />
+          <span className="break-all">
+            {getText('settings', 'secretRulesDropped', language, { count: String(status.dropped_rules.length) })}
+            {' '}{status.dropped_rules.join(', ')}
+          </span>
+        </p>
+      )}
+      {status.errors.length > 0 && (
+        <ul className="mt-2 space-y-1 text-destructive">
+          {status.errors.map((err, i) => (
+            <li key={i} className="flex gap-1.5 items-start">
+              <AlertTriangle size={12} className="shrink-0 mt-0.5" />
+              <span className="break-all">
+                {err.rule_id ? `[${err.rule_id}] ` : ''}{err.message}
+              </span>
+            </li>
+          ))}
+        </ul>
+      )}
+    </div>
+  );
+}
@@ -1,5 +1,5 @@
 import { FileNode } from '@/types/context';
-import { AIModelConfig } from '@/types/model'; 
+import { AIModelConfig, isEstimatedCount } from '@/types/model';
 
 export interface LanguageStat {
   name: string;
@@ -14,12 +14,15 @@ export interface ModelCostStat {
   modelId: string;
   modelName: string;
   limit: number;
+  // 按该模型的分词器统计的 Token 数
+  tokens: number;
+  // 不是用模型自己的分词器统计的 Token 数是估算值
+  estimated: boolean;
   cost: number;
 }
 
 export interface AnalyticsData {
   languages: LanguageStat[];
-  topFiles: FileNode[];
   modelCosts: ModelCostStat[]; // 动态数组
 }
 
@@ -62,8 +65,8 @@ function getFlatSelectedFiles(nodes: FileNode[]): FileNode[] {
  * 动态分析函数
  */
 export function analyzeContext(
-  nodes: FileNode[], 
-  totalTokens: number, 
+  nodes: FileNode[],
+  tokensFor: (model: AIModelConfig) => number, // 每个模型按自己的分词器计数
   models: AIModelConfig[] // 必传参数
 ): AnalyticsData {
   const files = getFlatSelectedFiles(nodes);
@@ -93,22 +96,21 @@ export function analyzeContext(
     .sort((a, b) => b.size - a.size)
     .slice(0, 5);
 
-  const topFiles = [...files]
-    .sort((a, b) => (b.size || 0) - (a.size || 0))
-    .slice(0, 5);
-
   // 动态成本计算
-  const millions = totalTokens / 1_000_000;
-  const modelCosts: ModelCostStat[] = models.map(model => ({
-    modelId: model.id,
-    modelName: model.name,
-    limit: model.contextLimit,
-    cost: millions * model.inputPricePerMillion
-  }));
+  const modelCosts: ModelCostStat[] = models.map(model => {
+    const tokens = tokensFor(model);
+    return {
+      modelId: model.id,
+      modelName: model.name,
+      limit: model.contextLimit,
+      tokens,
+      estimated: isEstimatedCount(model),
+      cost: (tokens / 1_000_000) * model.inputPricePerMillion
+    };
+  });
   
   return {
     languages,
-    topFiles,
     modelCosts
   };
 }
\ No newline at end of file
@@ -1,4 +1,6 @@
-import { FileNode } from '@/types/context';
+import { invoke } from '@tauri-apps/api/core';
+import { ContextHeader, FileNode, IgnoreConfig, REPO_MAP_TOKENS } from '@/types/context';
+import { TokenizerKind } from '@/types/model';
 import { generateAsciiTree } from './tree_generator';
 
 export function getSelectedPaths(nodes: FileNode[]): string[] {
@@ -14,28 +16,103 @@ export function getSelectedPaths(nodes: FileNode[]): string[] {
   return paths;
 }
 
-export function generateHeader(nodes: FileNode[], removeComments: boolean): string {
+// 标签、标题等格式相关的部分由 Rust 端按输出格式渲染，这里只提供内容
+export function generateHeader(nodes: FileNode[], removeComments: boolean): ContextHeader {
   const selectedPaths = getSelectedPaths(nodes);
-  const treeString = generateAsciiTree(nodes);
 
   const parts: string[] = [];
-  parts.push(`<project_context>`);
   parts.push(`This is a source code context provided by Code Forge AI.`);
   parts.push(`Total Files: ${selectedPaths.length}`);
   if (removeComments) {
       parts.push(`Note: Comments have been stripped to save tokens.`);
   }
-  parts.push(``);
-  parts.push(`<project_structure>`);
-  parts.push(treeString);
-  parts.push(`</project_structure>`);
-  parts.push(``);
 
-  return parts.join('\n');
+  return {
+    summary: parts.join('\n'),
+    structure: generateAsciiTree(nodes)
+  };
+}
+
+// 全局过滤规则和项目过滤规则的并集，扫描目录树和生成符号图时使用
+export function mergeIgnoreConfig(global: IgnoreConfig, project: IgnoreConfig): IgnoreConfig {
+  return {
+    dirs: Array.from(new Set([...global.dirs, ...project.dirs])),
+    files: Array.from(new Set([...global.files, ...project.files])),
+    extensions: Array.from(new Set([...global.extensions, ...project.extensions])),
+  };
+}
+
+// 与 Rust 端 context::repo_map::RepoMap 对应
+export interface RepoMap {
+  content: string;
+  tokens: number;
+  files: number;
+  total_symbols: number;
+}
+
+// 在 header 中附带整个仓库的符号图；生成失败时只记录错误，不影响上下文本身
+export async function generateHeaderWithRepoMap(
+  nodes: FileNode[],
+  removeComments: boolean,
+  repoMapRoot: string | null,
+  config: IgnoreConfig
+): Promise<ContextHeader> {
+  const header = generateHeader(nodes, removeComments);
+  if (!repoMapRoot) return header;
+  try {
+    const map = await invoke<RepoMap>('get_repo_map', {
+      root: repoMapRoot,
+      config,
+      options: { max_tokens: REPO_MAP_TOKENS }
+    });
+    header.repo_map = map.content;
+  } catch (err) {
+    console.error("Repo map generation failed:", err);
+  }
+  return header;
+}
+
+export type FileStatus = 'included' | 'binary' | 'too_large' | 'error';
+
+// 单个文件的统计，bytes / tokens 按渲染后的文件块计算
+export interface FileStats {
+  path: string;
+  language: string | null;
+  status: FileStatus;
+  encoding: string | null;
+  bytes: number;
+  lines: number;
+  tokens: number;
+}
+
+// 按目录或扩展名汇总，扩展名为空表示没有扩展名
+export interface StatsGroup {
+  key: string;
+  file_count: number;
+  bytes: number;
+  tokens: number;
 }
 
 export interface ContextStats {
   file_count: number;
   total_size: number;
   total_tokens: number;
+  tokenizer?: TokenizerKind;
+  exact?: boolean;
+  files?: FileStats[];
+  by_directory?: StatsGroup[];
+  by_extension?: StatsGroup[];
+}
+
+// 与 Rust 端 context::redact::RedactionReport 对应，不包含密钥本身
+export interface Redaction {
+  placeholder: string;
+  rule_id: string;
+  line_number: number;
+}
+
+export interface RedactionReport {
+  total: number;
+  unique_secrets: number;
+  files: { path: string; redactions: Redaction[] }[];
 }
@@ -1,96 +1,36 @@
-import { readDir } from '@tauri-apps/plugin-fs';
 import { invoke } from '@tauri-apps/api/core';
-import { join } from '@tauri-apps/api/path';
 import { FileNode, IgnoreConfig } from '@/types/context';
 
-// 并发控制队列
-class TaskQueue {
-  private running = 0;
-  private queue: (() => void)[] = [];
-  constructor(private concurrency: number) {}
-
-  async run<T>(task: () => Promise<T>): Promise<T> {
-    if (this.running >= this.concurrency) {
-      await new Promise<void>(resolve => this.queue.push(resolve));
-    }
-    this.running++;
-    try {
-      return await task();
-    } finally {
-      this.running--;
-      const next = this.queue.shift();
-      if (next) next();
-    }
-  }
+// Rust 端 walker 返回的节点（遵守 .gitignore / .ignore / 全局 excludes）
+interface WalkNode {
+  name: string;
+  path: string;
+  kind: 'file' | 'dir';
+  size: number;
+  isBinary: boolean;
+  children?: WalkNode[];
 }
 
-const scanQueue = new TaskQueue(50); // 限制最大 50 个并发 FS 操作
+function toFileNode(node: WalkNode): FileNode {
+  return {
+    id: node.path,
+    name: node.name,
+    path: node.path,
+    kind: node.kind,
+    size: node.size,
+    isBinary: node.isBinary,
+    children: node.children?.map(toFileNode),
+    isSelected: true,
+    isExpanded: false
+  };
+}
 
-export async function scanProject(
-  path: string,
-  config: IgnoreConfig,
-  visitedPaths = new Set<string>()
-): Promise<FileNode[]> {
+export async function scanProject(path: string, config: IgnoreConfig): Promise<FileNode[]> {
   try {
-    if (visitedPaths.has(path)) return [];
-    visitedPaths.add(path);
-
-    const entries = await scanQueue.run(() => readDir(path));
-
-    const nodes: (FileNode | null)[] = [];
-
-    const validEntries = entries.filter(entry => {
-        const name = entry.name;
-        if (config.dirs.includes(name)) return false;
-        if (config.files.includes(name)) return false;
-        const ext = name.split('.').pop()?.toLowerCase();
-        if (ext && config.extensions.includes(ext) && !entry.isDirectory) return false;
-        return true;
-    });
-
-    for (const entry of validEntries) {
-        const fullPath = await join(path, entry.name);
-        const isDir = entry.isDirectory;
-        
-        if (entry.isSymlink) continue; 
-
-        let children: FileNode[] | undefined = undefined;
-        let size = 0;
-
-        if (isDir) {
-            try {
-                children = await scanProject(fullPath, config, visitedPaths);
-            } catch (e) {
-                console.warn(`Failed to scan dir: ${fullPath}`, e);
-            }
-        } else {
-            try {
-                size = await scanQueue.run(() => invoke('get_file_size', { path: fullPath }));
-            } catch (err) {
-            }
-        }
-
-        nodes.push({
-            id: fullPath,
-            name: entry.name,
-            path: fullPath,
-            kind: isDir ? 'dir' : 'file',
-            size: size,
-            children: isDir ? children : undefined,
-            isSelected: true,
-            isExpanded: false
-        });
-    }
-
-    return nodes
-        .filter((n): n is FileNode => n !== null)
-        .sort((a, b) => {
-            if (a.kind === b.kind) return a.name.localeCompare(b.name);
-            return a.kind === 'dir' ? -1 : 1;
-        });
-
+    const tree = await invoke<WalkNode[]>('scan_project_tree', { root: path, config });
+    return tree.map(toFileNode);
   } catch (err) {
     console.error(`Error scanning ${path}:`, err);
     throw err;
   }
-}
\ No newline at end of file
+}
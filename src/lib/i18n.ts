@@ -64,15 +64,84 @@ const translations = {
       estCost: "Est. API Cost (Input)",
       costNote: "Calculated based on current token count and synced pricing.",
       contextUsage: "Context Usage",
+      targetModelHint: "Count tokens, show file details and split parts with this model's tokenizer",
+      tokenEstimateNote: "Counts marked ≈ are estimates, not the model's own tokenizer: Claude and Gemini scale cl100k tokens by a fixed ratio, other models without a published tokenizer use cl100k as is.",
       topFiles: "Top Token Hogs",
-      largestFiles: "Largest Files",
+      byTokens: "By Tokens",
+      tokenGroups: "Token Distribution",
+      byDirectory: "Directory",
+      byExtension: "Extension",
+      noExtension: "(no extension)",
+      statusIncluded: "Included",
+      statusBinary: "Binary",
+      statusTooLarge: "Too Large",
+      statusError: "Error",
       tipSelect: "Select files from the left tree",
       btnCopy: "Copy to Clipboard",
       btnSave: "Save to File...",
       processing: "Processing...",
+      btnCancelExport: "Cancel",
+      btnSplit: "Split...",
+      splitTitle: "Split Context",
+      splitDesc: "Split the context into parts under a token limit. Paste them one at a time; each part tells the model to wait for the rest.",
+      splitMaxTokens: "Max tokens per part",
+      splitAction: "Split",
+      splitEmpty: "Set a token limit and click Split",
+      splitPartLabel: "Part {index} of {total}",
+      splitHeaderOnly: "Project overview only",
+      splitSaveAll: "Save as Numbered Files...",
+      btnGitChanges: "Git Changes...",
+      gitTitle: "Changed Files as Context",
+      gitDesc: "Collect the files changed since a commit, branch or the fork point from main, including uncommitted and untracked files.",
+      gitBaseRefPlaceholder: "Base ref (default: main / master)",
+      gitAction: "Collect",
+      gitMergeBase: "Use merge-base",
+      gitIncludeDiff: "Include diff hunks",
+      gitSkipDeleted: "Skip deleted files",
+      gitEmpty: "Enter a base ref and click Collect",
+      gitNoChanges: "No changes since the base ref",
+      gitBaseCommit: "Since {commit}",
+      btnSecretScan: "Scan for Secrets...",
+      secretScanTitle: "Scan Repository for Secrets",
+      secretScanDesc: "Scan the lines added by every commit for leaked secrets. Each secret is reported once, at the commit that introduced it.",
+      secretScanRangePlaceholder: "Range (e.g. main..HEAD; default: all refs)",
+      secretScanAction: "Scan",
+      secretScanTreeDesc: "Scan every file in the project that is not ignored by .gitignore or the filters. Binary and large files are skipped.",
+      secretScanModeTree: "Working Tree",
+      secretScanModeHistory: "History",
+      secretScanTreeSummary: "{files} files scanned, {skipped} skipped",
+      secretScanUnreadable: ", {count} could not be read",
+      secretScanEmpty: "Click Scan to start",
+      secretScanClean: "No secrets found",
+      secretScanOccurrences: "in {count} commits",
+      secretHook: "Pre-commit guard",
+      secretHookDesc: "Scans staged changes before every commit and blocks it when secrets are found",
+      secretHookInstalled: "Installed",
+      secretHookForeign: "Another pre-commit hook already exists",
+      secretHookInstall: "Install",
+      secretHookUninstall: "Uninstall",
+      secretReportExport: "Export report",
+      secretReportSaved: "Exported {count} findings",
+      secretSortRisk: "By risk",
+      secretSortRiskTooltip: "Sort by severity, then confidence",
+      secretHideLow: "Hide low confidence",
+      secretHideLowTooltip: "Hide findings with a confidence score below {score}",
+      secretDecodedTooltip: "Found after decoding ({chain}); the whole encoded string is highlighted and redacted",
+      toastExportCancelled: "Export cancelled",
       removeComments: "Remove Comments",
+      outputFormat: "Output Format",
+      autoSkeleton: "Auto Skeleton",
+      autoSkeletonTooltip: "Use skeleton mode for files larger than 100 KB (signatures only, function bodies omitted)",
+      skeletonToggle: "Skeleton mode (signatures only)",
+      notebookOutputs: "Notebook Outputs",
+      notebookOutputsTooltip: "Include text outputs of Jupyter notebook cells (truncated; images and other binary outputs are always dropped)",
+      repoMap: "Repo Map",
+      repoMapTooltip: "Prepend a map of the whole repository's top-level symbols, ranked by how often they are referenced",
       securityFilter: "Security Filter",
       securityFilterTooltip: "Scan for secrets before copying",
+      redactSecrets: "Auto Redact",
+      redactSecretsTooltip: "Replace detected secrets with placeholders such as [REDACTED:rule#1]",
+      toastRedacted: "{count} secret(s) redacted",
       selectAll: "Select All",
       deselectAll: "None",
       invertSel: "Invert",
@@ -264,12 +333,29 @@ const translations = {
       importProjectConfigMsg: "Do you want to overwrite existing project configs with the same path?",
       navSecurity: "Security & Whitelist",
       securityTitle: "Ignored Secrets (Whitelist)",
-      securityDesc: "These strings will be ignored in future security scans.",
+      securityDesc: "Matching findings will be ignored in future security scans. A repository's .gitleaksignore is honored as well.",
       value: "Value",
       rule: "Rule ID",
+      detectedBy: "detected by {rule}",
       addedAt: "Added At",
+      valueRegex: "Value Regex",
+      pathGlob: "Path Glob",
+      projectRoot: "Project",
+      fingerprint: "Fingerprint",
+      expiresAt: "Expires",
+      expiresOn: "Expires {date}",
+      expired: "Expired {date}",
+      anyValue: "Any value",
+      addAllowlistEntry: "Add Entry",
+      allowlistEntryDesc: "An entry applies when all filled-in fields match. Path globs are relative to the project root (a glob without / matches file names); fingerprints use the gitleaks file:rule:line format.",
       noIgnored: "No ignored secrets yet.",
       confirmDeleteSecret: "Remove from whitelist? It will be detected again in future scans.",
+      secretRules: "Detection Rules",
+      secretRulesCount: "{count} rules",
+      secretRulesBuiltin: "built-in",
+      secretRulesDesc: "Place a gitleaks-compatible gitleaks.toml in the app data directory to add or override rules; a repository's .gitleaks.toml adds to these rules when scanning that project and cannot replace them.",
+      secretRulesReload: "Reload rules",
+      secretRulesDropped: "{count} rules disabled by config files:",
       navSearch: "Search",
       searchTitle: "Search Engine Settings",
       searchDesc: "Configure the engines for Spotlight Web Search (triggered by ?).",
@@ -600,13 +686,79 @@ const translations = {
       estCost: "预估 API 成本 (输入)",
       costNote: "基于当前 Token 数量和云端价格计算",
       contextUsage: "上下文窗口占用",
+      targetModelHint: "按此模型的分词器统计 Token、显示文件明细和拆分上下文",
+      tokenEstimateNote: "标有 ≈ 的 Token 数为估算值，并非模型自身的分词器：Claude 和 Gemini 由 cl100k 结果乘以固定系数得出，其他没有公开分词器的模型直接使用 cl100k",
       topFiles: "Token 消耗大户",
-      largestFiles: "最大文件 Top 5",
+      byTokens: "按 Token 数",
+      tokenGroups: "Token 分布",
+      byDirectory: "目录",
+      byExtension: "扩展名",
+      noExtension: "（无扩展名）",
+      statusIncluded: "已包含",
+      statusBinary: "二进制",
+      statusTooLarge: "过大",
+      statusError: "出错",
       tipSelect: "请从左侧文件树选择文件",
       btnCopy: "复制上下文",
       btnSave: "保存为文件...",
       processing: "处理中...",
+      btnCancelExport: "取消",
+      btnSplit: "分段...",
+      splitTitle: "分段输出",
+      splitDesc: "按 token 上限把上下文拆成多段，逐段粘贴；每段都会提示模型等待剩余部分。",
+      splitMaxTokens: "每段最多 token 数",
+      splitAction: "拆分",
+      splitEmpty: "设置 token 上限后点击拆分",
+      splitPartLabel: "第 {index} 段，共 {total} 段",
+      splitHeaderOnly: "仅项目概览",
+      splitSaveAll: "保存为编号文件...",
+      btnGitChanges: "Git 变更...",
+      gitTitle: "以变更文件作为上下文",
+      gitDesc: "收集自某个提交、分支或与 main 的分叉点以来改动过的文件，包括未提交和未跟踪的文件。",
+      gitBaseRefPlaceholder: "基准引用（默认 main / master）",
+      gitAction: "收集",
+      gitMergeBase: "使用分叉点 (merge-base)",
+      gitIncludeDiff: "附带 diff 片段",
+      gitSkipDeleted: "跳过已删除的文件",
+      gitEmpty: "输入基准引用后点击收集",
+      gitNoChanges: "自基准引用以来没有变更",
+      gitBaseCommit: "基于 {commit}",
+      btnSecretScan: "扫描密钥...",
+      secretScanTitle: "扫描仓库中的密钥",
+      secretScanDesc: "扫描每个提交新增的行，查找泄露的密钥。同一个密钥只在引入它的提交中报告一次。",
+      secretScanRangePlaceholder: "范围（如 main..HEAD；默认扫描所有引用）",
+      secretScanAction: "扫描",
+      secretScanTreeDesc: "扫描项目中未被 .gitignore 和过滤规则排除的所有文件，跳过二进制和过大的文件。",
+      secretScanModeTree: "工作区",
+      secretScanModeHistory: "提交历史",
+      secretScanTreeSummary: "已扫描 {files} 个文件，跳过 {skipped} 个",
+      secretScanUnreadable: "，{count} 个无法读取",
+      secretScanEmpty: "点击扫描开始",
+      secretScanClean: "未发现密钥",
+      secretScanOccurrences: "出现在 {count} 个提交中",
+      secretHook: "提交前检查",
+      secretHookDesc: "每次提交前扫描暂存区改动，发现密钥时阻止提交",
+      secretHookInstalled: "已安装",
+      secretHookForeign: "仓库中已存在其他 pre-commit 钩子",
+      secretHookInstall: "安装",
+      secretHookUninstall: "卸载",
+      secretReportExport: "导出报告",
+      secretReportSaved: "已导出 {count} 条结果",
+      secretSortRisk: "按风险",
+      secretSortRiskTooltip: "按严重程度、再按置信度排序",
+      secretHideLow: "隐藏低置信度",
+      secretHideLowTooltip: "隐藏置信度低于 {score} 的结果",
+      secretDecodedTooltip: "解码（{chain}）后发现，高亮和脱敏针对整个编码字符串",
+      toastExportCancelled: "已取消导出",
       removeComments: "移除注释",
+      outputFormat: "输出格式",
+      autoSkeleton: "自动骨架",
+      autoSkeletonTooltip: "超过 100 KB 的文件只保留签名，省略函数体",
+      skeletonToggle: "骨架模式（只保留签名）",
+      notebookOutputs: "Notebook 输出",
+      notebookOutputsTooltip: "附带 Jupyter notebook 单元格的文本输出（会截断；图片等二进制输出始终丢弃）",
+      repoMap: "仓库符号图",
+      repoMapTooltip: "在开头附带整个仓库的顶层符号概览，按被引用次数排序",
       securityAlert: "安全警告",
       issuesFound: "发现 {count} 个问题",
       securityMsg: "检测到潜在的敏感信息，请审查并取消勾选误报项（即您希望保留原样的项目）",
@@ -621,6 +773,9 @@ const translations = {
       ignoreForever: "永久忽略未选中的项目 (加入白名单)",
       securityFilter: "安全检测",
       securityFilterTooltip: "复制前检测敏感信息 (API Key 等)",
+      redactSecrets: "自动脱敏",
+      redactSecretsTooltip: "把检测到的密钥替换为 [REDACTED:rule#1] 之类的占位符",
+      toastRedacted: "已脱敏 {count} 处密钥",
       selectAll: "全选",
       deselectAll: "全不选",
       invertSel: "反选",
@@ -800,12 +955,29 @@ const translations = {
       importProjectConfigMsg: "是否覆盖路径相同的现有项目配置？",
       navSecurity: "安全与白名单",
       securityTitle: "已忽略的敏感词 (白名单)",
-      securityDesc: "以下字符串将在未来的安全扫描中被自动忽略。",
+      securityDesc: "匹配以下条目的结果将在未来的安全扫描中被自动忽略，同时会读取仓库中的 .gitleaksignore。",
       value: "内容",
       rule: "规则 ID",
+      detectedBy: "由 {rule} 发现",
       addedAt: "添加时间",
+      valueRegex: "内容正则",
+      pathGlob: "路径 Glob",
+      projectRoot: "项目",
+      fingerprint: "指纹",
+      expiresAt: "过期时间",
+      expiresOn: "{date} 过期",
+      expired: "已于 {date} 过期",
+      anyValue: "任意内容",
+      addAllowlistEntry: "添加条目",
+      allowlistEntryDesc: "条目中填写的条件全部满足时生效。路径 Glob 相对于项目根目录（不含 / 时匹配文件名）；指纹格式与 gitleaks 相同：file:rule:line。",
       noIgnored: "暂无忽略记录。",
       confirmDeleteSecret: "确定从白名单移除？移除后下次扫描将再次报警。",
+      secretRules: "检测规则",
+      secretRulesCount: "{count} 条规则",
+      secretRulesBuiltin: "内置",
+      secretRulesDesc: "在应用数据目录放置与 gitleaks 兼容的 gitleaks.toml 可添加或覆盖规则；扫描项目时会叠加仓库中的 .gitleaks.toml，但它不能替换这些规则。",
+      secretRulesReload: "重新加载规则",
+      secretRulesDropped: "{count} 条规则被配置文件禁用：",
       navSearch: "搜索设置",
       searchTitle: "搜索引擎配置",
       searchDesc: "配置 Spotlight 网络搜索（使用 ? 触发）时使用的引擎。",
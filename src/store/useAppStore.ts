@@ -18,6 +18,8 @@ export const DEFAULT_MODELS: AIModelConfig[] = [
     "provider": "Google",
     "contextLimit": 1048576,
     "inputPricePerMillion": 2.00,
+    "tokenizer": "gemini",
+    "tokenCountEstimated": true,
     "color": "bg-blue-600"
   },
   {
@@ -26,6 +28,8 @@ export const DEFAULT_MODELS: AIModelConfig[] = [
     "provider": "Other",
     "contextLimit": 2000000,
     "inputPricePerMillion": 0.20,
+    "tokenizer": "cl100k",
+    "tokenCountEstimated": true,
     "color": "bg-gray-900"
   },
   {
@@ -34,6 +38,8 @@ export const DEFAULT_MODELS: AIModelConfig[] = [
     "provider": "DeepSeek",
     "contextLimit": 128000,
     "inputPricePerMillion": 0.28,
+    "tokenizer": "cl100k",
+    "tokenCountEstimated": true,
     "color": "bg-purple-600"
   },
   {
@@ -42,6 +48,8 @@ export const DEFAULT_MODELS: AIModelConfig[] = [
     "provider": "Other",
     "contextLimit": 200000,
     "inputPricePerMillion": 0.6,
+    "tokenizer": "cl100k",
+    "tokenCountEstimated": true,
     "color": "bg-blue-400"
   }
 ];
@@ -1,7 +1,7 @@
 import { create } from 'zustand';
 import { persist, createJSONStorage } from 'zustand/middleware';
 import { fileStorage } from '@/lib/storage';
-import { IgnoreConfig, DEFAULT_PROJECT_IGNORE, FileNode } from '@/types/context';
+import { IgnoreConfig, DEFAULT_PROJECT_IGNORE, FileNode, OutputFormat, SkeletonOptions, AUTO_SKELETON_THRESHOLD, NotebookOptions, NOTEBOOK_OUTPUT_CHARS, SecretSort } from '@/types/context';
 import { invoke } from '@tauri-apps/api/core';
 
 const setAllChildren = (node: FileNode, isSelected: boolean): FileNode => {
@@ -81,11 +81,21 @@ const collectDirIds = (nodes: FileNode[]): string[] => {
 interface ContextState {
   projectIgnore: IgnoreConfig;
   removeComments: boolean;
+  outputFormat: OutputFormat;
+  skeletonPaths: string[];
+  autoSkeleton: boolean;
+  notebookOutputs: boolean;
+  includeRepoMap: boolean;
+  // 统计和拆分时按这个模型的分词器计数，为 null 时使用模型列表中的第一个
+  targetModelId: string | null;
 
   projectRoot: string | null;
   fileTree: FileNode[];
   isScanning: boolean;
   detectSecrets: boolean;
+  redactSecrets: boolean;
+  hideLowConfidenceSecrets: boolean;
+  secretSort: SecretSort;
 
   // 展开状态管理
   expandedIds: string[];
@@ -102,15 +112,35 @@ interface ContextState {
   toggleSelect: (nodeId: string, checked: boolean) => void;
   invertSelection: () => void;
   setRemoveComments: (enable: boolean) => void;
+  setOutputFormat: (format: OutputFormat) => void;
+  toggleSkeleton: (path: string) => void;
+  setAutoSkeleton: (enable: boolean) => void;
+  getSkeletonOptions: () => SkeletonOptions;
+  setNotebookOutputs: (enable: boolean) => void;
+  getNotebookOptions: () => NotebookOptions;
+  setIncludeRepoMap: (enable: boolean) => void;
+  setTargetModelId: (id: string | null) => void;
   setDetectSecrets: (enable: boolean) => void;
+  setRedactSecrets: (enable: boolean) => void;
+  setHideLowConfidenceSecrets: (enable: boolean) => void;
+  setSecretSort: (sort: SecretSort) => void;
 }
 
 export const useContextStore = create<ContextState>()(
   persist(
-    (set) => ({
+    (set, get) => ({
       projectIgnore: DEFAULT_PROJECT_IGNORE,
       removeComments: false,
+      outputFormat: 'xml',
+      skeletonPaths: [],
+      autoSkeleton: false,
+      notebookOutputs: false,
+      includeRepoMap: false,
+      targetModelId: null,
       detectSecrets: true,
+      redactSecrets: false,
+      hideLowConfidenceSecrets: false,
+      secretSort: 'position',
       projectRoot: null,
       fileTree: [],
       isScanning: false,
@@ -201,7 +231,30 @@ export const useContextStore = create<ContextState>()(
       })),
 
       setRemoveComments: (enable) => set({ removeComments: enable }),
+      setOutputFormat: (format) => set({ outputFormat: format }),
+
+      toggleSkeleton: (path) => set((state) => ({
+        skeletonPaths: state.skeletonPaths.includes(path)
+          ? state.skeletonPaths.filter(p => p !== path)
+          : [...state.skeletonPaths, path]
+      })),
+      setAutoSkeleton: (enable) => set({ autoSkeleton: enable }),
+
+      // 传给 Rust 端的骨架选项
+      getSkeletonOptions: () => {
+        const { skeletonPaths, autoSkeleton } = get();
+        return { paths: skeletonPaths, threshold: autoSkeleton ? AUTO_SKELETON_THRESHOLD : null };
+      },
+      setNotebookOutputs: (enable) => set({ notebookOutputs: enable }),
+
+      // 传给 Rust 端的 notebook 选项
+      getNotebookOptions: () => ({ include_outputs: get().notebookOutputs, max_output_chars: NOTEBOOK_OUTPUT_CHARS }),
+      setIncludeRepoMap: (enable) => set({ includeRepoMap: enable }),
+      setTargetModelId: (id) => set({ targetModelId: id }),
       setDetectSecrets: (enable) => set({ detectSecrets: enable }),
+      setRedactSecrets: (enable) => set({ redactSecrets: enable }),
+      setHideLowConfidenceSecrets: (enable) => set({ hideLowConfidenceSecrets: enable }),
+      setSecretSort: (sort) => set({ secretSort: sort }),
     }),
     {
       name: 'context-config',
@@ -209,7 +262,16 @@ export const useContextStore = create<ContextState>()(
       partialize: (state) => ({
         projectRoot: state.projectRoot,
         removeComments: state.removeComments,
+        outputFormat: state.outputFormat,
+        skeletonPaths: state.skeletonPaths,
+        autoSkeleton: state.autoSkeleton,
+        notebookOutputs: state.notebookOutputs,
+        includeRepoMap: state.includeRepoMap,
+        targetModelId: state.targetModelId,
         detectSecrets: state.detectSecrets,
+        redactSecrets: state.redactSecrets,
+        hideLowConfidenceSecrets: state.hideLowConfidenceSecrets,
+        secretSort: state.secretSort,
         expandedIds: state.expandedIds,
       }),
     }
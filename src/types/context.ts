@@ -29,12 +29,70 @@ export const DEFAULT_PROJECT_IGNORE: IgnoreConfig = {
   extensions: []
 };
 
+// 上下文输出格式，与 Rust 端 OutputFormat 对应
+export type OutputFormat = 'xml' | 'markdown' | 'json' | 'plain';
+
+export const OUTPUT_FORMAT_EXTENSIONS: Record<OutputFormat, string> = {
+  xml: 'xml',
+  markdown: 'md',
+  json: 'json',
+  plain: 'txt'
+};
+
+// 文档开头的说明和目录树，由 Rust 端按输出格式渲染
+export interface ContextHeader {
+  summary: string;
+  structure: string;
+  // 仓库符号图，见 Rust 端 context::repo_map
+  repo_map?: string;
+}
+
+// 骨架模式：只保留 import、类型声明和函数签名，与 Rust 端 SkeletonOptions 对应
+export interface SkeletonOptions {
+  paths: string[];
+  threshold: number | null;
+}
+
+// 开启自动骨架后，超过该大小的文件使用骨架模式
+export const AUTO_SKELETON_THRESHOLD = 100 * 1024;
+
+// Jupyter notebook 处理选项，与 Rust 端 NotebookOptions 对应
+export interface NotebookOptions {
+  include_outputs: boolean;
+  max_output_chars: number;
+}
+
+// 附带 notebook 输出时，每条输出保留的字符数
+export const NOTEBOOK_OUTPUT_CHARS = 2000;
+
+// 附带仓库符号图时的 token 预算
+export const REPO_MAP_TOKENS = 2000;
+
+// 与 Rust 端 gitleaks::Severity 对应
+export type SecretSeverity = 'Low' | 'Medium' | 'High' | 'Critical';
+
+// 与 Rust 端 gitleaks::Encoding 对应
+export type SecretEncoding = 'base64' | 'hex' | 'percent';
+
+// 解码后发现的密钥：text 为原文中的编码片段，encodings 从外到内
+export interface EncodedSegment {
+  encodings: SecretEncoding[];
+  text: string;
+}
+
+// 密钥扫描结果的排序：按位置，或按严重程度 + 置信度从高到低
+export type SecretSort = 'position' | 'risk';
+
+// 置信度（0-100）低于该值视为低置信度，可在结果中隐藏
+export const LOW_CONFIDENCE = 40;
+
 export interface FileNode {
   id: string;
   name: string;
   path: string;
   kind: 'file' | 'dir';
   size?: number;
+  isBinary?: boolean;
   isSelected: boolean;
   isPartial?: boolean;
   isExpanded?: boolean;
@@ -15,4 +15,12 @@ export interface FileMeta {
   size: number;
   previewType: PreviewType;
   mime: string;
+  /** 文本类文件检测到的编码，如 "UTF-8"、"GBK"、"UTF-16LE" */
+  encoding: string | null;
+}
+
+/** read_preview_text 的返回值，内容已转成 UTF-8 */
+export interface PreviewText {
+  content: string;
+  encoding: string;
 }
@@ -1,9 +1,25 @@
+// 与 Rust 端 context::tokenizer::TokenizerKind 对应
+export type TokenizerKind = 'cl100k' | 'o200k' | 'claude' | 'gemini';
+
+// 未指定分词器的模型按 cl100k 计数
+export const tokenizerOf = (model?: AIModelConfig | null): TokenizerKind => model?.tokenizer ?? 'cl100k';
+
+// Claude / Gemini 没有本地分词器，Rust 端按 cl100k 结果乘系数估算
+const isExactTokenizer = (kind: TokenizerKind) => kind === 'cl100k' || kind === 'o200k';
+
+// 模型配置可以显式标注为估算（例如借用 cl100k 计数的 DeepSeek、Qwen），否则按分词器判断
+export const isEstimatedCount = (model?: AIModelConfig | null) =>
+  model?.tokenCountEstimated ?? !isExactTokenizer(tokenizerOf(model));
+
 export interface AIModelConfig {
   id: string;
   name: string;
   provider: 'OpenAI' | 'Anthropic' | 'Google' | 'DeepSeek' | 'Other';
   contextLimit: number;
   inputPricePerMillion: number;
+  tokenizer?: TokenizerKind;
+  // 该分词器不是模型自己的，Token 数只是估算
+  tokenCountEstimated?: boolean;
   color?: string;
 }
 